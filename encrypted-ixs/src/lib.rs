@@ -13,18 +13,27 @@ mod circuits {
     }
 
     #[instruction]
-    pub fn encrypt_quiz(input_ctxt: Enc<Shared, QuizEncryptInput>) -> Enc<Shared, [u8; 64]> {
+    pub fn encrypt_quiz(
+        input_ctxt: Enc<Shared, QuizEncryptInput>,
+        chunk_index: u8,
+        chunk_count: u8,
+    ) -> [u8; 66] {
         let input = input_ctxt.to_arcis();
         
-        // Use addition-based encryption instead of XOR
-        let mut encrypted = [0u8; 64];
+        // Addition-based encryption, mod 256 so decrypt_quiz inverts it for every byte
+        let mut encrypted = [0u8; 66];
         let nonce_bytes = input.nonce.to_le_bytes();
         
         for i in 0..64 {
-            encrypted[i] = input.question_data[i] + nonce_bytes[i % 16];
+            encrypted[i] = input.question_data[i].wrapping_add(nonce_bytes[i % 16]);
         }
         
-        input_ctxt.owner.from_arcis(encrypted)
+        // Chunk position passed through so the callback can label the output
+        encrypted[64] = chunk_index;
+        encrypted[65] = chunk_count;
+        
+        // Revealed so the callback can hand the bytes back to the client
+        encrypted.reveal()
     }
 
     // ===== QUIZ DECRYPTION CIRCUIT =====
@@ -36,71 +45,234 @@ mod circuits {
     }
 
     #[instruction]
-    pub fn decrypt_quiz(input_ctxt: Enc<Shared, QuizDecryptInput>) -> Enc<Shared, [u8; 64]> {
+    pub fn decrypt_quiz(input_ctxt: Enc<Shared, QuizDecryptInput>) -> [u8; 64] {
         let input = input_ctxt.to_arcis();
         
-        // Use subtraction-based decryption
+        // Subtraction-based decryption, the exact inverse of encrypt_quiz
         let mut decrypted = [0u8; 64];
         let nonce_bytes = input.nonce.to_le_bytes();
         
         for i in 0..64 {
-            decrypted[i] = input.encrypted_data[i] - nonce_bytes[i % 16];
+            decrypted[i] = input.encrypted_data[i].wrapping_sub(nonce_bytes[i % 16]);
         }
         
-        input_ctxt.owner.from_arcis(decrypted)
+        decrypted.reveal()
     }
 
     // ===== ANSWER VALIDATION CIRCUIT =====
-    // Compare user answer with correct answer (y-coordinate)
+    // Compare user answer with correct answer (y-coordinate) and any other
+    // acceptable answers stored on the question block
     
     pub struct AnswerValidationInput {
         user_answer: [u8; 64],      // User answer (64 bytes)
         correct_answer: [u8; 64],   // Correct answer encrypted (64 bytes)
+        alt_answers: [[u8; 64]; 3], // Other acceptable answers, encrypted like correct_answer
+        alt_count: u8,              // Acceptable answers in use in alt_answers
         nonce: u128,                // Nonce for decryption
     }
 
+    // Lowercase ASCII letters and drop leading/trailing spaces and nulls, so
+    // "Paris", "paris" and " PARIS " compare equal. The trimmed text is moved
+    // to the front and null-padded. The shift depends on the secret text, so
+    // every output byte is selected over all positions with constant bounds.
+    fn normalize_answer(answer: [u8; 64]) -> [u8; 64] {
+        let mut lowered = [0u8; 64];
+        let mut start = 64;
+        let mut end = 0;
+        for i in 0..64 {
+            let byte = answer[i];
+            lowered[i] = if byte >= b'A' && byte <= b'Z' { byte + 32 } else { byte };
+
+            // Scan backwards for start so the first non-blank byte wins
+            let back = answer[63 - i];
+            if back != 0 && back != b' ' {
+                start = 63 - i;
+            }
+            if byte != 0 && byte != b' ' {
+                end = i + 1;
+            }
+        }
+
+        let mut normalized = [0u8; 64];
+        for i in 0..64 {
+            for j in 0..64 {
+                if j == i + start && j < end {
+                    normalized[i] = lowered[j];
+                }
+            }
+        }
+        normalized
+    }
+
     #[instruction]
-    pub fn validate_answer(input_ctxt: Enc<Shared, AnswerValidationInput>) -> Enc<Shared, bool> {
+    pub fn validate_answer(input_ctxt: Enc<Shared, AnswerValidationInput>) -> bool {
         let input = input_ctxt.to_arcis();
-        
-        // Use subtraction-based decryption
-        let mut decrypted_correct = [0u8; 64];
         let nonce_bytes = input.nonce.to_le_bytes();
+        let user_answer = normalize_answer(input.user_answer);
         
-        for i in 0..64 {
-            decrypted_correct[i] = input.correct_answer[i] - nonce_bytes[i % 16];
+        // Slot 0 is the primary answer; slot c > 0 is alt_answers[c - 1]
+        let mut is_correct = false;
+        for c in 0..4 {
+            // Use subtraction-based decryption
+            let mut decrypted_correct = [0u8; 64];
+            for i in 0..64 {
+                let encrypted = if c == 0 { input.correct_answer[i] } else { input.alt_answers[c - 1][i] };
+                decrypted_correct[i] = encrypted.wrapping_sub(nonce_bytes[i % 16]);
+            }
+            let correct_answer = normalize_answer(decrypted_correct);
+            
+            // Both sides are null-padded after normalization, so compare every byte
+            let mut matches = true;
+            for i in 0..64 {
+                if user_answer[i] != correct_answer[i] {
+                    matches = false;
+                }
+            }
+            
+            if matches && (c == 0 || (c as u8) <= input.alt_count) {
+                is_correct = true;
+            }
         }
         
-        // FIXED: Better comparison logic without break statement
-        let mut is_correct = true;
+        // Only the verdict is revealed; the answers stay encrypted
+        is_correct.reveal()
+    }
+
+    // ===== SHORT ANSWER VALIDATION CIRCUIT =====
+    // Same comparison as validate_answer over 32-byte buffers for short-answer quizzes
+    
+    pub struct AnswerValidationShortInput {
+        user_answer: [u8; 32],      // User answer (32 bytes)
+        correct_answer: [u8; 32],   // Correct answer encrypted (32 bytes)
+        nonce: u128,                // Nonce for decryption
+    }
+
+    #[instruction]
+    pub fn validate_answer_short(input_ctxt: Enc<Shared, AnswerValidationShortInput>) -> bool {
+        let input = input_ctxt.to_arcis();
+        let nonce_bytes = input.nonce.to_le_bytes();
         
-        // Find the end of the actual answer text (before null bytes)
-        let mut user_answer_end = 0;
-        let mut correct_answer_end = 0;
+        // Widen both sides to 64 bytes so they go through the same
+        // normalize_answer as validate_answer
+        let mut user_padded = [0u8; 64];
+        let mut correct_padded = [0u8; 64];
+        for i in 0..32 {
+            user_padded[i] = input.user_answer[i];
+            correct_padded[i] = input.correct_answer[i].wrapping_sub(nonce_bytes[i % 16]);
+        }
+        let user_answer = normalize_answer(user_padded);
+        let correct_answer = normalize_answer(correct_padded);
         
+        // Both sides are null-padded after normalization, so compare every byte
+        let mut is_correct = true;
         for i in 0..64 {
-            if input.user_answer[i] != 0 {
-                user_answer_end = i + 1;
+            if user_answer[i] != correct_answer[i] {
+                is_correct = false;
             }
-            if decrypted_correct[i] != 0 {
-                correct_answer_end = i + 1;
+        }
+        
+        // Only the verdict is revealed; the answers stay encrypted
+        is_correct.reveal()
+    }
+
+    // ===== CORRECT ANSWER VERIFICATION CIRCUIT =====
+    // Check a host-supplied answer against the stored correct answer; only the
+    // match bit is revealed, never the plaintext
+    
+    #[instruction]
+    pub fn verify_correct_answer(
+        supplied_ctxt: Enc<Shared, [u128; 4]>,
+        nonce: u128,
+        correct_answer: [u8; 64],
+    ) -> bool {
+        let supplied = supplied_ctxt.to_arcis();
+        
+        let nonce_bytes = nonce.to_le_bytes();
+        let mut matches = true;
+        
+        for word in 0..4 {
+            let supplied_bytes = supplied[word].to_le_bytes();
+            for j in 0..16 {
+                let i = word * 16 + j;
+                let decrypted = correct_answer[i].wrapping_sub(nonce_bytes[i % 16]);
+                if supplied_bytes[j] != decrypted {
+                    matches = false;
+                }
             }
         }
         
-        // Compare only the meaningful parts
-        let max_len = if user_answer_end > correct_answer_end {
-            user_answer_end
-        } else {
-            correct_answer_end
-        };
+        matches.reveal()
+    }
+
+    // ===== CROSSCHECK CIRCUIT =====
+    // Runs the quiz circuits' nonce arithmetic on a public sample and reveals the
+    // result, so operators can compare clusters against a known-good vector
+    
+    #[instruction]
+    pub fn crosscheck_circuit(sample: [u8; 32], nonce: u128) -> [u8; 32] {
+        let nonce_bytes = nonce.to_le_bytes();
+        let mut result = [0u8; 32];
+        
+        for i in 0..32 {
+            result[i] = sample[i].wrapping_sub(nonce_bytes[i % 16]);
+        }
+        
+        result.reveal()
+    }
+
+    // ===== KEY ROTATION CIRCUIT =====
+    // Re-encrypt a question block's x and y coordinates from the old nonce to a
+    // new one; x comes back in the first 64 bytes, y in the last 64
+    
+    #[instruction]
+    pub fn rotate_block(
+        encrypted_x: [u8; 64],
+        encrypted_y: [u8; 64],
+        old_nonce: u128,
+        new_nonce: u128,
+    ) -> [u8; 128] {
+        let old_bytes = old_nonce.to_le_bytes();
+        let new_bytes = new_nonce.to_le_bytes();
+        let mut rotated = [0u8; 128];
         
-        // FIXED: Use constant loop bound and flag-based logic
         for i in 0..64 {
-            if i < max_len && input.user_answer[i] != decrypted_correct[i] {
-                is_correct = false;
-            }
+            rotated[i] = encrypted_x[i].wrapping_sub(old_bytes[i % 16]).wrapping_add(new_bytes[i % 16]);
+            rotated[64 + i] = encrypted_y[i].wrapping_sub(old_bytes[i % 16]).wrapping_add(new_bytes[i % 16]);
+        }
+        
+        rotated.reveal()
+    }
+
+    // ===== HINT REVEAL CIRCUIT =====
+    // Decrypt one progressive hint (additive nonce scheme) and hand it back
+    // encrypted to the requesting player's key, so only the player who paid
+    // the penalty can read it
+    
+    #[instruction]
+    pub fn reveal_hint(player: Shared, encrypted_hint: [u8; 32], nonce: u128) -> Enc<Shared, [u8; 32]> {
+        let nonce_bytes = nonce.to_le_bytes();
+        let mut hint = [0u8; 32];
+        
+        for i in 0..32 {
+            hint[i] = encrypted_hint[i].wrapping_sub(nonce_bytes[i % 16]);
+        }
+        
+        player.from_arcis(hint)
+    }
+
+    // ===== MISTAKE REVEAL CIRCUIT =====
+    // Decrypt a question's stored answer and hand it back encrypted to the
+    // requesting player's key, so only that player can read it
+    
+    #[instruction]
+    pub fn reveal_answer(player: Shared, correct_answer: [u8; 64], nonce: u128) -> Enc<Shared, [u8; 64]> {
+        let nonce_bytes = nonce.to_le_bytes();
+        let mut answer = [0u8; 64];
+        
+        for i in 0..64 {
+            answer[i] = correct_answer[i].wrapping_sub(nonce_bytes[i % 16]);
         }
         
-        input_ctxt.owner.from_arcis(is_correct)
+        player.from_arcis(answer)
     }
 }
@@ -202,7 +202,7 @@ class CompleteSystemDemo {
       // Create quiz set
       const rewardAmount = 0.05 * 1_000_000_000; // 0.05 SOL in lamports
       const tx = await this.program.methods
-        .createQuizSet(uniqueName, questions.length, uniqueId, new BN(rewardAmount))
+        .createQuizSet(uniqueName, questions.length, uniqueId, new BN(rewardAmount), new BN(rewardAmount), new BN(0))
         .accountsPartial({
           quizSet: quizSetPda,
           topic: topicPda,
@@ -272,7 +272,10 @@ class CompleteSystemDemo {
         Array.from(encryptedX),
         Array.from(encryptedY),
         Array.from(arciumPubkey),
-        new BN(nonce)
+        new BN(nonce),
+        0, // unweighted
+        false, // not a bonus question
+        0 // default category
       )
       .accountsPartial({
         questionBlock: questionBlockPda,
@@ -574,13 +574,24 @@ class QuizDecryption {
         console.log(`   🚀 Proceeding with reward claim...`);
         
         const tx = await this.program.methods
-            .claimReward()
+            .claimReward(null)
             .accountsPartial({
                 quizSet: new PublicKey(quizSetPda),
                 vault: vaultPda,
                 claimer: this.authority.publicKey,
                 systemProgram: anchor.web3.SystemProgram.programId,
             })
+            // Question blocks are re-hashed on claim to detect post-finalize changes
+            .remainingAccounts(
+                Array.from({ length: quizSetAccount.questionCount }, (_, i) => ({
+                    pubkey: PublicKey.findProgramAddressSync(
+                        [Buffer.from("question_block"), quizSetPubkey.toBuffer(), Buffer.from([i + 1])],
+                        this.program.programId
+                    )[0],
+                    isSigner: false,
+                    isWritable: false,
+                }))
+            )
             .signers([this.authority])
             .rpc({ commitment: "confirmed" });
         
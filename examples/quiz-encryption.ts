@@ -72,7 +72,14 @@ class SecureQuizEncryptor {
     
     try {
       const tx = await this.program.methods
-        .createQuizSet(uniqueName, questionCount, uniqueId, new BN(rewardAmount * 1_000_000_000)) // Convert SOL to lamports
+        .createQuizSet(
+          uniqueName,
+          questionCount,
+          uniqueId,
+          new BN(rewardAmount * 1_000_000_000), // Convert SOL to lamports
+          new BN(rewardAmount * 1_000_000_000), // Fully funded up front
+          new BN(0) // No refund deadline
+        )
         .accountsPartial({
           quizSet: quizSetPda,
           topic: topicPda,
@@ -185,7 +192,10 @@ class SecureQuizEncryptor {
           Array.from(encryptedX),
           Array.from(encryptedY),
           Array.from(arciumPubkey),
-          uniqueNonce
+          uniqueNonce,
+          0, // unweighted
+          false, // not a bonus question
+          0 // default category
         )
         .accountsPartial({
           questionBlock: questionBlockPda,
@@ -1,11 +1,146 @@
 use anchor_lang::prelude::*;
 use arcium_anchor::prelude::*;
 use arcium_client::idl::arcium::types::CallbackAccount;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use anchor_spl::token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked};
 
 // ===== ARCIUM CONSTANTS =====
+// Bytes reserved for topic and quiz set names. Accounts are always created at
+// their full LEN, so a later rename never needs more space than was allocated.
+const MAX_NAME_LEN: usize = 100;
+// Answer buffer sizes supported by the validation circuits
+const ANSWER_BUFFER_SIZE: u8 = 64;
+const ANSWER_BUFFER_SIZE_SHORT: u8 = 32;
+// Maximum number of quiz sets pooled into one series
+const MAX_SERIES_QUIZZES: usize = 10;
+// Maximum entries kept on a topic leaderboard
+const MAX_LEADERBOARD_ENTRIES: usize = 100;
+// Rank reported for users who aren't on the leaderboard (ranks are 1-based)
+const UNRANKED: u32 = 0;
+// Maximum players sharing the win with the first finisher on an exact tie
+const MAX_CO_WINNERS: usize = 4;
+// Winner slots a quiz can split its reward across (the first finisher plus
+// co-winners, so bounded by MAX_CO_WINNERS)
+const MAX_WINNERS: u8 = MAX_CO_WINNERS as u8 + 1;
+// Maximum questions per quiz set. Question block PDAs are seeded with the index
+// as a single byte, and per-question bitmaps are u64, so it must stay <= 64.
+const MAX_QUESTION_COUNT: u8 = 50;
+const _: () = assert!(MAX_QUESTION_COUNT <= 64);
+// Maximum reward currencies tracked in a topic's escrow statistics
+const MAX_REWARD_MINTS: usize = 8;
+// Key under which native SOL rewards are tallied in TopicRewardStats
+const NATIVE_SOL_MINT: Pubkey = Pubkey::new_from_array([0u8; 32]);
+// Accounts a batch instruction may take via remaining_accounts. The heaviest
+// batch (get_topic_quiz_summaries: one QuizSet deserialize and one event per
+// account) costs roughly 10k CU per entry, so 16 stays inside the default 200k
+// budget; larger batches fail up front with BatchTooLarge instead of running
+// out of compute halfway and still charging the fee.
+const MAX_BATCH: usize = 16;
+// Bytes per quiz set in a summary batch's hashed records (see quiz_summary_record)
+const QUIZ_SUMMARY_RECORD_LEN: usize = 32 + 8 + 1 + 1 + 1 + 1;
+// Share of an abandoned quiz's remaining reward paid to the keeper who settles it
+const KEEPER_FEE_BPS: u64 = 100;
+// Progressive hints an author can attach to a question
+const MAX_HINTS: usize = 3;
+const HINT_SIZE: usize = 32;
+// Score deducted per hint step: the nth hint on a question costs n times this
+const HINT_PENALTY_POINTS: u32 = 1;
+// Acceptable answers a question can have besides its primary one
+const MAX_ALT_ANSWERS: usize = 3;
+// Answers a player can hold in their offline queue while Arcium is unavailable
+const MAX_QUEUED_ANSWERS: usize = 8;
+// Question text and options are packed as length-prefixed fields and split
+// into 64-byte chunks, one encrypt_quiz computation per chunk
+const QUESTION_CHUNK_SIZE: usize = 64;
+const MAX_QUESTION_CHUNKS: usize = 4;
+// Subtopic categories a quiz's questions can be tagged with
+const MAX_CATEGORIES: usize = 8;
+// Bumped whenever fields are appended to QuestionBlock
+const QUESTION_BLOCK_VERSION: u8 = 8;
+// Question blocks per add_encrypted_question_blocks_batch call. Each payload
+// is 149 bytes of instruction data plus a 32-byte account key, so five, with
+// the quiz set, topic, authority and system program, just fit Solana's
+// 1232-byte transaction limit.
+const MAX_QUESTION_BLOCK_BATCH: usize = 5;
+// Completion-time brackets a quiz can scale winner payouts by
+const MAX_REWARD_BRACKETS: usize = 4;
+
 const COMP_DEF_OFFSET_VALIDATE_ANSWER: u32 = comp_def_offset("validate_answer");
+const COMP_DEF_OFFSET_VALIDATE_ANSWER_SHORT: u32 = comp_def_offset("validate_answer_short");
+const COMP_DEF_OFFSET_VERIFY_CORRECT_ANSWER: u32 = comp_def_offset("verify_correct_answer");
+const COMP_DEF_OFFSET_CROSSCHECK_CIRCUIT: u32 = comp_def_offset("crosscheck_circuit");
+const COMP_DEF_OFFSET_ROTATE_BLOCK: u32 = comp_def_offset("rotate_block");
 const COMP_DEF_OFFSET_DECRYPT_QUIZ: u32 = comp_def_offset("decrypt_quiz");
+const COMP_DEF_OFFSET_REVEAL_HINT: u32 = comp_def_offset("reveal_hint");
 const COMP_DEF_OFFSET_ENCRYPT_QUIZ: u32 = comp_def_offset("encrypt_quiz");
+const COMP_DEF_OFFSET_REVEAL_ANSWER: u32 = comp_def_offset("reveal_answer");
+// Computation definitions checked by preflight_arcium, in remaining_accounts
+// order; circuit i is reported on bit PREFLIGHT_COMP_DEF_BIT + i
+const PREFLIGHT_COMP_DEFS: [u32; 9] = [
+    COMP_DEF_OFFSET_VALIDATE_ANSWER,
+    COMP_DEF_OFFSET_VALIDATE_ANSWER_SHORT,
+    COMP_DEF_OFFSET_VERIFY_CORRECT_ANSWER,
+    COMP_DEF_OFFSET_CROSSCHECK_CIRCUIT,
+    COMP_DEF_OFFSET_ROTATE_BLOCK,
+    COMP_DEF_OFFSET_DECRYPT_QUIZ,
+    COMP_DEF_OFFSET_REVEAL_HINT,
+    COMP_DEF_OFFSET_ENCRYPT_QUIZ,
+    COMP_DEF_OFFSET_REVEAL_ANSWER,
+];
+const PREFLIGHT_COMP_DEF_BIT: usize = 4;
+
+// ===== COMPUTATION FEE ESTIMATE =====
+// Rough lamport cost of one queued computation (Arcium fee plus callback
+// transaction), checked against the payer before queuing.
+#[constant]
+pub const COMPUTATION_BASE_FEE_LAMPORTS: u64 = 2_000_000;
+#[constant]
+pub const COMPUTATION_FEE_PER_ARGUMENT_LAMPORTS: u64 = 5_000;
+
+// Estimated lamports a payer needs to queue a computation with `argument_count` arguments
+pub fn estimate_computation_fee(argument_count: usize) -> u64 {
+    COMPUTATION_BASE_FEE_LAMPORTS
+        .saturating_add(COMPUTATION_FEE_PER_ARGUMENT_LAMPORTS.saturating_mul(argument_count as u64))
+}
+
+// ===== RATE HELPERS =====
+// Every rate in a stats event is in basis points (0-10000), never a percentage
+// or a fraction. 1 of 3 is 3333; nothing out of nothing is 0.
+pub fn win_rate_bps(wins: u32, total: u32) -> u16 {
+    if total == 0 {
+        return 0;
+    }
+    let bps = (wins as u64 * 10_000) / total as u64;
+    std::cmp::min(bps, 10_000) as u16
+}
+
+// ===== COMPUTATION OFFSET DERIVATION =====
+// Answer submissions must use this offset. It differs per player, quiz and
+// question, and the per-player submission counter makes every retry fresh, so
+// concurrent or repeated submissions never collide on the computation PDA.
+// Clients read `submission_count` from the player's PlayerProgress (0 before
+// their first answer) and take the first 8 bytes of
+// sha256("k3hoot:offset" || user || quiz_set || question_index || submission_count_le)
+// as a little-endian u64.
+pub fn derive_computation_offset(
+    user: &Pubkey,
+    quiz_set: &Pubkey,
+    question_index: u8,
+    submission_count: u32,
+) -> u64 {
+    let hash = hashv(&[
+        b"k3hoot:offset".as_ref(),
+        user.as_ref(),
+        quiz_set.as_ref(),
+        &[question_index],
+        &submission_count.to_le_bytes(),
+    ]);
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&hash.to_bytes()[..8]);
+    u64::from_le_bytes(bytes)
+}
 
 declare_id!("4K3zoVTLgNxm7eyNkHhQQUvQgoq5T4wTmrnkH7nZ6XJa");
 
@@ -16,20 +151,132 @@ pub mod k_3_hoot_program_arcium {
     // ===== ARCIUM COMPUTATION DEFINITIONS =====
 
     pub fn init_validate_answer_comp_def(ctx: Context<InitValidateAnswerCompDef>) -> Result<()> {
+        if comp_def_already_initialized(&ctx.accounts.comp_def_account, "validate_answer") {
+            return Ok(());
+        }
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    pub fn init_validate_answer_short_comp_def(ctx: Context<InitValidateAnswerShortCompDef>) -> Result<()> {
+        if comp_def_already_initialized(&ctx.accounts.comp_def_account, "validate_answer_short") {
+            return Ok(());
+        }
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    pub fn init_verify_correct_answer_comp_def(ctx: Context<InitVerifyCorrectAnswerCompDef>) -> Result<()> {
+        if comp_def_already_initialized(&ctx.accounts.comp_def_account, "verify_correct_answer") {
+            return Ok(());
+        }
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    pub fn init_crosscheck_circuit_comp_def(ctx: Context<InitCrosscheckCircuitCompDef>) -> Result<()> {
+        if comp_def_already_initialized(&ctx.accounts.comp_def_account, "crosscheck_circuit") {
+            return Ok(());
+        }
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    pub fn init_rotate_block_comp_def(ctx: Context<InitRotateBlockCompDef>) -> Result<()> {
+        if comp_def_already_initialized(&ctx.accounts.comp_def_account, "rotate_block") {
+            return Ok(());
+        }
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    pub fn init_reveal_hint_comp_def(ctx: Context<InitRevealHintCompDef>) -> Result<()> {
+        if comp_def_already_initialized(&ctx.accounts.comp_def_account, "reveal_hint") {
+            return Ok(());
+        }
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    pub fn init_reveal_answer_comp_def(ctx: Context<InitRevealAnswerCompDef>) -> Result<()> {
+        if comp_def_already_initialized(&ctx.accounts.comp_def_account, "reveal_answer") {
+            return Ok(());
+        }
         init_comp_def(ctx.accounts, true, 0, None, None)?;
         Ok(())
     }
 
     pub fn init_encrypt_quiz_comp_def(ctx: Context<InitEncryptQuizCompDef>) -> Result<()> {
+        if comp_def_already_initialized(&ctx.accounts.comp_def_account, "encrypt_quiz") {
+            return Ok(());
+        }
         init_comp_def(ctx.accounts, true, 0, None, None)?;
         Ok(())
     }
 
     pub fn init_decrypt_quiz_comp_def(ctx: Context<InitDecryptQuizCompDef>) -> Result<()> {
+        if comp_def_already_initialized(&ctx.accounts.comp_def_account, "decrypt_quiz") {
+            return Ok(());
+        }
         init_comp_def(ctx.accounts, true, 0, None, None)?;
         Ok(())
     }
 
+    // ===== ARCIUM PREFLIGHT =====
+
+    // Check every Arcium account a session will queue against before it starts.
+    // Nothing fails here: each account that doesn't match its derived address or
+    // isn't initialized sets a bit in missing_mask (0 MXE, 1 cluster, 2 fee pool,
+    // 3 clock, then one per PREFLIGHT_COMP_DEFS entry, passed as remaining accounts).
+    pub fn preflight_arcium(ctx: Context<PreflightArcium>) -> Result<()> {
+        let arcium_program = ctx.accounts.arcium_program.key();
+        let resolves = |account: Option<&AccountInfo>, expected: Option<Pubkey>| match (account, expected) {
+            (Some(account), Some(expected)) => {
+                account.key() == expected && !account.data_is_empty() && account.owner == &arcium_program
+            }
+            _ => false,
+        };
+
+        let mxe_info = ctx.accounts.mxe_account.to_account_info();
+        let mxe_ok = resolves(Some(&mxe_info), Some(derive_mxe_pda!()));
+        let expected_cluster = if mxe_ok {
+            Account::<MXEAccount>::try_from(&mxe_info)
+                .and_then(|mxe_account| Ok(derive_cluster_pda!(mxe_account)))
+                .ok()
+        } else {
+            None
+        };
+
+        let mut checks = vec![
+            mxe_ok,
+            resolves(Some(&ctx.accounts.cluster_account.to_account_info()), expected_cluster),
+            resolves(Some(&ctx.accounts.pool_account.to_account_info()), Some(ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)),
+            resolves(Some(&ctx.accounts.clock_account.to_account_info()), Some(ARCIUM_CLOCK_ACCOUNT_ADDRESS)),
+        ];
+        for (i, offset) in PREFLIGHT_COMP_DEFS.iter().enumerate() {
+            checks.push(resolves(ctx.remaining_accounts.get(i), Some(derive_comp_def_pda!(*offset))));
+        }
+
+        let missing_mask = checks
+            .iter()
+            .enumerate()
+            .filter(|(_, ok)| !**ok)
+            .fold(0u32, |mask, (bit, _)| mask | (1 << bit));
+
+        emit!(ArciumPreflight {
+            all_ok: missing_mask == 0,
+            missing_mask,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        if missing_mask == 0 {
+            msg!("✅ Arcium preflight passed");
+        } else {
+            msg!("⚠️ Arcium preflight failed, missing mask {:#b}", missing_mask);
+        }
+        Ok(())
+    }
+
     // ===== QUIZ ENCRYPTION/DECRYPTION FUNCTIONS =====
 
     pub fn encrypt_quiz_data(
@@ -39,33 +286,29 @@ pub mod k_3_hoot_program_arcium {
         options: [String; 4],
         _correct_answer: String,
         nonce: u128,
+        chunk_index: u8,
     ) -> Result<()> {
         ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
-        // Combine question + options into single data block
-        let mut combined_data = [0u8; 64]; // Changed to 64 bytes
-        let question_bytes = question_text.as_bytes();
-        let mut offset = 0;
-        
-        // Copy question text
-        let q_len = std::cmp::min(question_bytes.len(), 32);
-        combined_data[..q_len].copy_from_slice(&question_bytes[..q_len]);
-        offset += q_len;
-        
-        // Copy options
-        for option in options.iter() {
-            let option_bytes = option.as_bytes();
-            let o_len = std::cmp::min(option_bytes.len(), 8);
-            if offset + o_len < 64 {
-                combined_data[offset..offset + o_len].copy_from_slice(&option_bytes[..o_len]);
-                offset += o_len;
-            }
-        }
+        validate_options(&options)?;
+
+        // Long questions span several chunks; each call encrypts one of them
+        let chunks = pack_question_chunks(&question_text, &options)?;
+        let combined_data = chunks
+            .get(chunk_index as usize)
+            .ok_or(QuizError::InvalidQuestionChunk)?;
 
         // FIXED: Send data as individual bytes since PlaintextU8Array doesn't exist
         let mut args = vec![Argument::PlaintextU128(nonce)];
         for i in 0..64 {
             args.push(Argument::PlaintextU8(combined_data[i]));
         }
+        args.push(Argument::PlaintextU8(chunk_index));
+        args.push(Argument::PlaintextU8(chunks.len() as u8));
+
+        require!(
+            ctx.accounts.payer.lamports() >= estimate_computation_fee(args.len()),
+            QuizError::InsufficientComputeFee
+        );
 
         queue_computation(
             ctx.accounts, 
@@ -80,7 +323,7 @@ pub mod k_3_hoot_program_arcium {
             ])], 
         )?;
 
-        msg!("Quiz data encryption queued");
+        msg!("Quiz data encryption queued (chunk {} of {})", chunk_index + 1, chunks.len());
         Ok(())
     }
 
@@ -97,6 +340,11 @@ pub mod k_3_hoot_program_arcium {
             args.push(Argument::PlaintextU8(encrypted_data[i]));
         }
 
+        require!(
+            ctx.accounts.payer.lamports() >= estimate_computation_fee(args.len()),
+            QuizError::InsufficientComputeFee
+        );
+
         queue_computation(
             ctx.accounts, 
             computation_offset, 
@@ -114,6 +362,27 @@ pub mod k_3_hoot_program_arcium {
         Ok(())
     }
 
+    // ===== PROGRAM CONFIG FUNCTIONS =====
+
+    // One-time setup of program-wide limits. The program's upgrade authority
+    // becomes the admin, so quiz authors can't lift the limits on themselves.
+    pub fn initialize_program_config(ctx: Context<InitializeProgramConfig>, max_active_quizzes: u16) -> Result<()> {
+        let program_config = &mut ctx.accounts.program_config;
+        program_config.admin = ctx.accounts.admin.key();
+        program_config.max_active_quizzes = max_active_quizzes;
+
+        msg!("Program config initialized, admin {}", program_config.admin);
+        Ok(())
+    }
+
+    // Limit how many unsettled quizzes each author may have open (0 = unlimited)
+    pub fn set_max_active_quizzes(ctx: Context<ConfigureProgram>, max_active_quizzes: u16) -> Result<()> {
+        ctx.accounts.program_config.max_active_quizzes = max_active_quizzes;
+
+        msg!("Max active quizzes per author: {}", max_active_quizzes);
+        Ok(())
+    }
+
     // ===== TOPIC MANAGEMENT FUNCTIONS =====
 
     pub fn create_topic(
@@ -121,7 +390,7 @@ pub mod k_3_hoot_program_arcium {
         name: String,
     ) -> Result<()> {
         require!(name.len() > 0, QuizError::EmptyName);
-        require!(name.len() <= 100, QuizError::NameTooLong);
+        require!(name.len() <= MAX_NAME_LEN, QuizError::NameTooLong);
 
         let topic = &mut ctx.accounts.topic;
         topic.owner = ctx.accounts.owner.key();
@@ -132,6 +401,15 @@ pub mod k_3_hoot_program_arcium {
         topic.is_active = true;
         topic.min_reward_amount = 10_000_000; // 0.01 SOL in lamports
         topic.min_question_count = 3;
+        topic.allowed_reward_mint = None;
+        topic.require_distinct_answers = false;
+        topic.allow_authority_play = false;
+        topic.leaderboard_min_score = 0;
+
+        // Every escrow movement in the topic is booked here from the start
+        let reward_stats = &mut ctx.accounts.reward_stats;
+        reward_stats.topic = topic.key();
+        reward_stats.entries = Vec::new();
 
         emit!(TopicCreated {
             topic: topic.key(),
@@ -181,6 +459,61 @@ pub mod k_3_hoot_program_arcium {
         Ok(())
     }
 
+    // Restrict every quiz in the topic to one reward mint (None = any currency)
+    pub fn set_topic_reward_mint(
+        ctx: Context<ConfigureTopic>,
+        allowed_reward_mint: Option<Pubkey>,
+    ) -> Result<()> {
+        let topic = &mut ctx.accounts.topic;
+        topic.allowed_reward_mint = allowed_reward_mint;
+
+        msg!("Topic '{}' reward mint restriction: {:?}", topic.name, allowed_reward_mint);
+        Ok(())
+    }
+
+    // Quality gate: reject quizzes whose correct answers are all the same
+    pub fn set_topic_distinct_answers(
+        ctx: Context<ConfigureTopic>,
+        require_distinct_answers: bool,
+    ) -> Result<()> {
+        let topic = &mut ctx.accounts.topic;
+        topic.require_distinct_answers = require_distinct_answers;
+
+        msg!("Topic '{}' requires distinct answers: {}", topic.name, require_distinct_answers);
+        Ok(())
+    }
+
+    // Let quiz authorities answer and win their own quizzes (test environments).
+    // Quizzes copy the setting when they are created. The topic owner is who
+    // would profit, so the program admin must co-sign.
+    pub fn set_topic_allow_authority_play(
+        ctx: Context<ConfigureTopicAsAdmin>,
+        allow_authority_play: bool,
+    ) -> Result<()> {
+        let topic = &mut ctx.accounts.topic;
+        topic.allow_authority_play = allow_authority_play;
+
+        msg!("Topic '{}' allows authority play: {}", topic.name, allow_authority_play);
+        Ok(())
+    }
+
+    // Keep users whose topic score (quizzes won) is below this off the topic
+    // leaderboard (0 = everyone). Existing entries below it are dropped now.
+    pub fn set_leaderboard_min_score(
+        ctx: Context<SetLeaderboardMinScore>,
+        leaderboard_min_score: u32,
+    ) -> Result<()> {
+        let topic = &mut ctx.accounts.topic;
+        topic.leaderboard_min_score = leaderboard_min_score;
+
+        if let Some(leaderboard) = ctx.accounts.leaderboard.as_mut() {
+            leaderboard.entries.retain(|e| e.score >= leaderboard_min_score);
+        }
+
+        msg!("Topic '{}' leaderboard minimum score (quizzes won): {}", topic.name, leaderboard_min_score);
+        Ok(())
+    }
+
     // ===== QUIZ MANAGEMENT FUNCTIONS =====
 
     pub fn create_quiz_set(
@@ -188,12 +521,32 @@ pub mod k_3_hoot_program_arcium {
         name: String,
         question_count: u8,
         unique_id: u8,
-        reward_amount: u64, // SOL amount in lamports
+        reward_amount: u64, // Lamports, or base units of the reward mint when one is passed
+        initial_funding: u64, // Deposited now; the rest can follow via fund_quiz
+        deadline: i64, // Unix time after which an unwon reward can be refunded (0 = never)
     ) -> Result<()> {
         require!(name.len() > 0, QuizError::EmptyName);
-        require!(name.len() <= 100, QuizError::NameTooLong);
-        require!(question_count > 0 && question_count <= 50, QuizError::InvalidQuestionCount);
-        require!(reward_amount > 0, QuizError::InvalidRewardAmount);
+        require!(name.len() <= MAX_NAME_LEN, QuizError::NameTooLong);
+        require!(initial_funding <= reward_amount, QuizError::InvalidFundingAmount);
+        require!(deadline == 0 || deadline > Clock::get()?.unix_timestamp, QuizError::InvalidDeadline);
+
+        // Passing a reward mint switches the quiz to an SPL token reward
+        let reward_mint = ctx.accounts.reward_mint.as_ref().map(|mint| mint.key());
+        let reward_tokens = reward_token_accounts(
+            reward_mint,
+            ctx.accounts.reward_mint.as_ref(),
+            ctx.accounts.vault_token_account.as_ref(),
+            ctx.accounts.authority_token_account.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+            ctx.accounts.vault.key(),
+            ctx.accounts.authority.key(),
+        )?;
+
+        // Token rewards are deposited in full up front; fund_quiz only tops up lamports
+        require!(
+            reward_mint.is_none() || initial_funding == reward_amount,
+            QuizError::InvalidFundingAmount
+        );
 
         let topic = &ctx.accounts.topic;
         let quiz_set = &mut ctx.accounts.quiz_set;
@@ -202,7 +555,22 @@ pub mod k_3_hoot_program_arcium {
         require!(topic.is_active, QuizError::TopicNotActive);
         require!(topic.owner == ctx.accounts.authority.key(), QuizError::NotTopicOwner);
         require!(question_count >= topic.min_question_count, QuizError::InsufficientQuestions);
-        require!(reward_amount >= topic.min_reward_amount, QuizError::InsufficientReward);
+        // A zero reward makes an unrewarded practice quiz, which the topic minimum doesn't apply to
+        require!(
+            reward_amount == 0 || reward_amount >= topic.min_reward_amount,
+            QuizError::InsufficientReward
+        );
+        check_reward_mint(topic, reward_mint)?;
+
+        let max_active_quizzes = ctx.accounts.program_config.max_active_quizzes;
+        let author_quota = &mut ctx.accounts.author_quota;
+        require!(!author_quota.is_retired(unique_id), QuizError::QuizIdRetired);
+        require!(
+            max_active_quizzes == 0 || author_quota.active_quiz_count < max_active_quizzes,
+            QuizError::TooManyActiveQuizzes
+        );
+        author_quota.author = ctx.accounts.authority.key();
+        author_quota.active_quiz_count += 1;
 
         quiz_set.authority = ctx.accounts.authority.key();
         quiz_set.topic = topic.key();
@@ -215,16 +583,110 @@ pub mod k_3_hoot_program_arcium {
         quiz_set.winner = None;
         quiz_set.correct_answers_count = 0;
         quiz_set.unique_id = unique_id;
+        quiz_set.won_at = 0;
+        quiz_set.announce_after = 0;
+        quiz_set.is_winner_announced = false;
+        quiz_set.buffer_size = ANSWER_BUFFER_SIZE;
+        quiz_set.content_hash = [0u8; 32];
+        quiz_set.stats_public = false;
+        quiz_set.runner_up_amount = 0;
+        quiz_set.runner_up = None;
+        quiz_set.is_runner_up_claimed = false;
+        quiz_set.sudden_death = false;
+        quiz_set.claim_window_seconds = 0;
+        quiz_set.claim_grace_seconds = 0;
+        quiz_set.won_slot = 0;
+        quiz_set.co_winners = Vec::new();
+        quiz_set.co_winners_claimed = 0;
+        quiz_set.total_weight = 0;
+        quiz_set.assigned_weight = 0;
+        quiz_set.gate_mint = None;
+        quiz_set.is_practice = reward_amount == 0;
+        quiz_set.answer_fee = 0;
+        quiz_set.end_on_wrong = false;
+        quiz_set.bonus_count = 0;
+        quiz_set.retry_window_seconds = 0;
+        quiz_set.rotation_target_key = [0u8; 32];
+        quiz_set.rotation_in_progress = false;
+        quiz_set.rotated_count = 0;
+        quiz_set.rotation_pending_index = 0;
+        quiz_set.rotation_pending_nonce = 0;
+        quiz_set.reveal_on_wrong = false;
+        quiz_set.keeper_delay_seconds = 0;
+        quiz_set.funded_amount = initial_funding;
+        quiz_set.is_funded = initial_funding == reward_amount;
+        quiz_set.duration_seconds = 0;
+        quiz_set.first_answer_hash = [0u8; 32];
+        quiz_set.password_key = None;
+        quiz_set.has_distinct_answers = false;
+        quiz_set.per_question_reward = 0;
+        quiz_set.reward_mint = reward_mint;
+        quiz_set.deadline = deadline;
+        quiz_set.is_active = true;
+        quiz_set.result_authority = ctx.accounts.authority.key();
+        quiz_set.is_refunded = false;
+        quiz_set.max_winners = 1;
+        quiz_set.max_attempts = 0;
+        quiz_set.attempt_cooldown_seconds = 0;
+        quiz_set.result_grace_seconds = 0;
+        quiz_set.category_totals = [0; MAX_CATEGORIES];
+        quiz_set.allow_authority_play = topic.allow_authority_play;
+        quiz_set.reward_brackets = Vec::new();
+        quiz_set.min_participants = 0;
+        quiz_set.participant_count = 0;
+        quiz_set.require_claim_proof = false;
+        quiz_set.reward_mode = RewardMode::WinnerTakesAll;
+        quiz_set.proportional_paid = 0;
+        quiz_set.settlement_finalized = false;
+        quiz_set.commit_deadline = 0;
+        quiz_set.per_question_credited = 0;
+        quiz_set.per_question_paid = 0;
+        validate_quiz_config(quiz_set)?;
+
+        // Reuse a vault pre-created with init_quiz_vault, otherwise create it now
+        if ctx.accounts.vault.owner != &crate::ID {
+            create_vault(
+                &ctx.accounts.authority,
+                &ctx.accounts.vault,
+                &ctx.accounts.system_program,
+                &quiz_set.key(),
+                ctx.bumps.vault,
+            )?;
+        }
 
-        // Transfer SOL to vault
-        let transfer_ctx = CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: ctx.accounts.authority.to_account_info(),
-                to: ctx.accounts.vault.to_account_info(),
-            },
-        );
-        anchor_lang::system_program::transfer(transfer_ctx, reward_amount)?;
+        match &reward_tokens {
+            // Transfer tokens to the vault's token account
+            Some(tokens) => {
+                let transfer_ctx = CpiContext::new(
+                    tokens.token_program.to_account_info(),
+                    TransferChecked {
+                        from: tokens.user_token_account.to_account_info(),
+                        mint: tokens.mint.to_account_info(),
+                        to: tokens.vault_token_account.to_account_info(),
+                        authority: ctx.accounts.authority.to_account_info(),
+                    },
+                );
+                token_interface::transfer_checked(transfer_ctx, initial_funding, tokens.mint.decimals)?;
+            }
+            // Transfer SOL to vault
+            None => {
+                let transfer_ctx = CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: ctx.accounts.vault.to_account_info(),
+                    },
+                );
+                anchor_lang::system_program::transfer(transfer_ctx, initial_funding)?;
+            }
+        }
+
+        record_escrow_change(
+            &mut ctx.accounts.reward_stats,
+            reward_mint.unwrap_or(NATIVE_SOL_MINT),
+            initial_funding,
+            0,
+        )?;
 
         emit!(QuizSetCreated {
             quiz_set: quiz_set.key(),
@@ -240,6 +702,50 @@ pub mod k_3_hoot_program_arcium {
         Ok(())
     }
 
+    // Create a quiz's vault ahead of the quiz itself, so the quiz set and its
+    // funding can go out in separate transactions (e.g. multisig flows)
+    pub fn init_quiz_vault(ctx: Context<InitQuizVault>, unique_id: u8) -> Result<()> {
+        msg!("Vault {} ready for quiz set {} (id {})", ctx.accounts.vault.key(), ctx.accounts.quiz_set.key(), unique_id);
+        Ok(())
+    }
+
+    // Top up the vault of a quiz created with partial funding. Anyone may fund
+    // (e.g. a sponsor); answers and claims open once the full reward is in.
+    pub fn fund_quiz(ctx: Context<FundQuiz>, amount: u64) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        require!(quiz_set.reward_mint.is_none(), QuizError::NativeRewardOnly);
+        require!(amount > 0, QuizError::InvalidFundingAmount);
+        require!(
+            amount <= quiz_set.reward_amount - quiz_set.funded_amount,
+            QuizError::InvalidFundingAmount
+        );
+
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.funder.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(transfer_ctx, amount)?;
+
+        quiz_set.funded_amount += amount;
+        quiz_set.is_funded = quiz_set.funded_amount == quiz_set.reward_amount;
+
+        record_escrow_change(&mut ctx.accounts.reward_stats, NATIVE_SOL_MINT, amount, 0)?;
+
+        emit!(QuizFunded {
+            quiz_set: quiz_set.key(),
+            funder: ctx.accounts.funder.key(),
+            amount,
+            funded_amount: quiz_set.funded_amount,
+            is_funded: quiz_set.is_funded,
+        });
+
+        msg!("💰 Quiz set '{}' funded {}/{} lamports", quiz_set.name, quiz_set.funded_amount, quiz_set.reward_amount);
+        Ok(())
+    }
+
     pub fn add_encrypted_question_block(
         ctx: Context<AddEncryptedQuestionBlock>,
         question_index: u8,
@@ -247,1084 +753,7941 @@ pub mod k_3_hoot_program_arcium {
         encrypted_y_coordinate: [u8; 64],
         arcium_pubkey: [u8; 32],
         nonce: u128,
+        weight: u16,
+        is_bonus: bool,
+        category: u8,
     ) -> Result<()> {
         let quiz_set = &mut ctx.accounts.quiz_set;
         require!(quiz_set.authority == ctx.accounts.authority.key(), QuizError::Unauthorized);
-        require!(question_index > 0 && question_index <= quiz_set.question_count, QuizError::InvalidQuestionIndex);
-        require!(!quiz_set.is_initialized, QuizError::QuizSetAlreadyInitialized);
 
-        let question_block = &mut ctx.accounts.question_block;
-        question_block.quiz_set = quiz_set.key();
-        question_block.question_index = question_index as u32;
-        question_block.encrypted_x_coordinate = encrypted_x_coordinate;
-        question_block.encrypted_y_coordinate = encrypted_y_coordinate;
-        question_block.arcium_pubkey = arcium_pubkey;
-        question_block.nonce = nonce;
-        question_block.created_at = Clock::get()?.unix_timestamp;
-
-        if question_index == quiz_set.question_count {
-            quiz_set.is_initialized = true;
-        }
-
-        emit!(QuestionBlockAdded {
-            question_block: question_block.key(),
-            quiz_set: quiz_set.key(),
-            question_index: question_block.question_index,
-            timestamp: question_block.created_at,
-        });
+        let payload = QuestionBlockPayload {
+            question_index,
+            encrypted_x_coordinate,
+            encrypted_y_coordinate,
+            nonce,
+            weight,
+            is_bonus,
+            category,
+        };
+        let question_block = new_question_block(quiz_set, &payload, arcium_pubkey)?;
+        register_question_block(quiz_set, &ctx.accounts.topic, ctx.accounts.question_block.key(), &question_block)?;
+        ctx.accounts.question_block.set_inner(question_block);
 
         msg!("Question block {} added to quiz set '{}'", question_index, quiz_set.name);
         Ok(())
     }
 
-    pub fn validate_answer_onchain(
-        ctx: Context<ValidateAnswerOnchain>,
-        computation_offset: u64,
-        user_answer: String,
-        question_index: u8,
+    // Add several question blocks in one transaction. Each payload's block PDA
+    // is passed, in the same order, as a writable remaining account and created
+    // here; indices must be strictly increasing. See MAX_QUESTION_BLOCK_BATCH
+    // for how many fit in one transaction.
+    pub fn add_encrypted_question_blocks_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, AddEncryptedQuestionBlocksBatch<'info>>,
+        arcium_pubkey: [u8; 32],
+        payloads: Vec<QuestionBlockPayload>,
     ) -> Result<()> {
-        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
-        let question_block = &ctx.accounts.question_block;
-        let quiz_set = &ctx.accounts.quiz_set;
-        
-        require!(question_index > 0 && question_index <= quiz_set.question_count, QuizError::InvalidQuestionIndex);
+        require!(
+            !payloads.is_empty() && payloads.len() <= MAX_QUESTION_BLOCK_BATCH,
+            QuizError::BatchTooLarge
+        );
+        require!(payloads.len() == ctx.remaining_accounts.len(), QuizError::InvalidQuestionBlock);
+        require!(
+            payloads.windows(2).all(|pair| pair[0].question_index < pair[1].question_index),
+            QuizError::InvalidQuestionIndex
+        );
 
-        // FIXED: Convert user answer to proper format for Arcium
-        let mut answer_bytes = [0u8; 64];
-        let user_bytes = user_answer.as_bytes();
-        let len = std::cmp::min(user_bytes.len(), 64);
-        answer_bytes[..len].copy_from_slice(&user_bytes[..len]);
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        require!(quiz_set.authority == ctx.accounts.authority.key(), QuizError::Unauthorized);
 
-        // FIXED: Send proper arguments for Arcium computation
-        let mut args = vec![Argument::PlaintextU128(question_block.nonce)];
-        
-        // Add user answer bytes
-        for i in 0..64 {
-            args.push(Argument::PlaintextU8(answer_bytes[i]));
+        for (payload, info) in payloads.iter().zip(ctx.remaining_accounts.iter()) {
+            let question_block = new_question_block(quiz_set, payload, arcium_pubkey)?;
+
+            let (expected, bump) = Pubkey::find_program_address(
+                &[b"question_block", quiz_set.key().as_ref(), &[payload.question_index]],
+                &crate::ID,
+            );
+            require!(info.key() == expected, QuizError::InvalidQuestionBlock);
+            create_question_block(
+                &ctx.accounts.authority,
+                info,
+                &ctx.accounts.system_program,
+                &quiz_set.key(),
+                payload.question_index,
+                bump,
+            )?;
+
+            register_question_block(quiz_set, &ctx.accounts.topic, info.key(), &question_block)?;
+            question_block.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
         }
-        
-        // Add encrypted correct answer bytes
-        for i in 0..64 {
-            args.push(Argument::PlaintextU8(question_block.encrypted_y_coordinate[i]));
+
+        msg!("{} question blocks added to quiz set '{}'", payloads.len(), quiz_set.name);
+        Ok(())
+    }
+
+    // Grow a question block created under an older layout to the current size.
+    // Appended fields are zero-filled, which Borsh reads back as their defaults.
+    pub fn migrate_question_block(ctx: Context<MigrateQuestionBlock>) -> Result<()> {
+        let info = ctx.accounts.question_block.to_account_info();
+        let quiz_set_key = ctx.accounts.quiz_set.key();
+
+        // Only the legacy prefix is guaranteed to be readable before resizing
+        {
+            let data = info.try_borrow_data()?;
+            require!(
+                data.len() >= 8 + 32 && &data[..8] == <QuestionBlock as anchor_lang::Discriminator>::DISCRIMINATOR,
+                QuizError::InvalidQuestionBlock
+            );
+            require!(&data[8..40] == quiz_set_key.as_ref(), QuizError::InvalidQuestionBlock);
         }
 
-        queue_computation(
-            ctx.accounts, 
-            computation_offset, 
-            args,
-            None,
-            vec![ValidateAnswerCallback::callback_ix(&[])], 
-        )?;
+        let old_len = info.data_len();
+        if old_len < QuestionBlock::LEN {
+            let required = Rent::get()?.minimum_balance(QuestionBlock::LEN);
+            let top_up = required.saturating_sub(info.lamports());
+            if top_up > 0 {
+                let transfer_ctx = CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: info.clone(),
+                    },
+                );
+                anchor_lang::system_program::transfer(transfer_ctx, top_up)?;
+            }
+            info.resize(QuestionBlock::LEN)?;
+        }
+
+        let mut question_block = QuestionBlock::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        let old_version = question_block.version;
+        question_block.version = QUESTION_BLOCK_VERSION;
+        question_block.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        emit!(QuestionBlockMigrated {
+            question_block: info.key(),
+            quiz_set: quiz_set_key,
+            old_len: old_len as u32,
+            new_len: QuestionBlock::LEN as u32,
+            old_version,
+            new_version: QUESTION_BLOCK_VERSION,
+        });
 
-        msg!("Answer validation queued for question {}", question_index);
+        msg!("Question block {} migrated to v{}", question_block.question_index, QUESTION_BLOCK_VERSION);
         Ok(())
     }
 
-    // ===== SCORING SYSTEM FUNCTIONS =====
+    // Limit how long a question can be answered (0 = no expiry). Unlike the quiz
+    // configuration this can change after the quiz is live, for rotating content.
+    pub fn set_question_expiry(ctx: Context<SetQuestionExpiry>, valid_until: i64) -> Result<()> {
+        require!(valid_until >= 0, QuizError::InvalidExpiry);
 
-    // Record quiz completion and update scores
-    pub fn record_quiz_completion(
-        ctx: Context<RecordQuizCompletion>,
-        is_winner: bool,
-        score: u8,
-        total_questions: u8,
-        reward_amount: u64,
+        let question_block = &mut ctx.accounts.question_block;
+        question_block.valid_until = valid_until;
+
+        msg!("Question {} valid until {}", question_block.question_index, valid_until);
+        Ok(())
+    }
+
+    // Attach up to MAX_HINTS progressive hints, each encrypted client-side under
+    // hint_nonce with the same additive scheme as the answer. A separate nonce keeps
+    // the hints readable across key rotations of the block itself.
+    pub fn set_question_hints(
+        ctx: Context<SetQuestionHints>,
+        hints: Vec<[u8; HINT_SIZE]>,
+        hint_nonce: u128,
     ) -> Result<()> {
-        let quiz_set = &ctx.accounts.quiz_set;
-        let topic = &ctx.accounts.topic;
-        let user_score = &mut ctx.accounts.user_score;
-        let quiz_history = &mut ctx.accounts.quiz_history;
+        require!(hints.len() <= MAX_HINTS, QuizError::TooManyHints);
 
-        // Initialize user score if first time
-        if user_score.user == Pubkey::default() {
-            user_score.user = ctx.accounts.user.key();
-            user_score.topic = topic.key();
-            user_score.score = 0;
-            user_score.total_completed = 0;
-            user_score.last_activity = 0;
-            user_score.total_rewards = 0;
+        let question_block = &mut ctx.accounts.question_block;
+        question_block.hints = [[0u8; HINT_SIZE]; MAX_HINTS];
+        for (slot, hint) in question_block.hints.iter_mut().zip(hints.iter()) {
+            *slot = *hint;
         }
+        question_block.hint_count = hints.len() as u8;
+        question_block.hint_nonce = hint_nonce;
 
-        // Update user score
-        user_score.total_completed += 1;
-        user_score.last_activity = Clock::get()?.unix_timestamp;
-        
-        if is_winner {
-            user_score.score += 1;
-            user_score.total_rewards += reward_amount;
-        }
+        msg!("Question {} has {} hints", question_block.question_index, hints.len());
+        Ok(())
+    }
 
-        // Record quiz history
-        quiz_history.user = ctx.accounts.user.key();
-        quiz_history.quiz_set = quiz_set.key();
-        quiz_history.topic = topic.key();
-        quiz_history.completed_at = Clock::get()?.unix_timestamp;
-        quiz_history.score = score;
-        quiz_history.total_questions = total_questions;
-        quiz_history.is_winner = is_winner;
-        quiz_history.reward_claimed = if is_winner { reward_amount } else { 0 };
+    // Accept up to MAX_ALT_ANSWERS answers besides the primary one, each encrypted
+    // under the block's nonce like encrypted_y_coordinate. Any match counts as correct.
+    pub fn set_acceptable_answers(
+        ctx: Context<SetAcceptableAnswers>,
+        alt_answers: Vec<[u8; 64]>,
+    ) -> Result<()> {
+        require!(alt_answers.len() <= MAX_ALT_ANSWERS, QuizError::TooManyAcceptableAnswers);
+        require!(ctx.accounts.quiz_set.buffer_size == ANSWER_BUFFER_SIZE, QuizError::BufferSizeMismatch);
 
-        emit!(QuizCompletionRecorded {
-            user: ctx.accounts.user.key(),
-            quiz_set: quiz_set.key(),
-            topic: topic.key(),
-            is_winner,
-            score,
-            total_questions,
-            reward_amount: if is_winner { reward_amount } else { 0 },
-            timestamp: Clock::get()?.unix_timestamp,
-        });
+        // Swap the block's contribution to the content commitment
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        let question_block = &mut ctx.accounts.question_block;
+        xor_into(&mut quiz_set.content_hash, &question_block_hash(question_block));
+        question_block.alt_answers = [[0u8; 64]; MAX_ALT_ANSWERS];
+        for (slot, answer) in question_block.alt_answers.iter_mut().zip(alt_answers.iter()) {
+            *slot = *answer;
+        }
+        question_block.alt_answer_count = alt_answers.len() as u8;
+        xor_into(&mut quiz_set.content_hash, &question_block_hash(question_block));
 
-        msg!("Quiz completion recorded for user {} - Score: {}/{} - Winner: {}", 
-             ctx.accounts.user.key(), score, total_questions, is_winner);
+        msg!("Question {} accepts {} additional answers", question_block.question_index, alt_answers.len());
         Ok(())
     }
 
-    // Get user's overall stats across all topics
-    pub fn get_user_global_stats(
-        ctx: Context<GetUserGlobalStats>,
+    // ===== QUIZ CONFIGURATION FUNCTIONS =====
+
+    // Delay the winner announcement (and claim eligibility) until a given time
+    pub fn set_announce_after(
+        ctx: Context<ConfigureQuizSet>,
+        announce_after: i64,
     ) -> Result<()> {
-        // This function is mainly for anchor IDL generation
-        // The actual stats fetching will be done client-side by fetching all UserScore accounts
-        msg!("User global stats request for: {}", ctx.accounts.user.key());
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        quiz_set.announce_after = announce_after;
+        validate_quiz_config(quiz_set)?;
+
+        msg!("Quiz set '{}' winner announcement delayed until {}", quiz_set.name, announce_after);
         Ok(())
     }
 
-    // ===== NEW DEVNET TESTING FUNCTION =====
-    
-    // Function to manually set winner for devnet testing (bypasses Arcium callback)
-    pub fn set_winner_for_devnet(
-        ctx: Context<SetWinnerForDevnet>,
-        user_answers: Vec<String>,
-        correct_answers: Vec<String>,
+    // Select the answer buffer size (and therefore the validation circuit)
+    pub fn set_buffer_size(
+        ctx: Context<ConfigureQuizSet>,
+        buffer_size: u8,
     ) -> Result<()> {
         let quiz_set = &mut ctx.accounts.quiz_set;
-        
-        // Debug logging
-        msg!("🔍 Debug: Setting winner for devnet");
-        msg!("🔍 Debug: quiz_set.key() = {}", quiz_set.key());
-        msg!("🔍 Debug: authority.key() = {}", ctx.accounts.authority.key());
-        
-        // Set winner to authority (for devnet testing)
-        quiz_set.winner = Some(ctx.accounts.authority.key());
-        quiz_set.correct_answers_count = user_answers.len() as u8;
-        quiz_set.is_reward_claimed = false;
-        
-        msg!("✅ Winner set successfully: {}", ctx.accounts.authority.key());
-        msg!("✅ correct_answers_count set to: {}", quiz_set.correct_answers_count);
-        
+        quiz_set.buffer_size = buffer_size;
+        validate_quiz_config(quiz_set)?;
+
+        msg!("Quiz set '{}' answer buffer size set to {} bytes", quiz_set.name, buffer_size);
         Ok(())
     }
 
-    // Add a new function to set the winner for the actual correct answerer
-    pub fn set_winner_for_user(
-        ctx: Context<SetWinnerForUser>,
-        winner_pubkey: Pubkey,  // ← Receive pubkey of the actual correct answerer
-        correct_answers_count: u8,
+    // Opt in (or out) of publishing per-question correctness statistics in
+    // events. This is not privacy: the counts stay in plaintext on each
+    // QuestionBlock, so anyone reading the accounts can still see them.
+    pub fn set_stats_public(
+        ctx: Context<ConfigureQuizSet>,
+        stats_public: bool,
     ) -> Result<()> {
         let quiz_set = &mut ctx.accounts.quiz_set;
-        let setter = &ctx.accounts.setter;
-        
-        // Set winner as the actual correct answerer, not the authority
-        quiz_set.winner = Some(winner_pubkey);
-        quiz_set.correct_answers_count = correct_answers_count;
-        quiz_set.is_reward_claimed = false;
-        
-        msg!("✅ Winner set successfully: {}", winner_pubkey);
-        msg!("✅ correct_answers_count set to: {}", correct_answers_count);
-        msg!("✅ Set by: {}", setter.key());
-        
-        // Emit event
-        emit!(QuizCompleted {
-            quiz_set: quiz_set.key(),
-            winner: winner_pubkey,
-            reward_amount: quiz_set.reward_amount,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
-        
+        quiz_set.stats_public = stats_public;
+
+        msg!("Quiz set '{}' statistics are now {}", quiz_set.name, if stats_public { "published" } else { "unpublished" });
         Ok(())
     }
 
-    // ===== ARCIUM CALLBACKS =====
-
-    // FIXED: Proper callback handling with actual result logic
-    #[arcium_callback(encrypted_ix = "validate_answer")]
-    pub fn validate_answer_callback(
-        ctx: Context<ValidateAnswerCallback>,
-        output: ComputationOutputs<ValidateAnswerOutput>,
+    // Carve a consolation prize for the second finisher out of the reward
+    pub fn set_runner_up_amount(
+        ctx: Context<ConfigureQuizSet>,
+        runner_up_amount: u64,
     ) -> Result<()> {
-        let result = match output {
-            ComputationOutputs::Success(ValidateAnswerOutput { field_0 }) => {
-                msg!("Arcium computation completed successfully");
-                field_0
-            },
-            ComputationOutputs::Failure => {
-                msg!("Arcium computation failed");
-                return Err(ErrorCode::AbortedComputation.into());
-            }
-        };
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        quiz_set.runner_up_amount = runner_up_amount;
+        validate_quiz_config(quiz_set)?;
 
-        // FIXED: Extract boolean result from encrypted struct
-        let is_correct = match result {
-            _ => true, // Temporarily return true, will be replaced with actual logic
-        };
+        msg!("Quiz set '{}' runner-up reward set to {} lamports", quiz_set.name, runner_up_amount);
+        Ok(())
+    }
 
-        // Update quiz set with answer result
+    // In sudden death mode a player's first wrong answer eliminates them
+    pub fn set_sudden_death(
+        ctx: Context<ConfigureQuizSet>,
+        sudden_death: bool,
+    ) -> Result<()> {
         let quiz_set = &mut ctx.accounts.quiz_set;
-        let question_block = &ctx.accounts.question_block;
-        
-        // Mark this question as answered correctly
-        if is_correct {
-            // Check if all questions are answered correctly
-            if quiz_set.correct_answers_count == 0 {
-                quiz_set.correct_answers_count = 1;
-            } else {
-                quiz_set.correct_answers_count += 1;
-            }
-            
-            // If all questions answered correctly, set winner
-            if quiz_set.correct_answers_count >= quiz_set.question_count {
-                quiz_set.winner = Some(ctx.accounts.payer.key());
-                quiz_set.is_reward_claimed = false;
-                
-                emit!(QuizCompleted {
-                    quiz_set: quiz_set.key(),
-                    winner: ctx.accounts.payer.key(),
-                    reward_amount: quiz_set.reward_amount,
-                    timestamp: Clock::get()?.unix_timestamp,
-                });
-                
-                msg!("🎉 Quiz completed! Winner: {}", ctx.accounts.payer.key());
-            }
-        }
-
-        // Emit event with actual result
-        emit!(AnswerVerifiedEvent {
-            question_index: question_block.question_index,
-            is_correct,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
+        quiz_set.sudden_death = sudden_death;
 
-        msg!("Answer validation completed onchain. Result: {}", is_correct);
+        msg!("Quiz set '{}' sudden death: {}", quiz_set.name, sudden_death);
         Ok(())
     }
 
-    #[arcium_callback(encrypted_ix = "encrypt_quiz")]
-    pub fn encrypt_quiz_callback(
-        ctx: Context<EncryptQuizCallback>,
-        output: ComputationOutputs<EncryptQuizOutput>,
-    ) -> Result<()> {
-        let _encrypted_data = match &output {
-            ComputationOutputs::Success(EncryptQuizOutput { field_0 }) => field_0,
-            _ => return Err(ErrorCode::AbortedComputation.into()),
-        };
+    // Wrong retries of the same question within this window after a wrong
+    // answer carry no penalty (0 = every wrong answer is penalized)
+    pub fn set_retry_window(ctx: Context<ConfigureQuizSet>, retry_window_seconds: i64) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        quiz_set.retry_window_seconds = retry_window_seconds;
+        validate_quiz_config(quiz_set)?;
 
-        let mut result_bytes = [0u8; 8];
-        result_bytes[0] = 1;
+        msg!("Quiz set '{}' retry window: {}s", quiz_set.name, retry_window_seconds);
+        Ok(())
+    }
 
-        emit!(QuizDataEncryptedEvent {
-            encrypted_data: result_bytes,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
+    // A wrong answer ends the player's run, keeping the score earned so far
+    pub fn set_end_on_wrong(ctx: Context<ConfigureQuizSet>, end_on_wrong: bool) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        quiz_set.end_on_wrong = end_on_wrong;
 
-        msg!("Quiz data encrypted successfully");
+        msg!("Quiz set '{}' end on wrong: {}", quiz_set.name, end_on_wrong);
         Ok(())
     }
 
-    #[arcium_callback(encrypted_ix = "decrypt_quiz")]
-    pub fn decrypt_quiz_callback(
-        ctx: Context<DecryptQuizCallback>,
-        output: ComputationOutputs<DecryptQuizOutput>,
+    // Limit how long a winner has to claim. The grace period extends the nominal
+    // window to absorb network congestion; 0 window = no deadline.
+    pub fn set_claim_window(
+        ctx: Context<ConfigureQuizSet>,
+        claim_window_seconds: i64,
+        claim_grace_seconds: i64,
     ) -> Result<()> {
-        let _decrypted_data = match &output {
-            ComputationOutputs::Success(DecryptQuizOutput { field_0 }) => field_0,
-            _ => return Err(ErrorCode::AbortedComputation.into()),
-        };
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        quiz_set.claim_window_seconds = claim_window_seconds;
+        quiz_set.claim_grace_seconds = claim_grace_seconds;
+        validate_quiz_config(quiz_set)?;
 
-        let mut result_bytes = [0u8; 8];
-        result_bytes[0] = 1;
+        msg!(
+            "Quiz set '{}' claim window: {}s + {}s grace",
+            quiz_set.name, claim_window_seconds, claim_grace_seconds
+        );
+        Ok(())
+    }
 
-        emit!(QuizDataDecryptedEvent {
-            decrypted_data: result_bytes,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
+    // Make the quiz's questions weighted; block weights must add up to this
+    // when the last block is added. 0 = every question is worth one point.
+    pub fn set_total_weight(ctx: Context<ConfigureQuizSet>, total_weight: u16) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        quiz_set.total_weight = total_weight;
+        validate_quiz_config(quiz_set)?;
 
-        msg!("Quiz data decrypted successfully");
+        msg!("Quiz set '{}' total weight: {}", quiz_set.name, total_weight);
         Ok(())
     }
 
-    // ===== NEW VAULT MANAGEMENT FUNCTIONS =====
+    // Only holders of `gate_mint` may answer or claim (None = open to everyone)
+    pub fn set_gate_mint(ctx: Context<ConfigureQuizSet>, gate_mint: Option<Pubkey>) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        quiz_set.gate_mint = gate_mint;
+
+        msg!("Quiz set '{}' gate mint: {:?}", quiz_set.name, gate_mint);
+        Ok(())
+    }
 
-    pub fn claim_reward(ctx: Context<ClaimReward>) -> Result<()> {
+    // Practice quizzes are replayable: players may reset their progress at any time
+    pub fn set_practice(ctx: Context<ConfigureQuizSet>, is_practice: bool) -> Result<()> {
         let quiz_set = &mut ctx.accounts.quiz_set;
-        let vault = &ctx.accounts.vault;
-        let claimer = &ctx.accounts.claimer;
-        
-        msg!("🔍 Debug: claim_reward called");
-        msg!("🔍 Debug: quiz_set.is_initialized = {}", quiz_set.is_initialized);
-        msg!("🔍 Debug: quiz_set.winner = {:?}", quiz_set.winner);
-        msg!("🔍 Debug: quiz_set.is_reward_claimed = {}", quiz_set.is_reward_claimed);
-        msg!("🔍 Debug: claimer = {}", claimer.key());
-        
-        let reward_amount = quiz_set.reward_amount;
-        
-        // FIXED: Use raw invoke_signed for PDA-to-account SOL transfer
-        let quiz_set_key = quiz_set.key();
-        let vault_seeds = &[
-            b"vault",
-            quiz_set_key.as_ref(),
-            &[ctx.bumps.vault]
-        ];
-        
-        let signer_seeds: &[&[&[u8]]] = &[vault_seeds];
-        
-        // Transfer lamports directly using invoke_signed
-        **vault.to_account_info().try_borrow_mut_lamports()? -= reward_amount;
-        **claimer.to_account_info().try_borrow_mut_lamports()? += reward_amount;
-        
-        // Mark reward as claimed
-        quiz_set.is_reward_claimed = true;
-        
-        msg!("✅ Reward claimed successfully: {} SOL", reward_amount / 1_000_000_000);
-        msg!("✅ Claimer: {}", claimer.key());
-        msg!("💰 SOL transferred from vault to claimer");
-        
-        // Emit event
-        emit!(RewardClaimed {
-            quiz_set: quiz_set.key(),
-            winner: claimer.key(),
-            reward_amount,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
-        
+        quiz_set.is_practice = is_practice;
+        validate_quiz_config(quiz_set)?;
+
+        msg!("Quiz set '{}' practice mode: {}", quiz_set.name, is_practice);
         Ok(())
     }
-}
 
-// ===== ACCOUNT STRUCTURES =====
+    // Learning aid for practice quizzes: a wrong answer reveals the correct one
+    pub fn set_reveal_on_wrong(ctx: Context<ConfigureQuizSet>, reveal_on_wrong: bool) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        quiz_set.reveal_on_wrong = reveal_on_wrong;
+        validate_quiz_config(quiz_set)?;
 
-// ===== TOPIC MANAGEMENT ACCOUNTS =====
+        msg!("Quiz set '{}' reveal on wrong: {}", quiz_set.name, reveal_on_wrong);
+        Ok(())
+    }
 
-#[derive(Accounts)]
-#[instruction(name: String)]
-pub struct CreateTopic<'info> {
-    #[account(
-        init,
-        payer = owner,
-        space = Topic::LEN,
-        seeds = [b"topic", name.as_bytes()],
-        bump
-    )]
-    pub topic: Account<'info, Topic>,
-    
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    // How long past the claim deadline a quiz must sit before any keeper may
+    // settle it (0 = keeper settlement disabled)
+    pub fn set_keeper_delay(ctx: Context<ConfigureQuizSet>, keeper_delay_seconds: i64) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        quiz_set.keeper_delay_seconds = keeper_delay_seconds;
+        validate_quiz_config(quiz_set)?;
 
-#[derive(Accounts)]
-pub struct TransferTopicOwnership<'info> {
-    #[account(
-        mut,
-        seeds = [b"topic", topic.name.as_bytes()],
-        bump,
-        has_one = owner @ QuizError::NotTopicOwner
-    )]
-    pub topic: Account<'info, Topic>,
-    
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+        msg!("Quiz set '{}' keeper delay: {}s", quiz_set.name, keeper_delay_seconds);
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct ToggleTopicStatus<'info> {
-    #[account(
-        mut,
-        seeds = [b"topic", topic.name.as_bytes()],
-        bump,
-        has_one = owner @ QuizError::NotTopicOwner
-    )]
-    pub topic: Account<'info, Topic>,
-    
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    // Give each player a fixed time to finish, counted from their first
+    // submission (0 = untimed)
+    pub fn set_duration(ctx: Context<ConfigureQuizSet>, duration_seconds: i64) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        quiz_set.duration_seconds = duration_seconds;
+        validate_quiz_config(quiz_set)?;
 
-// ===== QUIZ MANAGEMENT ACCOUNTS =====
+        msg!("Quiz set '{}' duration: {}s", quiz_set.name, duration_seconds);
+        Ok(())
+    }
 
-#[derive(Accounts)]
-#[instruction(name: String, question_count: u8, unique_id: u8, reward_amount: u64)]
-pub struct CreateQuizSet<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = QuizSet::LEN,
-        seeds = [b"quiz_set", authority.key().as_ref(), &[unique_id]],
-        bump
-    )]
-    pub quiz_set: Account<'info, QuizSet>,
-    
-    #[account(
-        seeds = [b"topic", topic.name.as_bytes()],
-        bump,
-        constraint = topic.is_active @ QuizError::TopicNotActive,
-        constraint = topic.owner == authority.key() @ QuizError::NotTopicOwner
-    )]
-    pub topic: Account<'info, Topic>,
-    
-    #[account(
-        init,
-        payer = authority,
-        space = 0,  // FIXED: No data space - pure SOL storage
-        seeds = [b"vault", quiz_set.key().as_ref()],
-        bump
-    )]
-    /// CHECK: This is a vault account for storing SOL rewards
-    pub vault: UncheckedAccount<'info>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    // Make the quiz private behind a shared password (None = open). Clients
+    // derive an Ed25519 keypair from the seed sha256("k3hoot:password" ||
+    // quiz_set || password) and pass only its public key.
+    pub fn set_password_key(ctx: Context<ConfigureQuizSet>, password_key: Option<Pubkey>) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        quiz_set.password_key = password_key;
 
-#[derive(Accounts)]
-#[instruction(question_index: u8)]
-pub struct AddEncryptedQuestionBlock<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = QuestionBlock::LEN,
-        seeds = [
-            b"question_block",
-            quiz_set.key().as_ref(),
-            &[question_index]
-        ],
-        bump
-    )]
-    pub question_block: Account<'info, QuestionBlock>,
-    
-    #[account(mut)]
-    pub quiz_set: Account<'info, QuizSet>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+        msg!("Quiz set '{}' password protected: {}", quiz_set.name, password_key.is_some());
+        Ok(())
+    }
 
-// ===== SCORING SYSTEM ACCOUNTS =====
+    // Pay every correct answer individually from the vault instead of a single
+    // prize for finishing (0 = completion prize)
+    pub fn set_per_question_reward(ctx: Context<ConfigureQuizSet>, per_question_reward: u64) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        quiz_set.per_question_reward = per_question_reward;
+        validate_quiz_config(quiz_set)?;
 
-#[derive(Accounts)]
-#[instruction(timestamp_seed: u64)]
+        msg!("Quiz set '{}' per-question reward: {} lamports", quiz_set.name, per_question_reward);
+        Ok(())
+    }
+
+    // Split the reward across the first `max_winners` players to finish instead
+    // of winner-take-all (1 = single winner). Each gets reward / max_winners,
+    // even if fewer finish. Fixed once there is a winner, since shares depend on it.
+    pub fn set_max_winners(ctx: Context<ConfigureQuizSet>, max_winners: u8) -> Result<()> {
+        require!((1..=MAX_WINNERS).contains(&max_winners), QuizError::InvalidMaxWinners);
+
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        require!(quiz_set.winner.is_none(), QuizError::WinnerAlreadySet);
+        quiz_set.max_winners = max_winners;
+
+        msg!("Quiz set '{}' max winners: {}", quiz_set.name, max_winners);
+        Ok(())
+    }
+
+    // Limit guessing: at most `max_attempts` answers per question per player,
+    // at least `attempt_cooldown_seconds` apart (0 turns either limit off)
+    pub fn set_attempt_limits(
+        ctx: Context<ConfigureQuizSet>,
+        max_attempts: u8,
+        attempt_cooldown_seconds: i64,
+    ) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        quiz_set.max_attempts = max_attempts;
+        quiz_set.attempt_cooldown_seconds = attempt_cooldown_seconds;
+        validate_quiz_config(quiz_set)?;
+
+        msg!(
+            "Quiz set '{}' attempts: max {} per question, {}s cooldown",
+            quiz_set.name, max_attempts, attempt_cooldown_seconds
+        );
+        Ok(())
+    }
+
+    // Keep counting answer results that land up to `result_grace_seconds` after
+    // a single-winner quiz is won (e.g. for stats); later ones are dropped as stale
+    pub fn set_result_grace(ctx: Context<ConfigureQuizSet>, result_grace_seconds: i64) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        quiz_set.result_grace_seconds = result_grace_seconds;
+        validate_quiz_config(quiz_set)?;
+
+        msg!("Quiz set '{}' result grace: {}s", quiz_set.name, result_grace_seconds);
+        Ok(())
+    }
+
+    // Scale each winner's share by how fast they completed the quiz, e.g.
+    // [(10, 10_000), (30, 5_000)]: full share within 10s, half within 30s,
+    // nothing slower. Empty pays full shares regardless of time.
+    // Brackets only scale the winner and co-winner shares: other completers
+    // are not paid by their bracket, and the runner-up keeps a fixed amount.
+    pub fn set_reward_brackets(ctx: Context<ConfigureQuizSet>, reward_brackets: Vec<RewardBracket>) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        require!(quiz_set.winner.is_none(), QuizError::WinnerAlreadySet);
+        quiz_set.reward_brackets = reward_brackets;
+        validate_quiz_config(quiz_set)?;
+
+        msg!("Quiz set '{}' reward brackets: {}", quiz_set.name, quiz_set.reward_brackets.len());
+        Ok(())
+    }
+
+    // Proportional quizzes pay every claimant reward_amount * score / question_count
+    // instead of paying only the winner, until the pool runs out
+    pub fn set_reward_mode(ctx: Context<ConfigureQuizSet>, reward_mode: RewardMode) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        quiz_set.reward_mode = reward_mode;
+        validate_quiz_config(quiz_set)?;
+
+        msg!("Quiz set '{}' reward mode: {:?}", quiz_set.name, reward_mode);
+        Ok(())
+    }
+
+    // High-value quizzes: claims must be co-signed by the proof key the player
+    // registered while playing (see register_proof_key)
+    pub fn set_require_claim_proof(ctx: Context<ConfigureQuizSet>, require_claim_proof: bool) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        quiz_set.require_claim_proof = require_claim_proof;
+
+        msg!("Quiz set '{}' requires claim proof: {}", quiz_set.name, require_claim_proof);
+        Ok(())
+    }
+
+    // Close commits at `commit_deadline` and only accept reveals after it, so no
+    // answer is public while others can still commit (0 = no commit phase)
+    pub fn set_commit_deadline(ctx: Context<ConfigureQuizSet>, commit_deadline: i64) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        require!(quiz_set.winner.is_none(), QuizError::WinnerAlreadySet);
+        quiz_set.commit_deadline = commit_deadline;
+        validate_quiz_config(quiz_set)?;
+
+        msg!("Quiz set '{}' commit deadline: {}", quiz_set.name, commit_deadline);
+        Ok(())
+    }
+
+    // Hold claims until at least `min_participants` distinct players have
+    // submitted an answer (0 = no minimum)
+    pub fn set_min_participants(ctx: Context<ConfigureQuizSet>, min_participants: u16) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        quiz_set.min_participants = min_participants;
+
+        msg!("Quiz set '{}' minimum participants: {}", quiz_set.name, min_participants);
+        Ok(())
+    }
+
+    // Delegate set_winner_for_user to another signer (e.g. a results oracle);
+    // defaults to the quiz authority
+    pub fn set_result_authority(ctx: Context<ConfigureQuizSet>, result_authority: Pubkey) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        quiz_set.result_authority = result_authority;
+
+        msg!("Quiz set '{}' result authority: {}", quiz_set.name, result_authority);
+        Ok(())
+    }
+
+    // Charge players a fee per answer, paid into the Arcium fee pool
+    pub fn set_answer_fee(ctx: Context<ConfigureQuizSet>, answer_fee: u64) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        quiz_set.answer_fee = answer_fee;
+
+        msg!("Quiz set '{}' answer fee: {} lamports", quiz_set.name, answer_fee);
+        Ok(())
+    }
+
+    pub fn rename_quiz_set(ctx: Context<RenameQuizSet>, name: String) -> Result<()> {
+        require!(name.len() > 0, QuizError::EmptyName);
+        require!(name.len() <= MAX_NAME_LEN, QuizError::NameTooLong);
+
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        let old_name = std::mem::replace(&mut quiz_set.name, name.clone());
+
+        emit!(QuizSetRenamed {
+            quiz_set: quiz_set.key(),
+            old_name,
+            new_name: name,
+        });
+
+        msg!("Quiz set renamed to '{}'", quiz_set.name);
+        Ok(())
+    }
+
+    // Publish a winner whose announcement was held back by `announce_after`
+    pub fn announce_winner(ctx: Context<AnnounceWinner>) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(now >= quiz_set.announce_after, QuizError::WinnerNotYetAnnounced);
+
+        announce_quiz_winner(quiz_set, now);
+
+        msg!("Winner announced for quiz set '{}'", quiz_set.name);
+        Ok(())
+    }
+
+    // Phase one of answering: store only sha256(answer || salt || user), so the
+    // answer isn't readable from the transaction. Committing again before the
+    // reveal replaces the previous commitment.
+    pub fn commit_answer(ctx: Context<CommitAnswer>, question_index: u8, commitment: [u8; 32]) -> Result<()> {
+        let quiz_set = &ctx.accounts.quiz_set;
+        require!(question_index > 0 && question_index <= quiz_set.question_count, QuizError::InvalidQuestionIndex);
+        let now = Clock::get()?.unix_timestamp;
+        require!(quiz_set.deadline == 0 || now <= quiz_set.deadline, QuizError::QuizDeadlinePassed);
+        check_commit_open(quiz_set, now)?;
+
+        let answer_commitment = &mut ctx.accounts.answer_commitment;
+        answer_commitment.user = ctx.accounts.user.key();
+        answer_commitment.quiz_set = quiz_set.key();
+        answer_commitment.question_index = question_index;
+        answer_commitment.commitment = commitment;
+        answer_commitment.committed_slot = Clock::get()?.slot;
+
+        msg!("🔒 Answer committed for question {}", question_index);
+        Ok(())
+    }
+
+    // Phase two: open the commitment and queue the validation. On a quiz with a
+    // commit deadline, reveals open only once commits have closed, so a revealed
+    // answer can't be copied into a new commitment.
+    pub fn reveal_answer(
+        ctx: Context<RevealAnswer>,
+        computation_offset: u64,
+        user_answer: String,
+        question_index: u8,
+        salt: [u8; 32],
+        prefund_next: bool,
+    ) -> Result<()> {
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        // Remember who submitted so the callback credits the right player
+        let answer_submission = &mut ctx.accounts.answer_submission;
+        answer_submission.submitter = ctx.accounts.payer.key();
+        answer_submission.quiz_set = ctx.accounts.quiz_set.key();
+        answer_submission.question_index = question_index;
+        answer_submission.computation_offset = computation_offset;
+        answer_submission.submitted_at = Clock::get()?.unix_timestamp;
+        let answer_submission_time = answer_submission.submitted_at;
+
+        let player_progress = &mut ctx.accounts.player_progress;
+        if player_progress.player == Pubkey::default() {
+            player_progress.player = ctx.accounts.payer.key();
+            player_progress.quiz_set = ctx.accounts.quiz_set.key();
+        }
+        if player_progress.started_at == 0 {
+            player_progress.started_at = answer_submission.submitted_at;
+        }
+        require!(
+            question_index > 0 && question_index <= ctx.accounts.quiz_set.question_count,
+            QuizError::InvalidQuestionIndex
+        );
+        let question_block = load_question_block(&ctx.accounts.question_block, &ctx.accounts.quiz_set.key(), question_index)?;
+        check_answer_allowed(&ctx.accounts.quiz_set, player_progress, &question_block, answer_submission_time)?;
+        require!(ctx.accounts.quiz_set.buffer_size == ANSWER_BUFFER_SIZE, QuizError::BufferSizeMismatch);
+        require!(!ctx.accounts.quiz_set.rotation_in_progress, QuizError::KeyRotationInProgress);
+        check_authority_play(&ctx.accounts.quiz_set, &ctx.accounts.payer.key())?;
+        check_gate(
+            &ctx.accounts.quiz_set,
+            &ctx.accounts.payer.key(),
+            ctx.accounts.gate_token_account.as_deref(),
+        )?;
+
+        let expected_offset = derive_computation_offset(
+            &ctx.accounts.payer.key(),
+            &ctx.accounts.quiz_set.key(),
+            question_index,
+            player_progress.submission_count,
+        );
+        require!(computation_offset == expected_offset, QuizError::InvalidComputationOffset);
+        player_progress.submission_count = player_progress.submission_count.saturating_add(1);
+        count_participant(&mut ctx.accounts.quiz_set, player_progress);
+
+        collect_answer_fee(
+            &ctx.accounts.quiz_set,
+            player_progress,
+            &ctx.accounts.payer,
+            &ctx.accounts.pool_account,
+            &ctx.accounts.system_program,
+            prefund_next,
+        )?;
+
+        let quiz_set = &ctx.accounts.quiz_set;
+        
+        record_attempt(quiz_set, &mut ctx.accounts.player_progress, question_index, answer_submission_time)?;
+        require!(user_answer.len() <= ANSWER_BUFFER_SIZE as usize, QuizError::AnswerTooLong);
+
+        // Open the commitment; it is spent whether or not the answer is correct
+        open_answer_commitment(
+            quiz_set,
+            &ctx.accounts.answer_commitment,
+            &ctx.accounts.payer.to_account_info(),
+            &user_answer,
+            &salt,
+        )?;
+
+        // FIXED: Convert user answer to proper format for Arcium
+        let mut answer_bytes = [0u8; 64];
+        let user_bytes = user_answer.as_bytes();
+        let len = std::cmp::min(user_bytes.len(), 64);
+        answer_bytes[..len].copy_from_slice(&user_bytes[..len]);
+
+        let args = validate_answer_args(&question_block, &answer_bytes);
+
+        require!(
+            ctx.accounts.payer.lamports() >= estimate_computation_fee(args.len()),
+            QuizError::InsufficientComputeFee
+        );
+
+        queue_computation(
+            ctx.accounts, 
+            computation_offset, 
+            args,
+            None,
+            vec![ValidateAnswerCallback::callback_ix(&[
+                CallbackAccount {
+                    pubkey: ctx.accounts.payer.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.answer_submission.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.player_progress.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.question_block.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.quiz_set.key(),
+                    is_writable: true,
+                },
+            ])], 
+        )?;
+
+        emit!(AnswerSubmittedEvent {
+            user: ctx.accounts.payer.key(),
+            quiz_set: ctx.accounts.quiz_set.key(),
+            question_index,
+            computation_offset,
+            timestamp: answer_submission_time,
+        });
+
+        msg!("Answer revealed and validation queued for question {}", question_index);
+        Ok(())
+    }
+
+    // Offline mode: commit to an answer while the Arcium cluster is unavailable.
+    // Only the commitment is queued, revealed when the answer is processed. It is
+    // checked now and judged as of now, so processing it later can't bypass the
+    // run timer or a question's expiry.
+    pub fn enqueue_answer(ctx: Context<EnqueueAnswer>, question_index: u8, commitment: [u8; 32]) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let quiz_set = &ctx.accounts.quiz_set;
+        require!(question_index > 0 && question_index <= quiz_set.question_count, QuizError::InvalidQuestionIndex);
+        check_commit_open(quiz_set, now)?;
+        let question_block = load_question_block(&ctx.accounts.question_block, &quiz_set.key(), question_index)?;
+
+        let player_progress = &mut ctx.accounts.player_progress;
+        if player_progress.player == Pubkey::default() {
+            player_progress.player = ctx.accounts.player.key();
+            player_progress.quiz_set = quiz_set.key();
+        }
+        if player_progress.started_at == 0 {
+            player_progress.started_at = now;
+        }
+        check_answer_allowed(quiz_set, player_progress, &question_block, now)?;
+        require!(quiz_set.buffer_size == ANSWER_BUFFER_SIZE, QuizError::BufferSizeMismatch);
+        check_authority_play(quiz_set, &ctx.accounts.player.key())?;
+        check_gate(quiz_set, &ctx.accounts.player.key(), ctx.accounts.gate_token_account.as_deref())?;
+        record_attempt(quiz_set, player_progress, question_index, now)?;
+
+        let answer_queue = &mut ctx.accounts.answer_queue;
+        answer_queue.player = ctx.accounts.player.key();
+        answer_queue.quiz_set = quiz_set.key();
+        require!(answer_queue.entries.len() < MAX_QUEUED_ANSWERS, QuizError::AnswerQueueFull);
+
+        // Only the commitment is stored; the answer is revealed when it is processed
+        answer_queue.entries.push(QueuedAnswer {
+            question_index,
+            commitment,
+            queued_at: now,
+            queued_slot: Clock::get()?.slot,
+        });
+
+        emit!(AnswerQueued {
+            quiz_set: quiz_set.key(),
+            player: ctx.accounts.player.key(),
+            question_index,
+            queued: answer_queue.entries.len() as u8,
+            timestamp: now,
+        });
+        count_participant(&mut ctx.accounts.quiz_set, &mut ctx.accounts.player_progress);
+
+        msg!("📥 Answer for question {} queued offline ({} pending)", question_index, answer_queue.entries.len());
+        Ok(())
+    }
+
+    // Send the oldest offline answer to Arcium once the cluster is back, revealing
+    // the answer it committed to. One answer per call, since each needs its own
+    // computation account.
+    pub fn process_queued_answers(
+        ctx: Context<ProcessQueuedAnswers>,
+        computation_offset: u64,
+        user_answer: String,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+        require!(!ctx.accounts.answer_queue.entries.is_empty(), QuizError::AnswerQueueEmpty);
+        let queued = ctx.accounts.answer_queue.entries.remove(0);
+
+        let quiz_set = &ctx.accounts.quiz_set;
+        require!(!quiz_set.rotation_in_progress, QuizError::KeyRotationInProgress);
+        require!(user_answer.len() <= ANSWER_BUFFER_SIZE as usize, QuizError::AnswerTooLong);
+        check_answer_reveal(
+            quiz_set,
+            &queued.commitment,
+            queued.queued_slot,
+            &user_answer,
+            &salt,
+            &ctx.accounts.payer.key(),
+        )?;
+        let question_block = load_question_block(&ctx.accounts.question_block, &quiz_set.key(), queued.question_index)?;
+
+        // Judged as of the time it was queued, with the player's state as it is now
+        let player_progress = &mut ctx.accounts.player_progress;
+        check_answer_allowed(quiz_set, player_progress, &question_block, queued.queued_at)?;
+        require!(quiz_set.buffer_size == ANSWER_BUFFER_SIZE, QuizError::BufferSizeMismatch);
+
+        let expected_offset = derive_computation_offset(
+            &ctx.accounts.payer.key(),
+            &quiz_set.key(),
+            queued.question_index,
+            player_progress.submission_count,
+        );
+        require!(computation_offset == expected_offset, QuizError::InvalidComputationOffset);
+        player_progress.submission_count = player_progress.submission_count.saturating_add(1);
+
+        let answer_submission = &mut ctx.accounts.answer_submission;
+        answer_submission.submitter = ctx.accounts.payer.key();
+        answer_submission.quiz_set = quiz_set.key();
+        answer_submission.question_index = queued.question_index;
+        answer_submission.computation_offset = computation_offset;
+        answer_submission.submitted_at = queued.queued_at;
+
+        collect_answer_fee(
+            &ctx.accounts.quiz_set,
+            player_progress,
+            &ctx.accounts.payer,
+            &ctx.accounts.pool_account,
+            &ctx.accounts.system_program,
+            false,
+        )?;
+
+        let mut answer = [0u8; 64];
+        answer[..user_answer.len()].copy_from_slice(user_answer.as_bytes());
+        let args = validate_answer_args(&question_block, &answer);
+        require!(
+            ctx.accounts.payer.lamports() >= estimate_computation_fee(args.len()),
+            QuizError::InsufficientComputeFee
+        );
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![ValidateAnswerCallback::callback_ix(&[
+                CallbackAccount {
+                    pubkey: ctx.accounts.payer.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.answer_submission.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.player_progress.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.question_block.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.quiz_set.key(),
+                    is_writable: true,
+                },
+            ])],
+        )?;
+
+        emit!(AnswerSubmittedEvent {
+            user: ctx.accounts.player_progress.player,
+            quiz_set: ctx.accounts.quiz_set.key(),
+            question_index: queued.question_index,
+            computation_offset,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("📤 Queued answer for question {} sent for validation", queued.question_index);
+        Ok(())
+    }
+
+    // Validate against the 32-byte circuit for quizzes configured with short answers
+    pub fn validate_answer_short_onchain(
+        ctx: Context<ValidateAnswerShortOnchain>,
+        computation_offset: u64,
+        user_answer: String,
+        question_index: u8,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        // Remember who submitted so the callback credits the right player
+        let answer_submission = &mut ctx.accounts.answer_submission;
+        answer_submission.submitter = ctx.accounts.payer.key();
+        answer_submission.quiz_set = ctx.accounts.quiz_set.key();
+        answer_submission.question_index = question_index;
+        answer_submission.computation_offset = computation_offset;
+        answer_submission.submitted_at = Clock::get()?.unix_timestamp;
+        let answer_submission_time = answer_submission.submitted_at;
+
+        let player_progress = &mut ctx.accounts.player_progress;
+        if player_progress.player == Pubkey::default() {
+            player_progress.player = ctx.accounts.payer.key();
+            player_progress.quiz_set = ctx.accounts.quiz_set.key();
+        }
+        if player_progress.started_at == 0 {
+            player_progress.started_at = answer_submission.submitted_at;
+        }
+        require!(
+            question_index > 0 && question_index <= ctx.accounts.quiz_set.question_count,
+            QuizError::InvalidQuestionIndex
+        );
+        let question_block = load_question_block(&ctx.accounts.question_block, &ctx.accounts.quiz_set.key(), question_index)?;
+        check_answer_allowed(&ctx.accounts.quiz_set, player_progress, &question_block, answer_submission_time)?;
+        require!(ctx.accounts.quiz_set.buffer_size == ANSWER_BUFFER_SIZE_SHORT, QuizError::BufferSizeMismatch);
+        require!(!ctx.accounts.quiz_set.rotation_in_progress, QuizError::KeyRotationInProgress);
+        check_authority_play(&ctx.accounts.quiz_set, &ctx.accounts.payer.key())?;
+        check_gate(
+            &ctx.accounts.quiz_set,
+            &ctx.accounts.payer.key(),
+            ctx.accounts.gate_token_account.as_deref(),
+        )?;
+
+        let expected_offset = derive_computation_offset(
+            &ctx.accounts.payer.key(),
+            &ctx.accounts.quiz_set.key(),
+            question_index,
+            player_progress.submission_count,
+        );
+        require!(computation_offset == expected_offset, QuizError::InvalidComputationOffset);
+        player_progress.submission_count = player_progress.submission_count.saturating_add(1);
+        count_participant(&mut ctx.accounts.quiz_set, player_progress);
+
+        collect_answer_fee(
+            &ctx.accounts.quiz_set,
+            player_progress,
+            &ctx.accounts.payer,
+            &ctx.accounts.pool_account,
+            &ctx.accounts.system_program,
+            false,
+        )?;
+
+        let quiz_set = &ctx.accounts.quiz_set;
+        
+        record_attempt(quiz_set, &mut ctx.accounts.player_progress, question_index, answer_submission_time)?;
+        require!(user_answer.len() <= ANSWER_BUFFER_SIZE_SHORT as usize, QuizError::AnswerTooLong);
+
+        // Short answers are committed the same way as full-length ones
+        open_answer_commitment(
+            quiz_set,
+            &ctx.accounts.answer_commitment,
+            &ctx.accounts.payer.to_account_info(),
+            &user_answer,
+            &salt,
+        )?;
+
+        let mut answer_bytes = [0u8; ANSWER_BUFFER_SIZE_SHORT as usize];
+        let user_bytes = user_answer.as_bytes();
+        answer_bytes[..user_bytes.len()].copy_from_slice(user_bytes);
+
+        let mut args = vec![Argument::PlaintextU128(question_block.nonce)];
+        
+        // Add user answer bytes
+        for i in 0..ANSWER_BUFFER_SIZE_SHORT as usize {
+            args.push(Argument::PlaintextU8(answer_bytes[i]));
+        }
+        
+        // Short answers only occupy the leading half of the encrypted correct answer
+        for i in 0..ANSWER_BUFFER_SIZE_SHORT as usize {
+            args.push(Argument::PlaintextU8(question_block.encrypted_y_coordinate[i]));
+        }
+
+        require!(
+            ctx.accounts.payer.lamports() >= estimate_computation_fee(args.len()),
+            QuizError::InsufficientComputeFee
+        );
+
+        queue_computation(
+            ctx.accounts, 
+            computation_offset, 
+            args,
+            None,
+            vec![ValidateAnswerShortCallback::callback_ix(&[
+                CallbackAccount {
+                    pubkey: ctx.accounts.payer.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.answer_submission.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.player_progress.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.question_block.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.quiz_set.key(),
+                    is_writable: true,
+                },
+            ])], 
+        )?;
+
+        emit!(AnswerSubmittedEvent {
+            user: ctx.accounts.payer.key(),
+            quiz_set: ctx.accounts.quiz_set.key(),
+            question_index,
+            computation_offset,
+            timestamp: answer_submission_time,
+        });
+
+        msg!("Short answer validation queued for question {}", question_index);
+        Ok(())
+    }
+
+    // Host-only check that a stored correct answer decrypts to what the host expects.
+    // The host's plaintext goes in encrypted to the MXE; only a match bit comes out.
+    pub fn verify_correct_answer(
+        ctx: Context<VerifyCorrectAnswer>,
+        computation_offset: u64,
+        question_index: u8,
+        encrypted_answer: [[u8; 32]; 4],
+        pub_key: [u8; 32],
+        nonce: u128,
+    ) -> Result<()> {
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        let question_block = &ctx.accounts.question_block;
+        require!(
+            question_index > 0 && question_index <= ctx.accounts.quiz_set.question_count,
+            QuizError::InvalidQuestionIndex
+        );
+
+        let mut args = vec![
+            Argument::ArcisPubkey(pub_key),
+            Argument::PlaintextU128(nonce),
+        ];
+        for word in encrypted_answer {
+            args.push(Argument::EncryptedU128(word));
+        }
+        args.push(Argument::PlaintextU128(question_block.nonce));
+        for i in 0..ANSWER_BUFFER_SIZE as usize {
+            args.push(Argument::PlaintextU8(question_block.encrypted_y_coordinate[i]));
+        }
+
+        require!(
+            ctx.accounts.payer.lamports() >= estimate_computation_fee(args.len()),
+            QuizError::InsufficientComputeFee
+        );
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![VerifyCorrectAnswerCallback::callback_ix(&[
+                CallbackAccount {
+                    pubkey: ctx.accounts.question_block.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.quiz_set.key(),
+                    is_writable: false,
+                },
+            ])],
+        )?;
+
+        msg!("Correct answer verification queued for question {}", question_index);
+        Ok(())
+    }
+
+    // Run a fixed public sample through the cluster so operators can compare
+    // the emitted result hash against a known-good vector before migrating
+    pub fn crosscheck_circuit(
+        ctx: Context<CrosscheckCircuit>,
+        computation_offset: u64,
+        sample: [u8; 32],
+        nonce: u128,
+    ) -> Result<()> {
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        let mut args = Vec::with_capacity(sample.len() + 1);
+        for byte in sample {
+            args.push(Argument::PlaintextU8(byte));
+        }
+        args.push(Argument::PlaintextU128(nonce));
+
+        require!(
+            ctx.accounts.payer.lamports() >= estimate_computation_fee(args.len()),
+            QuizError::InsufficientComputeFee
+        );
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![CrosscheckCircuitCallback::callback_ix(&[])],
+        )?;
+
+        msg!("Circuit crosscheck queued");
+        Ok(())
+    }
+
+    // ===== KEY ROTATION FUNCTIONS =====
+
+    // Start moving every question block to a new MXE key. Answers are paused
+    // until each block has been re-encrypted with rotate_block. Starting again
+    // with the key of an aborted rotation resumes it.
+    pub fn begin_key_rotation(ctx: Context<BeginKeyRotation>, new_mxe_pubkey: [u8; 32]) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        require!(!quiz_set.rotation_in_progress, QuizError::KeyRotationInProgress);
+
+        let resuming = new_mxe_pubkey == quiz_set.rotation_target_key
+            && quiz_set.rotated_count > 0
+            && quiz_set.rotated_count < quiz_set.question_count;
+        if !resuming {
+            // Outside a rotation every block shares the quiz's current key
+            require!(
+                new_mxe_pubkey != ctx.accounts.question_block.arcium_pubkey,
+                QuizError::SameRotationKey
+            );
+            quiz_set.rotated_count = 0;
+        }
+
+        quiz_set.rotation_target_key = new_mxe_pubkey;
+        quiz_set.rotation_in_progress = true;
+        quiz_set.rotation_pending_index = 0;
+        quiz_set.rotation_pending_nonce = 0;
+
+        emit!(KeyRotationStarted {
+            quiz_set: quiz_set.key(),
+            new_mxe_pubkey,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("🔑 Key rotation started for quiz set '{}'", quiz_set.name);
+        Ok(())
+    }
+
+    // Give up on a rotation that can't finish (e.g. a lost callback) so answers
+    // resume. Blocks already moved keep the new key; a result still in flight
+    // no longer matches a pending block and is dropped.
+    pub fn abort_key_rotation(ctx: Context<AbortKeyRotation>) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        require!(quiz_set.rotation_in_progress, QuizError::NoKeyRotation);
+
+        quiz_set.rotation_in_progress = false;
+        quiz_set.rotation_pending_index = 0;
+        quiz_set.rotation_pending_nonce = 0;
+
+        emit!(KeyRotationAborted {
+            quiz_set: quiz_set.key(),
+            target_mxe_pubkey: quiz_set.rotation_target_key,
+            rotated_count: quiz_set.rotated_count,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("🔑 Key rotation aborted for quiz set '{}' after {} blocks", quiz_set.name, quiz_set.rotated_count);
+        Ok(())
+    }
+
+    // Re-encrypt one block under the rotation key. Blocks are rotated one at a
+    // time: the next call is accepted once the previous callback has landed.
+    pub fn rotate_block(
+        ctx: Context<RotateBlock>,
+        computation_offset: u64,
+        question_index: u8,
+        new_nonce: u128,
+    ) -> Result<()> {
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        let question_block = &ctx.accounts.question_block;
+        require!(quiz_set.rotation_in_progress, QuizError::NoKeyRotation);
+        require!(quiz_set.rotation_pending_index == 0, QuizError::KeyRotationInProgress);
+        require!(
+            question_block.arcium_pubkey != quiz_set.rotation_target_key,
+            QuizError::BlockAlreadyRotated
+        );
+
+        quiz_set.rotation_pending_index = question_index;
+        quiz_set.rotation_pending_nonce = new_nonce;
+
+        let mut args = Vec::with_capacity(2 * ANSWER_BUFFER_SIZE as usize + 2);
+        for i in 0..ANSWER_BUFFER_SIZE as usize {
+            args.push(Argument::PlaintextU8(question_block.encrypted_x_coordinate[i]));
+        }
+        for i in 0..ANSWER_BUFFER_SIZE as usize {
+            args.push(Argument::PlaintextU8(question_block.encrypted_y_coordinate[i]));
+        }
+        args.push(Argument::PlaintextU128(question_block.nonce));
+        args.push(Argument::PlaintextU128(new_nonce));
+
+        require!(
+            ctx.accounts.payer.lamports() >= estimate_computation_fee(args.len()),
+            QuizError::InsufficientComputeFee
+        );
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![RotateBlockCallback::callback_ix(&[
+                CallbackAccount {
+                    pubkey: ctx.accounts.question_block.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.quiz_set.key(),
+                    is_writable: true,
+                },
+            ])],
+        )?;
+
+        msg!("🔑 Rotation queued for question {}", question_index);
+        Ok(())
+    }
+
+    // Reveal the player's next hint for a question. Hints come in order and the
+    // nth one costs n * HINT_PENALTY_POINTS, charged when it is requested. The
+    // circuit encrypts the hint to the player's key, so only they can read it.
+    pub fn request_next_hint(
+        ctx: Context<RequestNextHint>,
+        computation_offset: u64,
+        question_index: u8,
+        player_pub_key: [u8; 32],
+        player_nonce: u128,
+    ) -> Result<()> {
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+        require!(
+            question_index > 0 && question_index <= ctx.accounts.quiz_set.question_count,
+            QuizError::InvalidQuestionIndex
+        );
+
+        let question_block = &ctx.accounts.question_block;
+        let player_progress = &mut ctx.accounts.player_progress;
+        if player_progress.player == Pubkey::default() {
+            player_progress.player = ctx.accounts.payer.key();
+            player_progress.quiz_set = ctx.accounts.quiz_set.key();
+        }
+        if player_progress.started_at == 0 {
+            player_progress.started_at = Clock::get()?.unix_timestamp;
+        }
+        require!(
+            ctx.accounts.quiz_set.password_key.is_none() || player_progress.joined,
+            QuizError::NotJoined
+        );
+        require!(!player_progress.eliminated, QuizError::PlayerEliminated);
+        require!(!player_progress.finished, QuizError::RunEnded);
+        require!(!player_progress.answers_revealed, QuizError::AnswersRevealed);
+
+        let hints_used = &mut player_progress.hints_used[(question_index - 1) as usize];
+        require!(*hints_used < question_block.hint_count, QuizError::NoHintsLeft);
+        let hint = question_block.hints[*hints_used as usize];
+        *hints_used += 1;
+        let hint_number = *hints_used;
+
+        // Whatever the current score can't cover is owed out of later points
+        let penalty = HINT_PENALTY_POINTS * hint_number as u32;
+        let deducted = penalty.min(player_progress.score);
+        player_progress.score -= deducted;
+        player_progress.hint_debt = player_progress.hint_debt.saturating_add(penalty - deducted);
+        player_progress.hint_penalty = player_progress.hint_penalty.saturating_add(penalty);
+
+        let mut args = Vec::with_capacity(HINT_SIZE + 3);
+        args.push(Argument::ArcisPubkey(player_pub_key));
+        args.push(Argument::PlaintextU128(player_nonce));
+        for byte in hint.iter() {
+            args.push(Argument::PlaintextU8(*byte));
+        }
+        args.push(Argument::PlaintextU128(question_block.hint_nonce));
+
+        require!(
+            ctx.accounts.payer.lamports() >= estimate_computation_fee(args.len()),
+            QuizError::InsufficientComputeFee
+        );
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![RevealHintCallback::callback_ix(&[
+                CallbackAccount {
+                    pubkey: ctx.accounts.player_progress.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.question_block.key(),
+                    is_writable: false,
+                },
+            ])],
+        )?;
+
+        emit!(HintRequested {
+            quiz_set: ctx.accounts.quiz_set.key(),
+            player: ctx.accounts.payer.key(),
+            question_index: question_index as u32,
+            hint_number,
+            penalty,
+        });
+
+        msg!("💡 Hint {} queued for question {} (-{} points)", hint_number, question_index, penalty);
+        Ok(())
+    }
+
+    // Once a player's run is over, show them the correct answer to a question
+    // they got wrong. The circuit re-encrypts it to the player's own key, so the
+    // result is readable by them alone and the answer never goes public.
+    pub fn reveal_my_mistakes(
+        ctx: Context<RevealMyMistakes>,
+        computation_offset: u64,
+        question_index: u8,
+        player_pub_key: [u8; 32],
+        player_nonce: u128,
+    ) -> Result<()> {
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        // Only the caller's own run counts; someone else winning doesn't end it.
+        // Quizzes that reveal on a miss have nothing at stake, so they skip the wait.
+        let quiz_set = &ctx.accounts.quiz_set;
+        let player_progress = &mut ctx.accounts.player_progress;
+        let now = Clock::get()?.unix_timestamp;
+        let run_timed_out = quiz_set
+            .run_deadline(player_progress.started_at)?
+            .is_some_and(|deadline| now > deadline);
+        require!(
+            quiz_set.reveal_on_wrong || player_progress.finished || player_progress.eliminated || run_timed_out,
+            QuizError::PlayerNotCompleted
+        );
+
+        let question_bit = index_to_bit(question_index.into())?;
+        require!(
+            player_progress.wrong_mask & question_bit != 0 && player_progress.scored_mask & question_bit == 0,
+            QuizError::NotAMistake
+        );
+
+        // Having seen an answer, the player can't answer again or reset to replay
+        if !quiz_set.reveal_on_wrong {
+            player_progress.answers_revealed = true;
+        }
+
+        let question_block = &ctx.accounts.question_block;
+        let mut args = vec![
+            Argument::ArcisPubkey(player_pub_key),
+            Argument::PlaintextU128(player_nonce),
+        ];
+        for byte in question_block.encrypted_y_coordinate.iter() {
+            args.push(Argument::PlaintextU8(*byte));
+        }
+        args.push(Argument::PlaintextU128(question_block.nonce));
+
+        require!(
+            ctx.accounts.payer.lamports() >= estimate_computation_fee(args.len()),
+            QuizError::InsufficientComputeFee
+        );
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![RevealAnswerCallback::callback_ix(&[
+                CallbackAccount {
+                    pubkey: ctx.accounts.player_progress.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.question_block.key(),
+                    is_writable: false,
+                },
+            ])],
+        )?;
+
+        msg!("🔎 Answer reveal queued for question {}", question_index);
+        Ok(())
+    }
+
+    // ===== SCORING SYSTEM FUNCTIONS =====
+
+    // Record quiz completion and update scores. Once per player per quiz set;
+    // the win, score and question count come from the quiz set and the player's
+    // verified progress, never from the caller.
+    pub fn record_quiz_completion(
+        ctx: Context<RecordQuizCompletion>,
+        reward_amount: u64,
+    ) -> Result<()> {
+        let quiz_set = &ctx.accounts.quiz_set;
+        let topic = &mut ctx.accounts.topic;
+        let user_score = &mut ctx.accounts.user_score;
+        let quiz_history = &mut ctx.accounts.quiz_history;
+        let is_winner = quiz_set.is_winner(&ctx.accounts.user.key());
+
+        let player_progress = &mut ctx.accounts.player_progress;
+        if player_progress.player == Pubkey::default() {
+            player_progress.player = ctx.accounts.user.key();
+            player_progress.quiz_set = quiz_set.key();
+        }
+        player_progress.completion_recorded = true;
+        let score = player_progress.correct_count;
+        let weighted_score = player_progress.score;
+        let total_questions = quiz_set.question_count;
+
+        // Initialize user score if first time
+        if user_score.user == Pubkey::default() {
+            topic.total_participants += 1;
+            user_score.user = ctx.accounts.user.key();
+            user_score.topic = topic.key();
+            user_score.score = 0;
+            user_score.total_completed = 0;
+            user_score.last_activity = 0;
+            user_score.total_rewards = 0;
+            user_score.best_score = 0;
+            user_score.best_score_quiz = Pubkey::default();
+            user_score.link_nonce = 0;
+        }
+
+        // Update user score
+        user_score.total_completed += 1;
+        user_score.last_activity = Clock::get()?.unix_timestamp;
+        
+        if is_winner {
+            user_score.score += 1;
+            user_score.total_rewards += reward_amount;
+        }
+
+        // Track personal best for this topic
+        let is_personal_best = weighted_score > user_score.best_score;
+        if is_personal_best {
+            user_score.best_score = weighted_score;
+            user_score.best_score_quiz = quiz_set.key();
+        }
+
+        if let Some(leaderboard) = ctx.accounts.leaderboard.as_mut() {
+            update_leaderboard(leaderboard, user_score, topic.leaderboard_min_score);
+        }
+
+        // Cross-topic totals; rewards are added when actually claimed
+        let global_stats = &mut ctx.accounts.global_stats;
+        global_stats.user = ctx.accounts.user.key();
+        global_stats.total_completions += 1;
+        if is_winner {
+            global_stats.total_wins += 1;
+        }
+        global_stats.last_activity = Clock::get()?.unix_timestamp;
+
+        // Record quiz history
+        quiz_history.user = ctx.accounts.user.key();
+        quiz_history.quiz_set = quiz_set.key();
+        quiz_history.topic = topic.key();
+        quiz_history.completed_at = Clock::get()?.unix_timestamp;
+        quiz_history.score = score;
+        quiz_history.total_questions = total_questions;
+        quiz_history.is_winner = is_winner;
+        quiz_history.reward_claimed = if is_winner { reward_amount } else { 0 };
+
+        emit!(QuizCompletionRecorded {
+            user: ctx.accounts.user.key(),
+            quiz_set: quiz_set.key(),
+            topic: topic.key(),
+            is_winner,
+            score,
+            total_questions,
+            reward_amount: if is_winner { reward_amount } else { 0 },
+            best_score: user_score.best_score,
+            best_score_quiz: user_score.best_score_quiz,
+            is_personal_best,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Quiz completion recorded for user {} - Score: {}/{} - Winner: {}", 
+             ctx.accounts.user.key(), score, total_questions, is_winner);
+        Ok(())
+    }
+
+    // Merge a score recorded under an ephemeral key into the user's main score.
+    // The ephemeral key proves consent by signing the link message in a preceding
+    // Ed25519 program instruction.
+    pub fn link_ephemeral_score(ctx: Context<LinkEphemeralScore>) -> Result<()> {
+        let user = ctx.accounts.user.key();
+        let ephemeral = ctx.accounts.ephemeral.key();
+        let topic = ctx.accounts.topic.key();
+
+        require!(user != ephemeral, QuizError::InvalidLinkProof);
+        let nonce = ctx.accounts.main_score.link_nonce;
+        verify_link_proof(&ctx.accounts.instructions_sysvar, &ephemeral, &link_message(&user, &topic, nonce))?;
+
+        let ephemeral_score = &ctx.accounts.ephemeral_score;
+        let main_score = &mut ctx.accounts.main_score;
+
+        // Initialize user score if first time
+        if main_score.user == Pubkey::default() {
+            main_score.user = user;
+            main_score.topic = topic;
+            main_score.score = 0;
+            main_score.total_completed = 0;
+            main_score.last_activity = 0;
+            main_score.total_rewards = 0;
+            main_score.best_score = 0;
+            main_score.best_score_quiz = Pubkey::default();
+            main_score.link_nonce = 0;
+        }
+
+        // Spend the nonce so the same signature can't merge a later ephemeral score
+        main_score.link_nonce += 1;
+        main_score.score += ephemeral_score.score;
+        main_score.total_completed += ephemeral_score.total_completed;
+        main_score.total_rewards += ephemeral_score.total_rewards;
+        main_score.last_activity = std::cmp::max(main_score.last_activity, ephemeral_score.last_activity);
+
+        if ephemeral_score.best_score > main_score.best_score {
+            main_score.best_score = ephemeral_score.best_score;
+            main_score.best_score_quiz = ephemeral_score.best_score_quiz;
+        }
+
+        emit!(EphemeralScoreLinked {
+            user,
+            ephemeral,
+            topic,
+            score: ephemeral_score.score,
+            total_completed: ephemeral_score.total_completed,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Ephemeral score {} linked into {}", ephemeral, user);
+        Ok(())
+    }
+
+    pub fn init_topic_leaderboard(ctx: Context<InitTopicLeaderboard>) -> Result<()> {
+        let leaderboard = &mut ctx.accounts.leaderboard;
+        leaderboard.topic = ctx.accounts.topic.key();
+        leaderboard.entries = Vec::new();
+
+        msg!("Leaderboard created for topic '{}'", ctx.accounts.topic.name);
+        Ok(())
+    }
+
+    // Emit the escrowed total for every reward currency used in the topic
+    pub fn get_topic_reward_stats(ctx: Context<GetTopicRewardStats>) -> Result<()> {
+        let reward_stats = &ctx.accounts.reward_stats;
+
+        for entry in reward_stats.entries.iter() {
+            emit!(TopicRewardTotal {
+                topic: reward_stats.topic,
+                mint: entry.mint,
+                is_native_sol: entry.mint == NATIVE_SOL_MINT,
+                escrowed: entry.escrowed,
+                total_deposited: entry.total_deposited,
+            });
+        }
+
+        Ok(())
+    }
+
+    // Emit the user's position on the topic leaderboard (UNRANKED if absent)
+    pub fn get_user_rank(ctx: Context<GetUserRank>) -> Result<()> {
+        let user = ctx.accounts.user.key();
+        let rank = ctx
+            .accounts
+            .leaderboard
+            .entries
+            .iter()
+            .position(|entry| entry.user == user)
+            .map(|index| index as u32 + 1)
+            .unwrap_or(UNRANKED);
+
+        emit!(UserRank {
+            user,
+            topic: ctx.accounts.topic.key(),
+            rank,
+            total_participants: ctx.accounts.topic.total_participants,
+        });
+
+        msg!("User {} rank: {} of {}", user, rank, ctx.accounts.topic.total_participants);
+        Ok(())
+    }
+
+    // Countdown for a timed quiz, computed on-chain so clients don't have to
+    // correct for their own clock skew
+    pub fn get_time_remaining(ctx: Context<GetTimeRemaining>) -> Result<()> {
+        let progress = &ctx.accounts.player_progress;
+        let deadline = ctx
+            .accounts
+            .quiz_set
+            .run_deadline(progress.started_at)?
+            .ok_or(QuizError::QuizNotTimed)?;
+        let now = Clock::get()?.unix_timestamp;
+        let seconds_left = std::cmp::max(deadline - now, 0);
+
+        emit!(TimeRemaining {
+            quiz_set: ctx.accounts.quiz_set.key(),
+            player: progress.player,
+            seconds_left,
+            expired: seconds_left == 0,
+        });
+
+        msg!("Player {} has {}s left", progress.player, seconds_left);
+        Ok(())
+    }
+
+    // Get user's overall stats across all topics, plus the sum of the per-topic
+    // UserScore accounts passed in remaining_accounts (one per topic)
+    pub fn get_user_global_stats(
+        ctx: Context<GetUserGlobalStats>,
+    ) -> Result<()> {
+        require!(ctx.remaining_accounts.len() <= MAX_BATCH, QuizError::BatchTooLarge);
+        let global_stats = &ctx.accounts.global_stats;
+        let user = ctx.accounts.user.key();
+
+        let mut topics: Vec<Pubkey> = Vec::with_capacity(ctx.remaining_accounts.len());
+        let (mut topic_score, mut topic_completions, mut topic_rewards) = (0u32, 0u32, 0u64);
+        for info in ctx.remaining_accounts.iter() {
+            require!(info.owner == &crate::ID, QuizError::InvalidUserScore);
+            let user_score = UserScore::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+            // Each topic counts once, so passing an account twice can't inflate the totals
+            require!(
+                user_score.user == user && !topics.contains(&user_score.topic),
+                QuizError::InvalidUserScore
+            );
+            topics.push(user_score.topic);
+
+            // UserScore.score is the topic score: one point per quiz won
+            topic_score = topic_score.saturating_add(user_score.score);
+            topic_completions = topic_completions.saturating_add(user_score.total_completed);
+            topic_rewards = topic_rewards.saturating_add(user_score.total_rewards);
+        }
+
+        emit!(UserGlobalStats {
+            user,
+            total_rewards: global_stats.total_rewards,
+            total_wins: global_stats.total_wins,
+            total_completions: global_stats.total_completions,
+            win_rate_bps: win_rate_bps(global_stats.total_wins, global_stats.total_completions),
+            topic_count: topics.len() as u8,
+            topic_score,
+            topic_completions,
+            topic_rewards,
+        });
+
+        msg!("User global stats request for: {} ({} topics)", user, topics.len());
+        Ok(())
+    }
+
+    // Join a password-protected quiz; answering is refused until the player
+    // has joined. The password never goes on chain: the preceding Ed25519
+    // program instruction must be signed by the password-derived key over
+    // join_message(player, quiz_set), so a seen proof admits no one else.
+    pub fn join_quiz(ctx: Context<JoinQuiz>) -> Result<()> {
+        let quiz_set = &ctx.accounts.quiz_set;
+        let password_key = quiz_set.password_key.ok_or(QuizError::NoPassword)?;
+        let message = join_message(&ctx.accounts.player.key(), &quiz_set.key());
+        verify_link_proof(&ctx.accounts.instructions_sysvar, &password_key, &message)
+            .map_err(|_| error!(QuizError::WrongPassword))?;
+
+        let player_progress = &mut ctx.accounts.player_progress;
+        if player_progress.player == Pubkey::default() {
+            player_progress.player = ctx.accounts.player.key();
+            player_progress.quiz_set = quiz_set.key();
+        }
+        player_progress.joined = true;
+
+        msg!("Player {} joined quiz set '{}'", player_progress.player, quiz_set.name);
+        Ok(())
+    }
+
+    // Register a second key that must co-sign this player's claim on quizzes
+    // with require_claim_proof. Set once, before the player wins, so whoever
+    // later holds the winning wallet can't claim without it.
+    pub fn register_proof_key(ctx: Context<RegisterProofKey>, proof_key: Pubkey) -> Result<()> {
+        let quiz_set = &ctx.accounts.quiz_set;
+        let player = ctx.accounts.player.key();
+        require!(proof_key != Pubkey::default() && proof_key != player, QuizError::InvalidProofKey);
+
+        let player_progress = &mut ctx.accounts.player_progress;
+        if player_progress.player == Pubkey::default() {
+            player_progress.player = player;
+            player_progress.quiz_set = quiz_set.key();
+        }
+        require!(player_progress.proof_key == Pubkey::default(), QuizError::ProofKeyAlreadySet);
+        require!(
+            player_progress.completed_at == 0
+                && !quiz_set.is_winner(&player)
+                && quiz_set.runner_up != Some(player),
+            QuizError::ProofKeyTooLate
+        );
+        player_progress.proof_key = proof_key;
+
+        msg!("🔑 Player {} registered a claim proof key", player);
+        Ok(())
+    }
+
+    // Start a quiz over. Allowed on practice quizzes, or on any quiz while it has
+    // no winner yet, so a finished competitive result can't be wiped.
+    pub fn reset_player_progress(ctx: Context<ResetPlayerProgress>) -> Result<()> {
+        let quiz_set = &ctx.accounts.quiz_set;
+        require!(!ctx.accounts.player_progress.answers_revealed, QuizError::AnswersRevealed);
+        require!(quiz_set.is_practice, QuizError::ResetNotAllowed);
+        // Starting over would let the same questions be paid again
+        require!(quiz_set.per_question_reward == 0, QuizError::ResetNotAllowed);
+        // Elimination and per-question attempt counts survive a reset, so replaying
+        // can't get around sudden death or the attempt limits
+        require!(!ctx.accounts.player_progress.eliminated, QuizError::PlayerEliminated);
+
+        let now = Clock::get()?.unix_timestamp;
+        let player_progress = &mut ctx.accounts.player_progress;
+        player_progress.correct_count = 0;
+        player_progress.wrong_count = 0;
+        player_progress.finished = false;
+        player_progress.score = 0;
+        player_progress.answered_mask = 0;
+        player_progress.scored_mask = 0;
+        player_progress.wrong_mask = 0;
+        player_progress.completed_at = 0;
+        player_progress.category_correct = [0; MAX_CATEGORIES];
+        player_progress.last_wrong_at = 0;
+        player_progress.last_wrong_question = 0;
+        player_progress.hints_used = [0u8; MAX_QUESTION_COUNT as usize];
+        player_progress.hint_penalty = 0;
+        player_progress.hint_debt = 0;
+        player_progress.pending_knockout = false;
+        player_progress.started_at = now;
+        player_progress.last_activity = now;
+        // submission_count is kept so retries still get fresh computation offsets,
+        // attempts and last_attempt so limits and cooldowns carry over
+
+        emit!(PlayerProgressReset {
+            quiz_set: quiz_set.key(),
+            player: player_progress.player,
+            timestamp: now,
+        });
+
+        msg!("🔄 Progress reset for {} on quiz '{}'", player_progress.player, quiz_set.name);
+        Ok(())
+    }
+
+    // Issue a verifiable record of a player's result in a finished quiz.
+    // The attestation PDA can only be written by this program, so off-chain
+    // systems can trust the event by checking it against the stored digest.
+    pub fn attest_completion(ctx: Context<AttestCompletion>) -> Result<()> {
+        let quiz_set = &ctx.accounts.quiz_set;
+        let player_progress = &ctx.accounts.player_progress;
+        let user = ctx.accounts.user.key();
+
+        let attestation = &mut ctx.accounts.attestation;
+        attestation.user = user;
+        attestation.quiz_set = quiz_set.key();
+        attestation.score = player_progress.score;
+        attestation.correct_count = player_progress.correct_count;
+        attestation.question_count = quiz_set.question_count;
+        attestation.is_winner = quiz_set.is_winner(&user);
+        attestation.attested_at = Clock::get()?.unix_timestamp;
+        attestation.bump = ctx.bumps.attestation;
+        attestation.digest = attestation_digest(attestation);
+
+        emit!(CompletionAttestation {
+            attestation: attestation.key(),
+            user,
+            quiz_set: attestation.quiz_set,
+            score: attestation.score,
+            correct_count: attestation.correct_count,
+            question_count: attestation.question_count,
+            is_winner: attestation.is_winner,
+            attested_at: attestation.attested_at,
+            digest: attestation.digest,
+        });
+
+        msg!("📜 Completion attested for {} on quiz '{}'", user, quiz_set.name);
+        Ok(())
+    }
+
+    // ===== NEW DEVNET TESTING FUNCTION =====
+    
+    // Function to manually set winner for devnet testing (bypasses Arcium callback)
+    pub fn set_winner_for_devnet(
+        ctx: Context<SetWinnerForDevnet>,
+        user_answers: Vec<String>,
+        correct_answers: Vec<String>,
+    ) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        
+        // Debug logging
+        msg!("🔍 Debug: Setting winner for devnet");
+        msg!("🔍 Debug: quiz_set.key() = {}", quiz_set.key());
+        msg!("🔍 Debug: authority.key() = {}", ctx.accounts.authority.key());
+        check_authority_play(quiz_set, &ctx.accounts.authority.key())?;
+        
+        // Set winner to authority (for devnet testing)
+        quiz_set.winner = Some(ctx.accounts.authority.key());
+        quiz_set.correct_answers_count = user_answers.len() as u8;
+        quiz_set.is_reward_claimed = false;
+        quiz_set.won_at = Clock::get()?.unix_timestamp;
+        quiz_set.won_slot = Clock::get()?.slot;
+        warn_if_underfunded(quiz_set, &ctx.accounts.vault, ctx.accounts.authority.key())?;
+        
+        msg!("✅ Winner set successfully: {}", ctx.accounts.authority.key());
+        msg!("✅ correct_answers_count set to: {}", quiz_set.correct_answers_count);
+        
+        Ok(())
+    }
+
+    // Add a new function to set the winner for the actual correct answerer
+    pub fn set_winner_for_user(
+        ctx: Context<SetWinnerForUser>,
+        winner_pubkey: Pubkey,  // ← Receive pubkey of the actual correct answerer
+        correct_answers_count: u8,
+    ) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        let setter = &ctx.accounts.setter;
+        check_authority_play(quiz_set, &winner_pubkey)?;
+        let clock = Clock::get()?;
+        require!(
+            quiz_set.max_winners <= 1 || quiz_set.has_open_winner_slot(clock.slot) || quiz_set.is_winner(&winner_pubkey),
+            QuizError::WinnersFull
+        );
+        
+        // Set winner as the actual correct answerer, not the authority
+        // (a second finisher in the same slot becomes a co-winner)
+        quiz_set.correct_answers_count = correct_answers_count;
+        record_finisher(quiz_set, winner_pubkey, clock.unix_timestamp, clock.slot);
+        warn_if_underfunded(quiz_set, &ctx.accounts.vault, winner_pubkey)?;
+        
+        msg!("✅ correct_answers_count set to: {}", correct_answers_count);
+        msg!("✅ Set by: {}", setter.key());
+        
+        Ok(())
+    }
+
+    // ===== ARCIUM CALLBACKS =====
+
+    // FIXED: Proper callback handling with actual result logic
+    #[arcium_callback(encrypted_ix = "validate_answer")]
+    pub fn validate_answer_callback(
+        ctx: Context<ValidateAnswerCallback>,
+        output: ComputationOutputs<ValidateAnswerOutput>,
+    ) -> Result<()> {
+        let ValidateAnswerOutput { field_0: is_correct } = computation_result(output)?;
+        msg!("Arcium computation completed successfully");
+
+        // Update quiz set with answer result
+        apply_answer_result(
+            &mut ctx.accounts.quiz_set,
+            &mut ctx.accounts.question_block,
+            &mut ctx.accounts.player_progress,
+            ctx.accounts.answer_submission.computation_offset,
+            is_correct,
+        )
+    }
+
+    #[arcium_callback(encrypted_ix = "validate_answer_short")]
+    pub fn validate_answer_short_callback(
+        ctx: Context<ValidateAnswerShortCallback>,
+        output: ComputationOutputs<ValidateAnswerShortOutput>,
+    ) -> Result<()> {
+        let ValidateAnswerShortOutput { field_0: is_correct } = computation_result(output)?;
+        msg!("Arcium computation completed successfully");
+
+        apply_answer_result(
+            &mut ctx.accounts.quiz_set,
+            &mut ctx.accounts.question_block,
+            &mut ctx.accounts.player_progress,
+            ctx.accounts.answer_submission.computation_offset,
+            is_correct,
+        )
+    }
+
+    #[arcium_callback(encrypted_ix = "verify_correct_answer")]
+    pub fn verify_correct_answer_callback(
+        ctx: Context<VerifyCorrectAnswerCallback>,
+        output: ComputationOutputs<VerifyCorrectAnswerOutput>,
+    ) -> Result<()> {
+        let VerifyCorrectAnswerOutput { field_0: ok } = computation_result(output)?;
+
+        emit!(CorrectAnswerVerified {
+            quiz_set: ctx.accounts.quiz_set.key(),
+            question_index: ctx.accounts.question_block.question_index,
+            ok,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Correct answer verification result: {}", ok);
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "crosscheck_circuit")]
+    pub fn crosscheck_circuit_callback(
+        ctx: Context<CrosscheckCircuitCallback>,
+        output: ComputationOutputs<CrosscheckCircuitOutput>,
+    ) -> Result<()> {
+        let CrosscheckCircuitOutput { field_0: result } = computation_result(output)?;
+        let result_hash = hashv(&[b"k3hoot:crosscheck", &result]).to_bytes();
+
+        emit!(CircuitCrosscheck {
+            result,
+            result_hash,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Circuit crosscheck completed");
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "rotate_block")]
+    pub fn rotate_block_callback(
+        ctx: Context<RotateBlockCallback>,
+        output: ComputationOutputs<RotateBlockOutput>,
+    ) -> Result<()> {
+        let RotateBlockOutput { field_0: rotated } = computation_result(output)?;
+
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        let question_block = &mut ctx.accounts.question_block;
+        require!(
+            quiz_set.rotation_pending_index as u32 == question_block.question_index,
+            QuizError::InvalidQuestionBlock
+        );
+
+        // Swap the block's contribution to the content commitment
+        xor_into(&mut quiz_set.content_hash, &question_block_hash(question_block));
+        question_block.encrypted_x_coordinate.copy_from_slice(&rotated[..64]);
+        question_block.encrypted_y_coordinate.copy_from_slice(&rotated[64..]);
+        let old_nonce = question_block.nonce;
+        for alt_answer in question_block.alt_answers.iter_mut() {
+            reencrypt(alt_answer, old_nonce, quiz_set.rotation_pending_nonce);
+        }
+        question_block.nonce = quiz_set.rotation_pending_nonce;
+        question_block.arcium_pubkey = quiz_set.rotation_target_key;
+        xor_into(&mut quiz_set.content_hash, &question_block_hash(question_block));
+
+        quiz_set.rotation_pending_index = 0;
+        quiz_set.rotation_pending_nonce = 0;
+        quiz_set.rotated_count += 1;
+        let now = Clock::get()?.unix_timestamp;
+
+        emit!(QuestionBlockRotated {
+            quiz_set: quiz_set.key(),
+            question_index: question_block.question_index,
+            rotated_count: quiz_set.rotated_count,
+            timestamp: now,
+        });
+
+        if quiz_set.rotated_count >= quiz_set.question_count {
+            quiz_set.rotation_in_progress = false;
+
+            emit!(KeyRotationCompleted {
+                quiz_set: quiz_set.key(),
+                new_mxe_pubkey: quiz_set.rotation_target_key,
+                timestamp: now,
+            });
+
+            msg!("🔑 Key rotation completed for quiz set '{}'", quiz_set.name);
+        }
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "reveal_hint")]
+    pub fn reveal_hint_callback(
+        ctx: Context<RevealHintCallback>,
+        output: ComputationOutputs<RevealHintOutput>,
+    ) -> Result<()> {
+        let RevealHintOutput { field_0: hint } = computation_result(output)?;
+
+        let question_block = &ctx.accounts.question_block;
+        emit!(HintRevealed {
+            quiz_set: question_block.quiz_set,
+            player: ctx.accounts.player_progress.player,
+            question_index: question_block.question_index,
+            encryption_key: hint.encryption_key,
+            nonce: hint.nonce,
+            ciphertexts: hint.ciphertexts,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("💡 Hint revealed to its player for question {}", question_block.question_index);
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "reveal_answer")]
+    pub fn reveal_answer_callback(
+        ctx: Context<RevealAnswerCallback>,
+        output: ComputationOutputs<RevealAnswerOutput>,
+    ) -> Result<()> {
+        let RevealAnswerOutput { field_0: answer } = computation_result(output)?;
+
+        let question_block = &ctx.accounts.question_block;
+        emit!(MistakeRevealed {
+            quiz_set: question_block.quiz_set,
+            player: ctx.accounts.player_progress.player,
+            question_index: question_block.question_index,
+            encryption_key: answer.encryption_key,
+            nonce: answer.nonce,
+            ciphertexts: answer.ciphertexts,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("🔎 Answer revealed to its player for question {}", question_block.question_index);
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "encrypt_quiz")]
+    pub fn encrypt_quiz_callback(
+        ctx: Context<EncryptQuizCallback>,
+        output: ComputationOutputs<EncryptQuizOutput>,
+    ) -> Result<()> {
+        let EncryptQuizOutput { field_0: encrypted } = computation_result(output)?;
+
+        // The ciphertext, then the chunk index and count passed through the circuit
+        let mut encrypted_data = [0u8; 64];
+        encrypted_data.copy_from_slice(&encrypted[..64]);
+        let (chunk_index, chunk_count) = (encrypted[64], encrypted[65]);
+
+        emit!(QuizDataEncryptedEvent {
+            question_block: ctx.accounts.question_block.key(),
+            chunk_index,
+            chunk_count,
+            encrypted_data,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Quiz data encrypted successfully (chunk {} of {})", chunk_index + 1, chunk_count);
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "decrypt_quiz")]
+    pub fn decrypt_quiz_callback(
+        ctx: Context<DecryptQuizCallback>,
+        output: ComputationOutputs<DecryptQuizOutput>,
+    ) -> Result<()> {
+        let DecryptQuizOutput { field_0: decrypted_data } = computation_result(output)?;
+
+        emit!(QuizDataDecryptedEvent {
+            decrypted_data,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Quiz data decrypted successfully");
+        Ok(())
+    }
+
+    // ===== NEW VAULT MANAGEMENT FUNCTIONS =====
+
+    pub fn claim_reward(ctx: Context<ClaimReward>, destination: Option<Pubkey>) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        let vault = &ctx.accounts.vault;
+        let claimer = &ctx.accounts.claimer;
+        
+        // The claimer signs, but funds may land in a separate wallet (e.g. cold storage)
+        let payout = match destination {
+            Some(destination) => {
+                let account = ctx.accounts.destination_account.as_ref().ok_or(QuizError::InvalidDestination)?;
+                require!(account.key() == destination, QuizError::InvalidDestination);
+                account.to_account_info()
+            }
+            None => claimer.to_account_info(),
+        };
+        
+        // Paying the vault into itself would net to zero while marking the reward claimed
+        require!(vault.key() != payout.key(), QuizError::DuplicateAccounts);
+
+        // Token rewards go to a token account owned by the payout wallet
+        let reward_tokens = reward_token_accounts(
+            quiz_set.reward_mint,
+            ctx.accounts.reward_mint.as_ref(),
+            ctx.accounts.vault_token_account.as_ref(),
+            ctx.accounts.claimer_token_account.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+            vault.key(),
+            payout.key(),
+        )?;
+        let vault_balance = vault_payable_balance(vault, reward_tokens.as_ref())?;
+
+        let quiz_set_key = quiz_set.key();
+        let vault_seeds: &[&[u8]] = &[b"vault", quiz_set_key.as_ref(), &[ctx.bumps.vault]];
+
+        // Eligibility failures log the state behind them (ErrorContext) for support
+        let fail = |code: QuizError| claim_failure(quiz_set, claimer.key(), vault_balance, code);
+        if !quiz_set.is_funded {
+            return Err(fail(QuizError::QuizNotFunded));
+        }
+        // Claims close at the deadline, except for a winner who was held back by
+        // min_participants: once the deadline passes the minimum no longer applies
+        let past_deadline = quiz_set.deadline != 0 && Clock::get()?.unix_timestamp > quiz_set.deadline;
+        let below_min_participants = quiz_set.participant_count < quiz_set.min_participants;
+        if past_deadline && !below_min_participants {
+            return Err(fail(QuizError::QuizDeadlinePassed));
+        }
+        if below_min_participants && !past_deadline {
+            return Err(fail(QuizError::InsufficientParticipants));
+        }
+        if quiz_set.require_claim_proof
+            && !has_claim_proof(ctx.accounts.player_progress.as_deref(), ctx.accounts.proof_signer.as_ref())
+        {
+            return Err(fail(QuizError::ClaimProofMissing));
+        }
+        check_gate(quiz_set, &claimer.key(), ctx.accounts.gate_token_account.as_deref())?;
+
+        // Rewards were earned against the committed blocks; refuse if they changed since
+        let content_hash = compute_content_hash(&quiz_set.key(), quiz_set.question_count, ctx.remaining_accounts)?;
+        require!(content_hash == quiz_set.content_hash, QuizError::QuizContentChanged);
+
+        if let Some(deadline) = quiz_set.claim_deadline()? {
+            if Clock::get()?.unix_timestamp > deadline {
+                return Err(fail(QuizError::ClaimWindowExpired));
+            }
+        }
+
+        // Proportional quizzes pay each claimant once, by score, first come first
+        // served. The QuizHistory score is a snapshot taken at completion, so it
+        // is still capped by the answers currently on the player's progress.
+        if quiz_set.reward_mode == RewardMode::Proportional {
+            require!(ctx.accounts.claim_receipt.claimer == Pubkey::default(), QuizError::ClaimReceiptExists);
+            let score = match (ctx.accounts.quiz_history.as_ref(), ctx.accounts.player_progress.as_ref()) {
+                (Some(history), Some(progress)) => {
+                    require!(
+                        history.user == claimer.key() && history.quiz_set == quiz_set.key(),
+                        QuizError::InvalidQuizHistory
+                    );
+                    history.score.min(progress.correct_count)
+                }
+                _ => return Err(fail(QuizError::NothingToClaim)),
+            };
+
+            let remaining = quiz_set.reward_amount.saturating_sub(quiz_set.proportional_paid);
+            if remaining == 0 {
+                return Err(fail(QuizError::RewardPoolExhausted));
+            }
+            let reward_amount = proportional_share(quiz_set.reward_amount, score, quiz_set.question_count).min(remaining);
+            if reward_amount == 0 {
+                return Err(fail(QuizError::NothingToClaim));
+            }
+            if vault_balance < reward_amount {
+                return Err(fail(QuizError::InsufficientVaultBalance));
+            }
+
+            pay_from_vault(vault, vault_seeds, &payout, reward_tokens.as_ref(), reward_amount)?;
+            quiz_set.proportional_paid += reward_amount;
+            if quiz_set.proportional_paid >= quiz_set.reward_amount {
+                release_active_slot(quiz_set, &mut ctx.accounts.author_quota);
+            }
+
+            record_claim(
+                quiz_set.key(),
+                quiz_set.reward_mint,
+                claimer.key(),
+                reward_amount,
+                &mut ctx.accounts.reward_stats,
+                &mut ctx.accounts.global_stats,
+                &mut ctx.accounts.claim_receipt,
+            )?;
+
+            msg!("✅ Proportional reward claimed: {} for {}/{}", reward_amount, score, quiz_set.question_count);
+
+            emit!(RewardClaimed {
+                quiz_set: quiz_set.key(),
+                winner: claimer.key(),
+                reward_amount,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+
+            return Ok(());
+        }
+
+        // Per-question quizzes pay out whatever the claimer has earned so far
+        if quiz_set.per_question_reward > 0 {
+            let reward_amount = ctx.accounts.player_progress.as_ref().map_or(0, |progress| progress.claimable);
+            if reward_amount == 0 {
+                return Err(fail(QuizError::NothingToClaim));
+            }
+            if vault_balance < reward_amount {
+                return Err(fail(QuizError::InsufficientVaultBalance));
+            }
+
+            pay_from_vault(vault, vault_seeds, &payout, reward_tokens.as_ref(), reward_amount)?;
+            quiz_set.per_question_paid += reward_amount;
+            if quiz_set.per_question_paid >= quiz_set.reward_amount {
+                release_active_slot(quiz_set, &mut ctx.accounts.author_quota);
+            }
+            if let Some(player_progress) = ctx.accounts.player_progress.as_mut() {
+                player_progress.claimable = 0;
+            }
+
+            record_claim(
+                quiz_set.key(),
+                quiz_set.reward_mint,
+                claimer.key(),
+                reward_amount,
+                &mut ctx.accounts.reward_stats,
+                &mut ctx.accounts.global_stats,
+                &mut ctx.accounts.claim_receipt,
+            )?;
+
+            msg!("✅ Per-question rewards claimed: {}", reward_amount);
+
+            emit!(RewardClaimed {
+                quiz_set: quiz_set.key(),
+                winner: claimer.key(),
+                reward_amount,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+
+            return Ok(());
+        }
+
+        if quiz_set.winner.is_none() {
+            return Err(fail(QuizError::QuizNotCompleted));
+        }
+        if !quiz_set.is_winner(&claimer.key()) && quiz_set.runner_up != Some(claimer.key()) {
+            return Err(fail(QuizError::NotWinner));
+        }
+
+        // The receipt outlives any reset of the quiz's own claimed flags
+        let claim_receipt = &mut ctx.accounts.claim_receipt;
+        require!(claim_receipt.claimer == Pubkey::default(), QuizError::ClaimReceiptExists);
+        
+        msg!("🔍 Debug: claim_reward called");
+        msg!("🔍 Debug: quiz_set.is_initialized = {}", quiz_set.is_initialized);
+        msg!("🔍 Debug: quiz_set.winner = {:?}", quiz_set.winner);
+        msg!("🔍 Debug: quiz_set.is_reward_claimed = {}", quiz_set.is_reward_claimed);
+        msg!("🔍 Debug: claimer = {}", claimer.key());
+        
+        // Winner takes the pool minus the runner-up's carve-out, split evenly with any co-winners
+        let is_winner = quiz_set.winner == Some(claimer.key());
+        let co_winner_index = quiz_set.co_winners.iter().position(|key| *key == claimer.key());
+        let (already_claimed, reward_amount) = if is_winner {
+            (quiz_set.is_reward_claimed, quiz_set.winner_share(true))
+        } else if let Some(index) = co_winner_index {
+            (quiz_set.co_winners_claimed & (1 << index) != 0, quiz_set.winner_share(false))
+        } else {
+            (quiz_set.is_runner_up_claimed, quiz_set.runner_up_amount)
+        };
+        if already_claimed {
+            return Err(fail(QuizError::RewardAlreadyClaimed));
+        }
+
+        // Speed brackets scale winner shares by the claimer's own completion
+        // time; whatever they don't pay out stays in the vault
+        let reward_amount = if quiz_set.reward_brackets.is_empty() || !(is_winner || co_winner_index.is_some()) {
+            reward_amount
+        } else {
+            let completion_seconds = match ctx.accounts.player_progress.as_ref() {
+                Some(progress) if progress.completed_at != 0 => progress.completed_at - progress.started_at,
+                _ => return Err(fail(QuizError::CompletionTimeUnknown)),
+            };
+            let bps = quiz_set.bracket_bps(completion_seconds);
+            if bps == 0 {
+                return Err(fail(QuizError::NothingToClaim));
+            }
+            (reward_amount as u128 * bps as u128 / 10_000) as u64
+        };
+        if vault_balance < reward_amount {
+            return Err(fail(QuizError::InsufficientVaultBalance));
+        }
+        
+        pay_from_vault(vault, vault_seeds, &payout, reward_tokens.as_ref(), reward_amount)?;
+        
+        // Mark reward as claimed
+        if is_winner {
+            quiz_set.is_reward_claimed = true;
+            release_active_slot(quiz_set, &mut ctx.accounts.author_quota);
+        } else if let Some(index) = co_winner_index {
+            quiz_set.co_winners_claimed |= 1 << index;
+        } else {
+            quiz_set.is_runner_up_claimed = true;
+        }
+        
+        record_claim(
+            quiz_set.key(),
+            quiz_set.reward_mint,
+            claimer.key(),
+            reward_amount,
+            &mut ctx.accounts.reward_stats,
+            &mut ctx.accounts.global_stats,
+            claim_receipt,
+        )?;
+        
+        msg!("✅ Reward claimed successfully: {}", reward_amount);
+        msg!("✅ Claimer: {}", claimer.key());
+        msg!("💰 Reward ({:?}) transferred from vault to {}", quiz_set.reward_mint, payout.key());
+        
+        // Emit event
+        emit!(RewardClaimed {
+            quiz_set: quiz_set.key(),
+            winner: claimer.key(),
+            reward_amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    // ===== QUIZ SERIES FUNCTIONS =====
+
+    pub fn create_series(
+        ctx: Context<CreateSeries>,
+        series_id: u8,
+        reward_amount: u64, // SOL amount in lamports
+    ) -> Result<()> {
+        require!(reward_amount > 0, QuizError::InvalidRewardAmount);
+
+        let series = &mut ctx.accounts.series;
+        series.authority = ctx.accounts.authority.key();
+        series.series_id = series_id;
+        series.quiz_sets = Vec::new();
+        series.reward_amount = reward_amount;
+        series.winner = None;
+        series.is_reward_claimed = false;
+        series.created_at = Clock::get()?.unix_timestamp;
+
+        // Transfer SOL to the shared series vault
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: ctx.accounts.series_vault.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(transfer_ctx, reward_amount)?;
+
+        emit!(SeriesCreated {
+            series: series.key(),
+            authority: series.authority,
+            reward_amount,
+            timestamp: series.created_at,
+        });
+
+        msg!("Quiz series {} created with {} SOL pooled reward", series_id, reward_amount / 1_000_000_000);
+        Ok(())
+    }
+
+    pub fn add_quiz_to_series(ctx: Context<AddQuizToSeries>) -> Result<()> {
+        let series = &mut ctx.accounts.series;
+        let quiz_set_key = ctx.accounts.quiz_set.key();
+
+        require!(series.winner.is_none(), QuizError::SeriesAlreadySettled);
+        require!(series.quiz_sets.len() < MAX_SERIES_QUIZZES, QuizError::SeriesFull);
+        require!(!series.quiz_sets.contains(&quiz_set_key), QuizError::QuizAlreadyInSeries);
+
+        series.quiz_sets.push(quiz_set_key);
+
+        emit!(QuizAddedToSeries {
+            series: series.key(),
+            quiz_set: quiz_set_key,
+            quiz_count: series.quiz_sets.len() as u8,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Quiz set {} added to series {}", quiz_set_key, series.series_id);
+        Ok(())
+    }
+
+    // The series winner is the best aggregate scorer: PlayerProgress.score summed
+    // across the member quizzes. Ties go to the player whose last answer landed
+    // first. remaining_accounts holds the member quiz sets in order, followed by
+    // the contenders' PlayerProgress accounts; every member quiz's winner must
+    // be among the contenders.
+    pub fn claim_series_reward(ctx: Context<ClaimSeriesReward>) -> Result<()> {
+        let series = &mut ctx.accounts.series;
+        let claimer = ctx.accounts.claimer.key();
+        require!(ctx.accounts.series_vault.key() != claimer, QuizError::DuplicateAccounts);
+
+        let leader = series_leader(&series.quiz_sets, ctx.remaining_accounts)?;
+        require!(leader == claimer, QuizError::NotWinner);
+
+        let reward_amount = series.reward_amount;
+        let vault_info = ctx.accounts.series_vault.to_account_info();
+        require!(vault_info.lamports() >= reward_amount, QuizError::InsufficientVaultBalance);
+
+        **vault_info.try_borrow_mut_lamports()? -= reward_amount;
+        **ctx.accounts.claimer.to_account_info().try_borrow_mut_lamports()? += reward_amount;
+
+        series.winner = Some(claimer);
+        series.is_reward_claimed = true;
+
+        emit!(SeriesRewardClaimed {
+            series: series.key(),
+            winner: claimer,
+            reward_amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("✅ Series reward claimed by {}: {} SOL", claimer, reward_amount / 1_000_000_000);
+        Ok(())
+    }
+
+    // ===== READ FUNCTIONS =====
+
+    // Emit a question's metadata; correctness counts are left out unless the
+    // host publishes them (they remain readable on the QuestionBlock account)
+    pub fn get_question_metadata(ctx: Context<GetQuestionMetadata>) -> Result<()> {
+        let quiz_set = &ctx.accounts.quiz_set;
+        let question_block = &ctx.accounts.question_block;
+        let stats_public = quiz_set.stats_public;
+
+        emit!(QuestionMetadata {
+            quiz_set: quiz_set.key(),
+            question_block: question_block.key(),
+            question_index: question_block.question_index,
+            created_at: question_block.created_at,
+            stats_public,
+            attempt_count: if stats_public { Some(question_block.attempt_count) } else { None },
+            correct_count: if stats_public { Some(question_block.correct_count) } else { None },
+            correct_rate_bps: if stats_public {
+                Some(win_rate_bps(question_block.correct_count, question_block.attempt_count))
+            } else {
+                None
+            },
+        });
+
+        Ok(())
+    }
+
+    // Emit one compact summary per quiz set passed in remaining_accounts, so a
+    // topic page can render all of its quizzes from a single transaction
+    pub fn get_topic_quiz_summaries(ctx: Context<GetTopicQuizSummaries>) -> Result<()> {
+        require!(ctx.remaining_accounts.len() <= MAX_BATCH, QuizError::BatchTooLarge);
+        let topic = ctx.accounts.topic.key();
+        let now = Clock::get()?.unix_timestamp;
+
+        // One summary per quiz, then an aggregate whose hash lets clients check
+        // they saw the whole batch (MAX_BATCH keeps the logs within limits)
+        let mut records = Vec::with_capacity(ctx.remaining_accounts.len() * QUIZ_SUMMARY_RECORD_LEN);
+        for info in ctx.remaining_accounts.iter() {
+            require!(info.owner == &crate::ID, QuizError::QuizNotInTopic);
+            let quiz_set = QuizSet::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+            require!(quiz_set.topic == topic, QuizError::QuizNotInTopic);
+
+            emit!(QuizSummary {
+                topic,
+                quiz_set: info.key(),
+                name: quiz_set.name.clone(),
+                reward_amount: quiz_set.reward_amount,
+                question_count: quiz_set.question_count,
+                is_initialized: quiz_set.is_initialized,
+                has_winner: quiz_set.winner.is_some(),
+                is_claimable: quiz_set.is_claimable(now)?,
+            });
+            records.extend_from_slice(&quiz_summary_record(&info.key(), &quiz_set, now)?);
+        }
+
+        emit!(BatchCompleted {
+            topic,
+            count: ctx.remaining_accounts.len() as u8,
+            summary_hash: hashv(&[&records]).to_bytes(),
+            timestamp: now,
+        });
+
+        msg!("Summarized {} quizzes for topic {}", ctx.remaining_accounts.len(), topic);
+        Ok(())
+    }
+
+    // Spectator snapshot: rank the supplied PlayerProgress accounts without modifying them
+    pub fn get_live_standings(ctx: Context<GetLiveStandings>) -> Result<()> {
+        require!(ctx.remaining_accounts.len() <= MAX_BATCH, QuizError::BatchTooLarge);
+        let quiz_set = ctx.accounts.quiz_set.key();
+
+        let mut standings = Vec::with_capacity(ctx.remaining_accounts.len());
+        for info in ctx.remaining_accounts.iter() {
+            require!(info.owner == &crate::ID, QuizError::ProgressNotInQuiz);
+            let progress = PlayerProgress::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+            require!(progress.quiz_set == quiz_set, QuizError::ProgressNotInQuiz);
+
+            standings.push(StandingEntry {
+                player: progress.player,
+                correct_count: progress.correct_count,
+                points: progress.score,
+            });
+        }
+
+        // Highest points first, correct answers break ties
+        standings.sort_by(|a, b| {
+            b.points.cmp(&a.points).then(b.correct_count.cmp(&a.correct_count))
+        });
+
+        msg!("Live standings for {}: {} players", quiz_set, standings.len());
+        emit!(LiveStandings {
+            quiz_set,
+            standings,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    // Hand an unclaimed reward to another player after the winner is disqualified
+    // (e.g. cheating attested off-chain). The new winner must have completed every
+    // required question without being eliminated, and gets a fresh claim window.
+    // Only possible before any share (winner, co-winner or runner-up) is paid.
+    pub fn reassign_winner(ctx: Context<ReassignWinner>, new_winner: Pubkey) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        let progress = &ctx.accounts.new_winner_progress;
+        let previous_winner = quiz_set.winner.unwrap_or_default();
+        require!(new_winner != previous_winner, QuizError::InvalidWinner);
+        require!(
+            !progress.eliminated
+                && progress.answered_mask.count_ones() >= quiz_set.required_question_count() as u32,
+            QuizError::WinnerNotQualified
+        );
+
+        // The new winner can't also keep a co-winner or runner-up share
+        if let Some(index) = quiz_set.co_winners.iter().position(|p| *p == new_winner) {
+            quiz_set.co_winners.remove(index);
+        }
+        if quiz_set.runner_up == Some(new_winner) {
+            quiz_set.runner_up = None;
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        quiz_set.winner = Some(new_winner);
+        quiz_set.won_at = now;
+
+        emit!(WinnerReassigned {
+            quiz_set: quiz_set.key(),
+            previous_winner,
+            new_winner,
+            authority: ctx.accounts.authority.key(),
+            timestamp: now,
+        });
+
+        msg!("⚖️ Winner reassigned from {} to {}", previous_winner, new_winner);
+        Ok(())
+    }
+
+    // Return the winner's share to the authority once the claim window
+    // (including grace) has passed without a claim
+    pub fn reclaim_unclaimed_winner_reward(ctx: Context<ReclaimUnclaimedWinnerReward>) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        let now = Clock::get()?.unix_timestamp;
+        require!(ctx.accounts.vault.key() != ctx.accounts.authority.key(), QuizError::DuplicateAccounts);
+
+        let deadline = quiz_set.claim_deadline()?.ok_or(QuizError::ClaimWindowOpen)?;
+        require!(now > deadline, QuizError::ClaimWindowOpen);
+
+        // Every winner and co-winner share nobody came for
+        let mut amount = 0;
+        if !quiz_set.is_reward_claimed {
+            amount += quiz_set.winner_share(true);
+        }
+        for index in 0..quiz_set.co_winners.len() {
+            if quiz_set.co_winners_claimed & (1 << index) == 0 {
+                amount += quiz_set.winner_share(false);
+            }
+        }
+        require!(amount > 0, QuizError::RewardAlreadyClaimed);
+
+        let reward_tokens = reward_token_accounts(
+            quiz_set.reward_mint,
+            ctx.accounts.reward_mint.as_ref(),
+            ctx.accounts.vault_token_account.as_ref(),
+            ctx.accounts.authority_token_account.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+            ctx.accounts.vault.key(),
+            ctx.accounts.authority.key(),
+        )?;
+        let quiz_set_key = quiz_set.key();
+        let vault_seeds: &[&[u8]] = &[b"vault", quiz_set_key.as_ref(), &[ctx.bumps.vault]];
+        pay_from_vault(
+            &ctx.accounts.vault,
+            vault_seeds,
+            &ctx.accounts.authority.to_account_info(),
+            reward_tokens.as_ref(),
+            amount,
+        )?;
+
+        // The winners' shares are settled; nothing left for them to claim
+        quiz_set.is_reward_claimed = true;
+        quiz_set.co_winners_claimed = (1u8 << quiz_set.co_winners.len()) - 1;
+        release_active_slot(quiz_set, &mut ctx.accounts.author_quota);
+
+        record_escrow_change(&mut ctx.accounts.reward_stats, quiz_set.reward_mint.unwrap_or(NATIVE_SOL_MINT), 0, amount)?;
+
+        emit!(WinnerRewardReclaimed {
+            quiz_set: quiz_set.key(),
+            winner: quiz_set.winner.unwrap_or_default(),
+            authority: ctx.accounts.authority.key(),
+            amount,
+            timestamp: now,
+        });
+
+        msg!("Unclaimed winner reward of {} ({:?}) returned to authority", amount, quiz_set.reward_mint);
+        Ok(())
+    }
+
+    // Return the reward to the authority once the quiz deadline has passed
+    // without anyone winning. The vault itself stays open (rent-exempt).
+    pub fn refund_unclaimed_reward(ctx: Context<RefundUnclaimedReward>) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        let now = Clock::get()?.unix_timestamp;
+        require!(quiz_set.deadline != 0 && now > quiz_set.deadline, QuizError::DeadlineNotReached);
+
+        let reward_tokens = reward_token_accounts(
+            quiz_set.reward_mint,
+            ctx.accounts.reward_mint.as_ref(),
+            ctx.accounts.vault_token_account.as_ref(),
+            ctx.accounts.authority_token_account.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+            ctx.accounts.vault.key(),
+            ctx.accounts.authority.key(),
+        )?;
+        let amount = vault_payable_balance(&ctx.accounts.vault, reward_tokens.as_ref())?;
+        require!(amount > 0, QuizError::InsufficientVaultBalance);
+
+        let quiz_set_key = quiz_set.key();
+        let vault_seeds: &[&[u8]] = &[b"vault", quiz_set_key.as_ref(), &[ctx.bumps.vault]];
+        pay_from_vault(
+            &ctx.accounts.vault,
+            vault_seeds,
+            &ctx.accounts.authority.to_account_info(),
+            reward_tokens.as_ref(),
+            amount,
+        )?;
+
+        // Nothing left to answer for or claim
+        quiz_set.funded_amount = 0;
+        quiz_set.is_funded = false;
+        quiz_set.is_refunded = true;
+        release_active_slot(quiz_set, &mut ctx.accounts.author_quota);
+
+        record_escrow_change(&mut ctx.accounts.reward_stats, quiz_set.reward_mint.unwrap_or(NATIVE_SOL_MINT), 0, amount)?;
+
+        emit!(RewardRefunded {
+            quiz_set: quiz_set_key,
+            authority: ctx.accounts.authority.key(),
+            reward_mint: quiz_set.reward_mint,
+            amount,
+            timestamp: now,
+        });
+
+        msg!("↩️ Unwon reward of {} refunded to authority", amount);
+        Ok(())
+    }
+
+    // Emit one QuizSettlement record for indexers once every share is paid or
+    // refunded. Every question block is passed via remaining_accounts (checked
+    // against the content hash) for the per-question stats; quizzes with more
+    // blocks than fit in a transaction need an address lookup table.
+    pub fn finalize_settlement(ctx: Context<FinalizeSettlement>) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        require!(quiz_set.is_settled(), QuizError::QuizNotSettled);
+        require!(!quiz_set.settlement_finalized, QuizError::SettlementAlreadyFinalized);
+
+        let content_hash = compute_content_hash(&quiz_set.key(), quiz_set.question_count, ctx.remaining_accounts)?;
+        require!(content_hash == quiz_set.content_hash, QuizError::QuizContentChanged);
+
+        let mut question_stats = Vec::with_capacity(ctx.remaining_accounts.len());
+        for info in ctx.remaining_accounts.iter() {
+            let block = QuestionBlock::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+            question_stats.push(QuestionSettlementStats {
+                question_index: block.question_index,
+                attempt_count: block.attempt_count,
+                correct_count: block.correct_count,
+            });
+        }
+        question_stats.sort_by_key(|stats| stats.question_index);
+
+        quiz_set.settlement_finalized = true;
+
+        emit!(QuizSettlement {
+            quiz_set: quiz_set.key(),
+            topic: quiz_set.topic,
+            authority: quiz_set.authority,
+            participant_count: quiz_set.participant_count,
+            winner: quiz_set.winner,
+            co_winners: quiz_set.co_winners.clone(),
+            runner_up: quiz_set.runner_up,
+            reward_amount: quiz_set.reward_amount,
+            reward_mint: quiz_set.reward_mint,
+            winner_claimed: quiz_set.is_reward_claimed,
+            co_winners_claimed: quiz_set.co_winners_claimed,
+            runner_up_claimed: quiz_set.is_runner_up_claimed,
+            proportional_paid: quiz_set.proportional_paid,
+            refunded: quiz_set.is_refunded,
+            question_stats,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("🧾 Settlement finalized for quiz set '{}'", quiz_set.name);
+        Ok(())
+    }
+
+    // Reclaim the rent of a settled quiz: the quiz set, every question block
+    // (passed via remaining_accounts) and the vault PDA all go to the authority
+    pub fn close_quiz_set(ctx: Context<CloseQuizSet>) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        let authority = ctx.accounts.authority.to_account_info();
+        let vault = &ctx.accounts.vault;
+
+        // Every share must be paid (or refunded) before the vault disappears,
+        // and the settlement record published before the accounts go away
+        require!(quiz_set.is_settled(), QuizError::QuizNotSettled);
+        require!(quiz_set.settlement_finalized, QuizError::SettlementNotFinalized);
+
+        // The blocks' hashes must add up to the committed content hash, so no
+        // block is left behind holding rent
+        let mut content_hash = [0u8; 32];
+        let mut seen_indices: u64 = 0;
+        for info in ctx.remaining_accounts.iter() {
+            require!(info.owner == &crate::ID, QuizError::InvalidQuestionBlock);
+            let block = QuestionBlock::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+            require!(
+                block.quiz_set == quiz_set.key()
+                    && block.question_index > 0
+                    && block.question_index <= MAX_QUESTION_COUNT as u32,
+                QuizError::InvalidQuestionBlock
+            );
+
+            let bit = index_to_bit(block.question_index)?;
+            require!(seen_indices & bit == 0, QuizError::InvalidQuestionBlock);
+            seen_indices |= bit;
+            xor_into(&mut content_hash, &question_block_hash(&block));
+        }
+        require!(content_hash == quiz_set.content_hash, QuizError::QuestionBlocksMissing);
+
+        // Token rewards: sweep whatever the brackets left behind, then close the
+        // vault's token account so its rent comes back too
+        let reward_tokens = reward_token_accounts(
+            quiz_set.reward_mint,
+            ctx.accounts.reward_mint.as_ref(),
+            ctx.accounts.vault_token_account.as_ref(),
+            ctx.accounts.authority_token_account.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+            vault.key(),
+            authority.key(),
+        )?;
+        let quiz_set_key = quiz_set.key();
+        let vault_seeds: &[&[u8]] = &[b"vault", quiz_set_key.as_ref(), &[ctx.bumps.vault]];
+        if let Some(tokens) = reward_tokens.as_ref() {
+            let leftover = tokens.vault_token_account.amount;
+            if leftover > 0 {
+                pay_from_vault(vault, vault_seeds, &authority, Some(tokens), leftover)?;
+                record_escrow_change(&mut ctx.accounts.reward_stats, quiz_set.reward_mint.unwrap_or(NATIVE_SOL_MINT), 0, leftover)?;
+            }
+            close_vault_token_account(vault, vault_seeds, tokens, &authority)?;
+        }
+
+        release_active_slot(quiz_set, &mut ctx.accounts.author_quota);
+        ctx.accounts.author_quota.author = quiz_set.authority;
+        ctx.accounts.author_quota.retire(quiz_set.unique_id);
+
+        let mut reclaimed = vault.lamports();
+        close_program_account(&vault.to_account_info(), &authority)?;
+        for info in ctx.remaining_accounts.iter() {
+            reclaimed += info.lamports();
+            close_program_account(info, &authority)?;
+        }
+        reclaimed += quiz_set.to_account_info().lamports();
+
+        emit!(QuizSetClosed {
+            quiz_set: quiz_set.key(),
+            authority: authority.key(),
+            question_blocks: ctx.remaining_accounts.len() as u8,
+            reclaimed_lamports: reclaimed,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("🧹 Quiz set '{}' closed, {} lamports reclaimed", quiz_set.name, reclaimed);
+        Ok(())
+    }
+
+    // Permissionless cleanup of a long-abandoned quiz: once the keeper delay has
+    // passed after the claim deadline, the remaining reward goes back to the
+    // authority minus a keeper fee, and the vault and quiz set are closed
+    pub fn keeper_settle(ctx: Context<KeeperSettle>) -> Result<()> {
+        let quiz_set = &mut ctx.accounts.quiz_set;
+        let now = Clock::get()?.unix_timestamp;
+
+        // A won quiz is abandoned once its claim window lapses; one nobody won
+        // once its own deadline passes without the authority taking the refund
+        let deadline = match quiz_set.winner {
+            Some(_) => quiz_set.claim_deadline()?,
+            None => (quiz_set.deadline != 0).then_some(quiz_set.deadline),
+        }
+        .ok_or(QuizError::QuizNotAbandoned)?;
+        require!(quiz_set.keeper_delay_seconds > 0, QuizError::QuizNotAbandoned);
+        require!(now > deadline.saturating_add(quiz_set.keeper_delay_seconds), QuizError::QuizNotAbandoned);
+
+        let keeper_tokens = reward_token_accounts(
+            quiz_set.reward_mint,
+            ctx.accounts.reward_mint.as_ref(),
+            ctx.accounts.vault_token_account.as_ref(),
+            ctx.accounts.keeper_token_account.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+            ctx.accounts.vault.key(),
+            ctx.accounts.keeper.key(),
+        )?;
+        let authority_tokens = reward_token_accounts(
+            quiz_set.reward_mint,
+            ctx.accounts.reward_mint.as_ref(),
+            ctx.accounts.vault_token_account.as_ref(),
+            ctx.accounts.authority_token_account.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+            ctx.accounts.vault.key(),
+            ctx.accounts.authority.key(),
+        )?;
+
+        let quiz_set_key = quiz_set.key();
+        let vault_seeds: &[&[u8]] = &[b"vault", quiz_set_key.as_ref(), &[ctx.bumps.vault]];
+        let vault_info = ctx.accounts.vault.to_account_info();
+        let authority_info = ctx.accounts.authority.to_account_info();
+
+        let (remaining_reward, keeper_fee, refunded) = match (keeper_tokens, authority_tokens) {
+            // Token rewards: split the token balance, then close the vault's
+            // token account so nothing is stranded in it
+            (Some(keeper_tokens), Some(authority_tokens)) => {
+                let remaining_reward = keeper_tokens.vault_token_account.amount;
+                let keeper_fee = remaining_reward * KEEPER_FEE_BPS / 10_000;
+                let keeper_info = ctx.accounts.keeper.to_account_info();
+                pay_from_vault(&ctx.accounts.vault, vault_seeds, &keeper_info, Some(&keeper_tokens), keeper_fee)?;
+                pay_from_vault(
+                    &ctx.accounts.vault,
+                    vault_seeds,
+                    &authority_info,
+                    Some(&authority_tokens),
+                    remaining_reward - keeper_fee,
+                )?;
+
+                close_vault_token_account(&ctx.accounts.vault, vault_seeds, &keeper_tokens, &authority_info)?;
+
+                let total = vault_info.lamports();
+                **vault_info.try_borrow_mut_lamports()? -= total;
+                **authority_info.try_borrow_mut_lamports()? += total;
+                (remaining_reward, keeper_fee, remaining_reward - keeper_fee)
+            }
+            _ => {
+                let total = vault_info.lamports();
+                let remaining_reward = total.saturating_sub(Rent::get()?.minimum_balance(0));
+                let keeper_fee = remaining_reward * KEEPER_FEE_BPS / 10_000;
+
+                // Drain the vault entirely so the runtime reclaims it
+                **vault_info.try_borrow_mut_lamports()? -= total;
+                **ctx.accounts.keeper.to_account_info().try_borrow_mut_lamports()? += keeper_fee;
+                **authority_info.try_borrow_mut_lamports()? += total - keeper_fee;
+                (remaining_reward, keeper_fee, total - keeper_fee)
+            }
+        };
+
+        quiz_set.is_reward_claimed = true;
+        quiz_set.co_winners_claimed = (1u8 << quiz_set.co_winners.len()) - 1;
+        quiz_set.is_runner_up_claimed = true;
+        release_active_slot(quiz_set, &mut ctx.accounts.author_quota);
+        ctx.accounts.author_quota.author = quiz_set.authority;
+        ctx.accounts.author_quota.retire(quiz_set.unique_id);
+
+        record_escrow_change(
+            &mut ctx.accounts.reward_stats,
+            quiz_set.reward_mint.unwrap_or(NATIVE_SOL_MINT),
+            0,
+            remaining_reward,
+        )?;
+
+        emit!(QuizSettledByKeeper {
+            quiz_set: quiz_set.key(),
+            keeper: ctx.accounts.keeper.key(),
+            authority: ctx.accounts.authority.key(),
+            keeper_fee,
+            refunded,
+            timestamp: now,
+        });
+
+        msg!("🧹 Abandoned quiz '{}' settled by keeper {}", quiz_set.name, ctx.accounts.keeper.key());
+        Ok(())
+    }
+
+    // ===== OPERATOR FUNCTIONS =====
+
+    // Fund the Arcium fee pool that this program's computations draw on, so
+    // queues don't fail when it runs low. Each operator's contributions are
+    // tallied in their own ledger.
+    pub fn operator_topup_fees(ctx: Context<OperatorTopupFees>, amount: u64) -> Result<()> {
+        require!(amount > 0, QuizError::InvalidTopUpAmount);
+
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.operator.to_account_info(),
+                to: ctx.accounts.pool_account.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(transfer_ctx, amount)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let ledger = &mut ctx.accounts.fee_ledger;
+        ledger.operator = ctx.accounts.operator.key();
+        ledger.total_topped_up = ledger.total_topped_up.saturating_add(amount);
+        ledger.topup_count = ledger.topup_count.saturating_add(1);
+        ledger.last_topup_at = now;
+
+        emit!(FeesToppedUp {
+            operator: ledger.operator,
+            pool_account: ctx.accounts.pool_account.key(),
+            amount,
+            total_topped_up: ledger.total_topped_up,
+            pool_balance: ctx.accounts.pool_account.to_account_info().lamports(),
+            timestamp: now,
+        });
+
+        msg!("⛽ Fee pool topped up by {} lamports (operator total: {})", amount, ledger.total_topped_up);
+        Ok(())
+    }
+}
+
+// ===== HELPER FUNCTIONS =====
+
+// Computation definitions are created once per deployment; re-running an init
+// instruction is a no-op so deployment scripts can be replayed safely
+fn comp_def_already_initialized(comp_def_account: &AccountInfo, circuit: &str) -> bool {
+    if comp_def_account.data_is_empty() {
+        return false;
+    }
+    msg!("Computation definition '{}' already initialized, skipping", circuit);
+    true
+}
+
+// Multiple-choice options must all be present and distinct, ignoring case
+fn validate_options(options: &[String]) -> Result<()> {
+    for (i, option) in options.iter().enumerate() {
+        require!(!option.trim().is_empty(), QuizError::EmptyOption);
+        let normalized = option.trim().to_lowercase();
+        require!(
+            options[..i].iter().all(|other| other.trim().to_lowercase() != normalized),
+            QuizError::DuplicateOption
+        );
+    }
+    Ok(())
+}
+
+// Serialize a question and its options as u8-length-prefixed fields, split
+// into the 64-byte chunks the encrypt_quiz circuit takes. Content that doesn't
+// fit is rejected rather than truncated, and so is a blank question, which
+// would otherwise pack to all zeros.
+pub fn pack_question_chunks(question_text: &str, options: &[String]) -> Result<Vec<[u8; QUESTION_CHUNK_SIZE]>> {
+    require!(!question_text.trim().is_empty(), QuizError::EmptyQuestion);
+    require!(options.iter().any(|option| !option.trim().is_empty()), QuizError::EmptyOption);
+
+    let mut packed = Vec::new();
+    for field in std::iter::once(question_text).chain(options.iter().map(String::as_str)) {
+        let bytes = field.as_bytes();
+        require!(bytes.len() <= u8::MAX as usize, QuizError::QuestionContentTooLong);
+        packed.push(bytes.len() as u8);
+        packed.extend_from_slice(bytes);
+    }
+    require!(
+        packed.len() <= QUESTION_CHUNK_SIZE * MAX_QUESTION_CHUNKS,
+        QuizError::QuestionContentTooLong
+    );
+
+    Ok(packed
+        .chunks(QUESTION_CHUNK_SIZE)
+        .map(|piece| {
+            let mut chunk = [0u8; QUESTION_CHUNK_SIZE];
+            chunk[..piece.len()].copy_from_slice(piece);
+            chunk
+        })
+        .collect())
+}
+
+// Inverse of pack_question_chunks: the question text followed by its options
+pub fn unpack_question_chunks(chunks: &[[u8; QUESTION_CHUNK_SIZE]], option_count: usize) -> Result<(String, Vec<String>)> {
+    let packed = chunks.concat();
+    let mut cursor = 0;
+    let mut fields = Vec::with_capacity(option_count + 1);
+    for _ in 0..=option_count {
+        let len = *packed.get(cursor).ok_or(QuizError::InvalidQuestionChunk)? as usize;
+        let bytes = packed
+            .get(cursor + 1..cursor + 1 + len)
+            .ok_or(QuizError::InvalidQuestionChunk)?;
+        let field = String::from_utf8(bytes.to_vec()).map_err(|_| QuizError::InvalidQuestionChunk)?;
+        fields.push(field);
+        cursor += 1 + len;
+    }
+
+    let question_text = fields.remove(0);
+    Ok((question_text, fields))
+}
+
+// On-chain mirror of the circuits' additive nonce scheme: byte i is offset by
+// nonce byte i % 16, mod 256, so nonce_decrypt inverts nonce_encrypt for every byte
+fn nonce_encrypt(data: &mut [u8], nonce: u128) {
+    let nonce_bytes = nonce.to_le_bytes();
+    for (i, byte) in data.iter_mut().enumerate() {
+        *byte = byte.wrapping_add(nonce_bytes[i % 16]);
+    }
+}
+
+fn nonce_decrypt(data: &mut [u8], nonce: u128) {
+    let nonce_bytes = nonce.to_le_bytes();
+    for (i, byte) in data.iter_mut().enumerate() {
+        *byte = byte.wrapping_sub(nonce_bytes[i % 16]);
+    }
+}
+
+// Record hashed into a summary batch's summary_hash for one quiz set:
+// quiz_set || reward_amount (u64 LE) || question_count || is_initialized || has_winner || is_claimable
+fn quiz_summary_record(key: &Pubkey, quiz_set: &QuizSet, now: i64) -> Result<[u8; QUIZ_SUMMARY_RECORD_LEN]> {
+    let mut record = [0u8; QUIZ_SUMMARY_RECORD_LEN];
+    record[..32].copy_from_slice(key.as_ref());
+    record[32..40].copy_from_slice(&quiz_set.reward_amount.to_le_bytes());
+    record[40] = quiz_set.question_count;
+    record[41] = quiz_set.is_initialized as u8;
+    record[42] = quiz_set.winner.is_some() as u8;
+    record[43] = quiz_set.is_claimable(now)? as u8;
+    Ok(record)
+}
+
+// Undo the nonce offset on a block's stored correct answer (same scheme as the
+// validation circuits). The offset is not secret: anyone reading the block's
+// encrypted_y_coordinate and nonce can recover the same plaintext, so the
+// stored answer is only obfuscated, and comparing it here exposes nothing new.
+fn decrypt_correct_answer(block: &QuestionBlock) -> [u8; 64] {
+    let mut decrypted = block.encrypted_y_coordinate;
+    nonce_decrypt(&mut decrypted, block.nonce);
+    decrypted
+}
+
+// Emit the completion events for the current winner and mark them as sent
+fn announce_quiz_winner(quiz_set: &mut Account<QuizSet>, now: i64) {
+    let winner = quiz_set.winner.unwrap_or_default();
+    quiz_set.is_winner_announced = true;
+
+    emit!(QuizCompleted {
+        quiz_set: quiz_set.key(),
+        winner,
+        reward_amount: quiz_set.reward_amount,
+        timestamp: now,
+    });
+
+    emit!(RewardClaimable {
+        quiz_set: quiz_set.key(),
+        winner,
+        reward_amount: quiz_set.reward_amount - quiz_set.runner_up_amount,
+        claimable_from: now,
+    });
+}
+
+// Hash of a single question block's committed content
+fn question_block_hash(block: &QuestionBlock) -> [u8; 32] {
+    hashv(&[
+        block.quiz_set.as_ref(),
+        &block.question_index.to_le_bytes(),
+        &block.encrypted_x_coordinate,
+        &block.encrypted_y_coordinate,
+        &block.arcium_pubkey,
+        &block.nonce.to_le_bytes(),
+        &[block.alt_answer_count],
+        &block.alt_answers.concat(),
+    ])
+    .to_bytes()
+}
+
+// Digest binding every attested field, stored on the attestation PDA
+fn attestation_digest(attestation: &Attestation) -> [u8; 32] {
+    hashv(&[
+        b"k3hoot:attest".as_ref(),
+        attestation.user.as_ref(),
+        attestation.quiz_set.as_ref(),
+        &attestation.score.to_le_bytes(),
+        &[attestation.correct_count, attestation.question_count, attestation.is_winner as u8],
+        &attestation.attested_at.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+fn xor_into(acc: &mut [u8; 32], value: &[u8; 32]) {
+    for (a, v) in acc.iter_mut().zip(value.iter()) {
+        *a ^= v;
+    }
+}
+
+// Recompute the content commitment from the full set of question blocks.
+// Blocks are XOR-folded so the result doesn't depend on the order they were added.
+fn compute_content_hash(
+    quiz_set_key: &Pubkey,
+    question_count: u8,
+    blocks: &[AccountInfo],
+) -> Result<[u8; 32]> {
+    require!(blocks.len() == question_count as usize, QuizError::QuizContentChanged);
+
+    let mut content_hash = [0u8; 32];
+    let mut seen_indices: u64 = 0; // fits because MAX_QUESTION_COUNT <= 64
+
+    for info in blocks {
+        require!(info.owner == &crate::ID, QuizError::QuizContentChanged);
+        let block = QuestionBlock::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+
+        require!(block.quiz_set == *quiz_set_key, QuizError::QuizContentChanged);
+        require!(
+            block.question_index > 0 && block.question_index <= question_count as u32,
+            QuizError::QuizContentChanged
+        );
+
+        let bit = index_to_bit(block.question_index)?;
+        require!(seen_indices & bit == 0, QuizError::QuizContentChanged);
+        seen_indices |= bit;
+
+        xor_into(&mut content_hash, &question_block_hash(&block));
+    }
+
+    Ok(content_hash)
+}
+
+// Message the ephemeral key signs to consent to being merged into `user`. The
+// nonce is the main score's link_nonce, so each signature is good for one link.
+fn link_message(user: &Pubkey, topic: &Pubkey, nonce: u32) -> Vec<u8> {
+    [b"k3hoot:link".as_ref(), user.as_ref(), topic.as_ref(), &nonce.to_le_bytes()].concat()
+}
+
+// Check that the instruction before this one is an Ed25519 signature
+// verification by `signer` over exactly `message`.
+fn verify_link_proof(instructions_sysvar: &AccountInfo, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    // Ed25519 instruction layout: [num_signatures, padding, offsets (14 bytes), data...]
+    const OFFSETS_START: usize = 2;
+    const SIGNATURE_LEN: usize = 64;
+    const PUBKEY_LEN: usize = 32;
+
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, QuizError::InvalidLinkProof);
+    let ix = load_instruction_at_checked((current_index - 1) as usize, instructions_sysvar)?;
+
+    require!(ix.program_id == ed25519_program::ID, QuizError::InvalidLinkProof);
+    require!(ix.data.len() > OFFSETS_START + 14 && ix.data[0] == 1, QuizError::InvalidLinkProof);
+
+    let read_u16 = |at: usize| u16::from_le_bytes([ix.data[at], ix.data[at + 1]]) as usize;
+    let signature_offset = read_u16(OFFSETS_START);
+    let signature_ix_index = read_u16(OFFSETS_START + 2);
+    let pubkey_offset = read_u16(OFFSETS_START + 4);
+    let pubkey_ix_index = read_u16(OFFSETS_START + 6);
+    let message_offset = read_u16(OFFSETS_START + 8);
+    let message_size = read_u16(OFFSETS_START + 10);
+    let message_ix_index = read_u16(OFFSETS_START + 12);
+
+    // All data must live inside the Ed25519 instruction itself
+    let this_ix = u16::MAX as usize;
+    require!(
+        signature_ix_index == this_ix && pubkey_ix_index == this_ix && message_ix_index == this_ix,
+        QuizError::InvalidLinkProof
+    );
+    require!(
+        ix.data.len() >= signature_offset + SIGNATURE_LEN
+            && ix.data.len() >= pubkey_offset + PUBKEY_LEN
+            && ix.data.len() >= message_offset + message_size,
+        QuizError::InvalidLinkProof
+    );
+
+    require!(&ix.data[pubkey_offset..pubkey_offset + PUBKEY_LEN] == signer.as_ref(), QuizError::InvalidLinkProof);
+    require!(&ix.data[message_offset..message_offset + message_size] == message, QuizError::InvalidLinkProof);
+
+    Ok(())
+}
+
+// Shared bookkeeping for every answer-validation callback
+// Sudden death eliminates the player; end-on-wrong ends their run with the score standing
+fn knock_out_player(
+    quiz_set: &Account<QuizSet>,
+    player_progress: &mut Account<PlayerProgress>,
+    question_index: u32,
+    now: i64,
+) {
+    let player = player_progress.player;
+
+    if quiz_set.sudden_death {
+        player_progress.eliminated = true;
+
+        emit!(PlayerEliminatedEvent {
+            quiz_set: quiz_set.key(),
+            player,
+            question_index,
+            timestamp: now,
+        });
+
+        msg!("💀 Player {} eliminated on question {}", player, question_index);
+    }
+
+    if quiz_set.end_on_wrong {
+        player_progress.finished = true;
+
+        emit!(PlayerRunEnded {
+            quiz_set: quiz_set.key(),
+            player,
+            question_index,
+            score: player_progress.score,
+            timestamp: now,
+        });
+
+        msg!("🏁 Player {} run ended on question {}", player, question_index);
+    }
+}
+
+fn apply_answer_result(
+    quiz_set: &mut Account<QuizSet>,
+    question_block: &mut Account<QuestionBlock>,
+    player_progress: &mut Account<PlayerProgress>,
+    computation_offset: u64,
+    is_correct: bool,
+) -> Result<()> {
+    let question_index = question_block.question_index;
+    let player = player_progress.player;
+    let now = Clock::get()?.unix_timestamp;
+
+    // The quiz was live when this was queued, but may not be any more
+    if is_stale_result(quiz_set, now, Clock::get()?.slot) {
+        emit!(StaleComputation {
+            quiz_set: quiz_set.key(),
+            player,
+            question_index,
+            timestamp: now,
+        });
+        emit!(AnswerVerifiedEvent {
+            question_index,
+            is_correct,
+            is_stale: true,
+            computation_offset,
+            timestamp: now,
+        });
+
+        msg!("⌛ Ignoring stale result for question {} from player {}", question_index, player);
+        return Ok(());
+    }
+    player_progress.last_activity = now;
+
+    // Results still in flight when a player was eliminated or their run ended no longer count
+    if player_progress.eliminated || player_progress.finished {
+        emit!(AnswerVerifiedEvent {
+            question_index,
+            is_correct,
+            is_stale: true,
+            computation_offset,
+            timestamp: now,
+        });
+
+        msg!("Ignoring result for player {} who is out of the quiz", player);
+        return Ok(());
+    }
+
+    // A miss held open for a retry knocks the player out once anything other
+    // than that retry comes in, or the window has lapsed; this result is dropped
+    if player_progress.pending_knockout
+        && (player_progress.last_wrong_question as u32 != question_index
+            || now - player_progress.last_wrong_at > quiz_set.retry_window_seconds)
+    {
+        player_progress.pending_knockout = false;
+        let missed_question = player_progress.last_wrong_question.into();
+        knock_out_player(quiz_set, player_progress, missed_question, now);
+        emit!(AnswerVerifiedEvent {
+            question_index,
+            is_correct,
+            is_stale: true,
+            computation_offset,
+            timestamp: now,
+        });
+        return Ok(());
+    }
+
+    // A question already answered correctly is never scored twice (e.g. a
+    // resubmission queued before the first result landed)
+    let question_bit = index_to_bit(question_index)?;
+    if is_correct && player_progress.scored_mask & question_bit != 0 {
+        emit!(AnswerVerifiedEvent {
+            question_index,
+            is_correct,
+            is_stale: false,
+            computation_offset,
+            timestamp: now,
+        });
+
+        msg!("Question {} already scored for player {}, ignoring", question_index, player);
+        return Ok(());
+    }
+
+    let was_completed = has_answered_every_question(quiz_set, player_progress);
+    if is_correct {
+        // A correct retry clears the miss held open for it
+        player_progress.pending_knockout = false;
+        player_progress.scored_mask |= question_bit;
+        player_progress.correct_count = player_progress.correct_count.saturating_add(1);
+        let category_correct = &mut player_progress.category_correct[question_block.category as usize];
+        *category_correct = category_correct.saturating_add(1);
+
+        // Credits stop once the whole reward has been handed out
+        let credit = quiz_set.per_question_reward.min(quiz_set.reward_amount.saturating_sub(quiz_set.per_question_credited));
+        quiz_set.per_question_credited += credit;
+        player_progress.claimable = player_progress.claimable.saturating_add(credit);
+
+        // Outstanding hint penalties are paid off before points reach the score
+        let points = if quiz_set.total_weight > 0 { question_block.weight as u32 } else { 1 };
+        let repaid = points.min(player_progress.hint_debt);
+        player_progress.hint_debt -= repaid;
+        player_progress.score = player_progress.score.saturating_add(points - repaid);
+    } else {
+        player_progress.wrong_mask |= question_bit;
+
+        // A quick retry of the question just missed is penalty-free (fat-finger).
+        // Only a penalized miss opens the window, so free retries can't extend it.
+        let within_retry_window = quiz_set.retry_window_seconds > 0
+            && player_progress.last_wrong_question as u32 == question_index
+            && now - player_progress.last_wrong_at <= quiz_set.retry_window_seconds;
+
+        if within_retry_window {
+            msg!("🔁 Retry of question {} within the window, no penalty", question_index);
+
+            // The retry failed too, so the miss held open for it stands
+            if player_progress.pending_knockout {
+                player_progress.pending_knockout = false;
+                knock_out_player(quiz_set, player_progress, question_index, now);
+            }
+        } else {
+            player_progress.wrong_count = player_progress.wrong_count.saturating_add(1);
+            player_progress.last_wrong_at = now;
+            player_progress.last_wrong_question = question_index as u8;
+
+            // Sudden death and end-on-wrong hold off while a retry is still possible
+            if quiz_set.sudden_death || quiz_set.end_on_wrong {
+                if quiz_set.retry_window_seconds > 0 {
+                    player_progress.pending_knockout = true;
+                } else {
+                    knock_out_player(quiz_set, player_progress, question_index, now);
+                }
+            }
+        }
+
+        // Unrewarded practice quizzes let the player fetch the answer right away,
+        // encrypted to their own key through reveal_my_mistakes
+        if quiz_set.reveal_on_wrong {
+            emit!(CorrectAnswerRevealed {
+                quiz_set: quiz_set.key(),
+                player,
+                question_index,
+                timestamp: now,
+            });
+        }
+    }
+
+    // Per-question statistics, only published when the host opted in
+    question_block.attempt_count = question_block.attempt_count.saturating_add(1);
+    if is_correct {
+        question_block.correct_count = question_block.correct_count.saturating_add(1);
+    }
+    if quiz_set.stats_public {
+        emit!(QuestionStatsUpdated {
+            quiz_set: quiz_set.key(),
+            question_index,
+            attempt_count: question_block.attempt_count,
+            correct_count: question_block.correct_count,
+            correct_rate_bps: win_rate_bps(question_block.correct_count, question_block.attempt_count),
+        });
+    }
+
+    // Mark this question as answered correctly. Bonus questions only add points.
+    if is_correct && !question_block.is_bonus {
+        player_progress.answered_mask |= question_bit;
+        if quiz_set.correct_answers_count < quiz_set.question_count {
+            quiz_set.correct_answers_count += 1;
+        }
+        
+        // If every required question is answered correctly, set winner (or co-winner / runner-up).
+        // A quiz that never finished initializing (e.g. weights short of the target) has no winner,
+        // so no claim can follow from it.
+        if quiz_set.is_initialized
+            && player_progress.answered_mask.count_ones() >= quiz_set.required_question_count() as u32
+        {
+            if player_progress.completed_at == 0 {
+                player_progress.completed_at = now;
+            }
+            record_finisher(quiz_set, player, now, Clock::get()?.slot);
+        }
+    }
+
+    // Once every question has a result, show the player how each category went
+    if !was_completed && has_answered_every_question(quiz_set, player_progress) {
+        emit!(CategoryScores {
+            quiz_set: quiz_set.key(),
+            player,
+            correct: player_progress.category_correct,
+            totals: quiz_set.category_totals,
+            timestamp: now,
+        });
+    }
+
+    // Emit event with actual result
+    emit!(AnswerVerifiedEvent {
+        question_index,
+        is_correct,
+        is_stale: false,
+        computation_offset,
+        timestamp: now,
+    });
+
+    msg!("Answer validation completed onchain. Result: {}", is_correct);
+    Ok(())
+}
+
+// Work out the series leader from the contenders' summed PlayerProgress scores
+fn series_leader(member_quiz_sets: &[Pubkey], remaining: &[AccountInfo]) -> Result<Pubkey> {
+    require!(!member_quiz_sets.is_empty(), QuizError::SeriesNotFinished);
+    require!(remaining.len() > member_quiz_sets.len(), QuizError::InvalidSeriesMembers);
+    let (quiz_set_infos, progress_infos) = remaining.split_at(member_quiz_sets.len());
+
+    let mut winners: Vec<Pubkey> = Vec::with_capacity(member_quiz_sets.len());
+    for (info, expected) in quiz_set_infos.iter().zip(member_quiz_sets.iter()) {
+        require!(info.key == expected && info.owner == &crate::ID, QuizError::InvalidSeriesMembers);
+        let quiz_set = QuizSet::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        winners.push(quiz_set.winner.ok_or(QuizError::SeriesNotFinished)?);
+    }
+
+    // (player, summed score, time of the latest answer)
+    let mut tallies: Vec<(Pubkey, u64, i64)> = Vec::new();
+    let mut seen: Vec<Pubkey> = Vec::with_capacity(progress_infos.len());
+
+    for info in progress_infos {
+        require!(info.owner == &crate::ID && !seen.contains(info.key), QuizError::InvalidSeriesMembers);
+        seen.push(*info.key);
+
+        let progress = PlayerProgress::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require!(member_quiz_sets.contains(&progress.quiz_set), QuizError::InvalidSeriesMembers);
+        let (expected, _) = Pubkey::find_program_address(
+            &[b"progress", progress.player.as_ref(), progress.quiz_set.as_ref()],
+            &crate::ID,
+        );
+        require!(info.key == &expected, QuizError::InvalidSeriesMembers);
+
+        match tallies.iter_mut().find(|(player, _, _)| *player == progress.player) {
+            Some(entry) => {
+                entry.1 += progress.score as u64;
+                entry.2 = std::cmp::max(entry.2, progress.last_activity);
+            }
+            None => tallies.push((progress.player, progress.score as u64, progress.last_activity)),
+        }
+    }
+
+    // A member quiz's winner can't be left out of the comparison
+    for winner in &winners {
+        require!(tallies.iter().any(|(player, _, _)| player == winner), QuizError::InvalidSeriesMembers);
+    }
+
+    let (leader, _, _) = tallies
+        .iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.2.cmp(&a.2)))
+        .copied()
+        .ok_or(QuizError::SeriesNotFinished)?;
+
+    Ok(leader)
+}
+
+// Enforce the topic's reward currency restriction (`None` mint = native SOL)
+fn check_reward_mint(topic: &Topic, reward_mint: Option<Pubkey>) -> Result<()> {
+    if let Some(allowed) = topic.allowed_reward_mint {
+        require!(reward_mint == Some(allowed), QuizError::RewardMintNotAllowed);
+    }
+    Ok(())
+}
+
+// Upsert the user's entry and keep the leaderboard sorted: most wins first,
+// then fewer attempts (better win rate). The lowest entry drops off when full.
+// Users whose score (quizzes won) is below the topic's minimum are kept off.
+fn update_leaderboard(leaderboard: &mut TopicLeaderboard, user_score: &UserScore, min_score: u32) {
+    if user_score.score < min_score {
+        leaderboard.entries.retain(|e| e.user != user_score.user);
+        return;
+    }
+
+    let entry = LeaderboardEntry {
+        user: user_score.user,
+        score: user_score.score,
+        total_completed: user_score.total_completed,
+        total_rewards: user_score.total_rewards,
+    };
+
+    match leaderboard.entries.iter_mut().find(|e| e.user == entry.user) {
+        Some(existing) => *existing = entry,
+        None => leaderboard.entries.push(entry),
+    }
+
+    leaderboard
+        .entries
+        .sort_by(|a, b| b.score.cmp(&a.score).then(a.total_completed.cmp(&b.total_completed)));
+    leaderboard.entries.truncate(MAX_LEADERBOARD_ENTRIES);
+}
+
+// Place a player who just completed the quiz. The first finisher wins; a
+// different player finishing in the same slot can't be told apart by the
+// tiebreak and becomes a co-winner; anyone later may take the runner-up spot.
+fn record_finisher(quiz_set: &mut Account<QuizSet>, player: Pubkey, now: i64, slot: u64) {
+    match quiz_set.winner {
+        None => {
+            quiz_set.winner = Some(player);
+            quiz_set.is_reward_claimed = false;
+            quiz_set.won_at = now;
+            quiz_set.won_slot = slot;
+
+            if now >= quiz_set.announce_after {
+                announce_quiz_winner(quiz_set, now);
+            } else {
+                msg!("⏳ Winner announcement delayed until {}", quiz_set.announce_after);
+            }
+
+            msg!("🎉 Quiz completed! Winner: {}", player);
+        }
+        Some(winner) if winner == player || quiz_set.co_winners.contains(&player) => {}
+        // Multi-winner quizzes take the next finishers, whatever their slot, and
+        // single-winner quizzes an exact tie, while the co-winner list is open
+        Some(_) if quiz_set.has_open_winner_slot(slot) => {
+            quiz_set.co_winners.push(player);
+
+            emit!(CoWinnerRecorded {
+                quiz_set: quiz_set.key(),
+                co_winner: player,
+                co_winner_count: quiz_set.co_winners.len() as u8,
+                slot,
+                timestamp: now,
+            });
+
+            if quiz_set.max_winners > 1 {
+                msg!("🏅 Winner {}/{}: {}", quiz_set.co_winners.len() + 1, quiz_set.max_winners, player);
+            } else {
+                msg!("🤝 Exact tie in slot {}: co-winner {}", slot, player);
+            }
+        }
+        Some(_) => {
+            if quiz_set.runner_up_amount > 0 && quiz_set.runner_up.is_none() {
+                quiz_set.runner_up = Some(player);
+
+                emit!(RunnerUpRecorded {
+                    quiz_set: quiz_set.key(),
+                    runner_up: player,
+                    reward_amount: quiz_set.runner_up_amount,
+                    timestamp: now,
+                });
+
+                msg!("🥈 Runner-up: {}", player);
+            }
+        }
+    }
+}
+
+// Arguments of the 64-byte validate_answer circuit: nonce, the player's answer,
+// the encrypted correct answer, then the alternates (masked by their count)
+fn validate_answer_args(question_block: &QuestionBlock, answer_bytes: &[u8; 64]) -> Vec<Argument> {
+    let mut args = vec![Argument::PlaintextU128(question_block.nonce)];
+
+    for byte in answer_bytes.iter() {
+        args.push(Argument::PlaintextU8(*byte));
+    }
+
+    for byte in question_block.encrypted_y_coordinate.iter() {
+        args.push(Argument::PlaintextU8(*byte));
+    }
+
+    for alt_answer in question_block.alt_answers.iter() {
+        for byte in alt_answer.iter() {
+            args.push(Argument::PlaintextU8(*byte));
+        }
+    }
+    args.push(Argument::PlaintextU8(question_block.alt_answer_count));
+
+    args
+}
+
+// Checks an answer must pass to count, judged as of `at` (the submission time,
+// or the time an offline answer was queued)
+fn check_answer_allowed(
+    quiz_set: &QuizSet,
+    player_progress: &PlayerProgress,
+    question_block: &QuestionBlock,
+    at: i64,
+) -> Result<()> {
+    require!(quiz_set.password_key.is_none() || player_progress.joined, QuizError::NotJoined);
+    require!(!player_progress.eliminated, QuizError::PlayerEliminated);
+    require!(!player_progress.finished, QuizError::RunEnded);
+    require!(!player_progress.answers_revealed, QuizError::AnswersRevealed);
+    if let Some(deadline) = quiz_set.run_deadline(player_progress.started_at)? {
+        require!(at <= deadline, QuizError::TimeExpired);
+    }
+    require!(quiz_set.deadline == 0 || at <= quiz_set.deadline, QuizError::QuizDeadlinePassed);
+    require!(quiz_set.is_funded, QuizError::QuizNotFunded);
+    require!(
+        question_block.valid_until == 0 || at <= question_block.valid_until,
+        QuizError::QuestionExpired
+    );
+    Ok(())
+}
+
+// Bit for question `index` (1-based) in the u64 question masks. Checked so an
+// index of 0 or past 64 is an error instead of an overflowing shift.
+fn index_to_bit(index: u32) -> Result<u64> {
+    require!(index > 0 && index <= 64, QuizError::InvalidQuestionIndex);
+    Ok(1u64 << (index - 1))
+}
+
+// Whether the player has a result, right or wrong, for every question
+fn has_answered_every_question(quiz_set: &QuizSet, player_progress: &PlayerProgress) -> bool {
+    let all_questions = index_to_bit(quiz_set.question_count.into()).map_or(0, |bit| (bit << 1).wrapping_sub(1));
+    (player_progress.scored_mask | player_progress.wrong_mask) & all_questions == all_questions
+}
+
+// Whether a validation result landing now can no longer count: the quiz was
+// reset or refunded after the computation was queued, or a single-winner quiz
+// was won more than result_grace_seconds ago with nothing left to score (no
+// per-question rewards, no runner-up prize, and no exact tie possible)
+fn is_stale_result(quiz_set: &QuizSet, now: i64, slot: u64) -> bool {
+    if !quiz_set.is_initialized || quiz_set.is_refunded {
+        return true;
+    }
+
+    quiz_set.winner.is_some()
+        && quiz_set.max_winners <= 1
+        && quiz_set.per_question_reward == 0
+        && quiz_set.runner_up_amount == 0
+        && slot != quiz_set.won_slot
+        && now > quiz_set.won_at.saturating_add(quiz_set.result_grace_seconds)
+}
+
+// Count a submission against the player's attempt limit and cooldown, so a
+// short answer can't be brute-forced by spamming computations
+fn record_attempt(quiz_set: &QuizSet, player_progress: &mut PlayerProgress, question_index: u8, now: i64) -> Result<()> {
+    require!(
+        quiz_set.attempt_cooldown_seconds == 0
+            || player_progress.last_attempt == 0
+            || now - player_progress.last_attempt >= quiz_set.attempt_cooldown_seconds,
+        QuizError::AttemptCooldown
+    );
+
+    let attempts = &mut player_progress.attempts[(question_index - 1) as usize];
+    require!(quiz_set.max_attempts == 0 || *attempts < quiz_set.max_attempts, QuizError::TooManyAttempts);
+    *attempts += 1;
+    player_progress.last_attempt = now;
+    Ok(())
+}
+
+// Load the question block being answered, turning a block the author never
+// created into QuestionBlockMissing instead of an opaque account error
+fn load_question_block(info: &AccountInfo, quiz_set: &Pubkey, question_index: u8) -> Result<QuestionBlock> {
+    require!(
+        info.owner == &crate::ID && !info.data_is_empty(),
+        QuizError::QuestionBlockMissing
+    );
+
+    let question_block = QuestionBlock::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    require!(
+        question_block.quiz_set == *quiz_set && question_block.question_index == question_index as u32,
+        QuizError::InvalidQuestionBlock
+    );
+
+    Ok(question_block)
+}
+
+// Cross-field invariants of a quiz set's configuration. Checked at creation and
+// after every configuration change, so no sequence of setters can leave the
+// quiz in a combination that would misbehave at play or claim time.
+fn validate_quiz_config(quiz_set: &QuizSet) -> Result<()> {
+    require!(
+        quiz_set.question_count > 0 && quiz_set.question_count <= MAX_QUESTION_COUNT,
+        QuizError::InvalidQuestionCount
+    );
+    // Only practice quizzes may go without a reward
+    require!(quiz_set.reward_amount > 0 || quiz_set.is_practice, QuizError::InvalidRewardAmount);
+    require!(
+        quiz_set.runner_up_amount == 0 || quiz_set.runner_up_amount < quiz_set.reward_amount,
+        QuizError::InvalidRunnerUpAmount
+    );
+    require!(quiz_set.attempt_cooldown_seconds >= 0, QuizError::InvalidAttemptCooldown);
+    require!(quiz_set.result_grace_seconds >= 0, QuizError::InvalidResultGrace);
+    require!(
+        quiz_set.reward_mode == RewardMode::WinnerTakesAll || quiz_set.per_question_reward == 0,
+        QuizError::InvalidRewardMode
+    );
+    require!(
+        quiz_set.reward_brackets.len() <= MAX_REWARD_BRACKETS
+            && quiz_set.reward_brackets.iter().all(|bracket| bracket.bps <= 10_000)
+            && quiz_set.reward_brackets.windows(2).all(|pair| pair[0].max_seconds < pair[1].max_seconds),
+        QuizError::InvalidRewardBrackets
+    );
+    require!(
+        quiz_set.buffer_size == ANSWER_BUFFER_SIZE || quiz_set.buffer_size == ANSWER_BUFFER_SIZE_SHORT,
+        QuizError::InvalidBufferSize
+    );
+
+    // An announcement time, when set, can't predate the quiz itself
+    require!(
+        quiz_set.announce_after == 0 || quiz_set.announce_after >= quiz_set.created_at,
+        QuizError::InvalidAnnounceTime
+    );
+
+    // Grace only extends a window; on its own it would never apply
+    require!(
+        quiz_set.claim_window_seconds >= 0 && quiz_set.claim_grace_seconds >= 0,
+        QuizError::InvalidClaimWindow
+    );
+    require!(
+        quiz_set.claim_grace_seconds == 0 || quiz_set.claim_window_seconds > 0,
+        QuizError::InvalidClaimWindow
+    );
+
+    require!(quiz_set.retry_window_seconds >= 0, QuizError::InvalidRetryWindow);
+
+    // Reveals open after the commit phase, which must leave time before the quiz deadline
+    require!(
+        quiz_set.commit_deadline == 0
+            || (quiz_set.commit_deadline > quiz_set.created_at
+                && (quiz_set.deadline == 0 || quiz_set.commit_deadline < quiz_set.deadline)),
+        QuizError::InvalidCommitDeadline
+    );
+    require!(quiz_set.keeper_delay_seconds >= 0, QuizError::InvalidKeeperDelay);
+    require!(quiz_set.duration_seconds >= 0, QuizError::InvalidDuration);
+
+    // One player answering everything must not be able to drain more than the pool
+    require!(
+        quiz_set.per_question_reward.saturating_mul(quiz_set.question_count as u64) <= quiz_set.reward_amount,
+        QuizError::InvalidRewardAmount
+    );
+
+    // Revealing answers would hand out a rewarded quiz's solutions
+    require!(
+        !quiz_set.reveal_on_wrong || (quiz_set.is_practice && quiz_set.reward_amount == 0),
+        QuizError::RevealRequiresPractice
+    );
+
+    // At least one question must be required to finish the quiz
+    require!(quiz_set.bonus_count < quiz_set.question_count, QuizError::InvalidBonusCount);
+
+    // The target can't drop below weight already handed to added blocks
+    require!(
+        quiz_set.total_weight == 0 || quiz_set.assigned_weight <= quiz_set.total_weight,
+        QuizError::WeightMismatch
+    );
+
+    Ok(())
+}
+
+// The claim is co-signed by the proof key registered on the claimer's progress.
+// The signature covers the transaction's recent blockhash, so it can't be
+// replayed from an earlier claim attempt.
+fn has_claim_proof(player_progress: Option<&PlayerProgress>, proof_signer: Option<&Signer>) -> bool {
+    match (player_progress, proof_signer) {
+        (Some(progress), Some(signer)) => {
+            progress.proof_key != Pubkey::default() && signer.key() == progress.proof_key
+        }
+        _ => false,
+    }
+}
+
+// A proportional claimant's cut of the pool: reward * score / question_count
+pub fn proportional_share(reward_amount: u64, score: u8, question_count: u8) -> u64 {
+    if question_count == 0 {
+        return 0;
+    }
+    let score = score.min(question_count);
+    (reward_amount as u128 * score as u128 / question_count as u128) as u64
+}
+
+// Count the player towards min_participants on their first submission
+fn count_participant(quiz_set: &mut QuizSet, player_progress: &mut PlayerProgress) {
+    if !player_progress.is_participant {
+        player_progress.is_participant = true;
+        quiz_set.participant_count = quiz_set.participant_count.saturating_add(1);
+    }
+}
+
+// The authority encrypted the answers, so they may only play or win their own
+// quiz when the topic allowed it
+fn check_authority_play(quiz_set: &QuizSet, player: &Pubkey) -> Result<()> {
+    require!(
+        quiz_set.allow_authority_play || *player != quiz_set.authority,
+        QuizError::AuthorityCannotParticipate
+    );
+    Ok(())
+}
+
+// On gated quizzes, `holder` must present a token account of the gate mint
+// that they own with a non-zero balance
+fn check_gate(quiz_set: &QuizSet, holder: &Pubkey, gate_token_account: Option<&TokenAccount>) -> Result<()> {
+    let Some(gate_mint) = quiz_set.gate_mint else {
+        return Ok(());
+    };
+
+    let token_account = gate_token_account.ok_or(QuizError::GateRequirementNotMet)?;
+    require!(
+        token_account.mint == gate_mint && token_account.owner == *holder && token_account.amount > 0,
+        QuizError::GateRequirementNotMet
+    );
+
+    Ok(())
+}
+
+// Unwrap a callback's computation output. The match is exhaustive, so a new
+// variant in the Arcium SDK fails to compile instead of being misreported.
+fn computation_result<O>(output: ComputationOutputs<O>) -> Result<O> {
+    match output {
+        ComputationOutputs::Success(value) => Ok(value),
+        ComputationOutputs::Failure => {
+            msg!("Arcium computation failed");
+            Err(ErrorCode::AbortedComputation.into())
+        }
+    }
+}
+
+// sha256(answer || salt || user): what commit_answer stores and reveal_answer opens.
+// The salt keeps short answers from being guessed from the hash, and the user key
+// stops a commitment from being copied by another player.
+pub fn answer_commitment_hash(answer: &str, salt: &[u8; 32], user: &Pubkey) -> [u8; 32] {
+    hashv(&[answer.as_bytes(), salt.as_ref(), user.as_ref()]).to_bytes()
+}
+
+// Commits (and offline enqueues) close at the quiz's commit deadline, if it has one
+fn check_commit_open(quiz_set: &QuizSet, now: i64) -> Result<()> {
+    require!(
+        quiz_set.commit_deadline == 0 || now <= quiz_set.commit_deadline,
+        QuizError::CommitPhaseClosed
+    );
+    Ok(())
+}
+
+// A reveal must wait for the commit phase to close and land after the commit's
+// slot, and must match what was committed
+fn check_answer_reveal(
+    quiz_set: &QuizSet,
+    commitment: &[u8; 32],
+    committed_slot: u64,
+    answer: &str,
+    salt: &[u8; 32],
+    user: &Pubkey,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        quiz_set.commit_deadline == 0 || clock.unix_timestamp > quiz_set.commit_deadline,
+        QuizError::CommitPhaseOpen
+    );
+    require!(clock.slot > committed_slot, QuizError::RevealTooEarly);
+    require!(*commitment == answer_commitment_hash(answer, salt, user), QuizError::CommitmentMismatch);
+    Ok(())
+}
+
+// Check a reveal against the player's AnswerCommitment and close it, refunding
+// its rent. An empty PDA means nothing was committed.
+fn open_answer_commitment<'info>(
+    quiz_set: &QuizSet,
+    info: &AccountInfo<'info>,
+    player: &AccountInfo<'info>,
+    answer: &str,
+    salt: &[u8; 32],
+) -> Result<()> {
+    require!(
+        info.owner == &crate::ID && !info.data_is_empty(),
+        QuizError::AnswerNotCommitted
+    );
+    let commitment = AnswerCommitment::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    check_answer_reveal(quiz_set, &commitment.commitment, commitment.committed_slot, answer, salt, player.key)?;
+    close_program_account(info, player)
+}
+
+// Close a program-owned account by hand (for accounts not declared with
+// `close`): move its lamports to `destination`, then wipe it and hand it back to
+// the system program so it can't be revived later in the same transaction
+fn close_program_account<'info>(info: &AccountInfo<'info>, destination: &AccountInfo<'info>) -> Result<()> {
+    let lamports = info.lamports();
+    **info.try_borrow_mut_lamports()? = 0;
+    **destination.try_borrow_mut_lamports()? += lamports;
+
+    info.try_borrow_mut_data()?.fill(0);
+    info.assign(&anchor_lang::system_program::ID);
+    Ok(())
+}
+
+// Close an emptied token-reward vault's token account, rent to `destination`
+fn close_vault_token_account<'info>(
+    vault: &UncheckedAccount<'info>,
+    vault_seeds: &[&[u8]],
+    tokens: &RewardTokenAccounts<'_, 'info>,
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    let close_ctx = CpiContext::new_with_signer(
+        tokens.token_program.to_account_info(),
+        CloseAccount {
+            account: tokens.vault_token_account.to_account_info(),
+            destination: destination.clone(),
+            authority: vault.to_account_info(),
+        },
+        &[vault_seeds],
+    );
+    token_interface::close_account(close_ctx)
+}
+
+// Warn the host when a winner is set on a vault that can't cover the reward,
+// instead of leaving the winner to find out at claim time. Token rewards are
+// deposited in full at creation, so only lamport vaults are checked.
+fn warn_if_underfunded(quiz_set: &Account<QuizSet>, vault: &UncheckedAccount, winner: Pubkey) -> Result<()> {
+    if quiz_set.reward_mint.is_some() {
+        return Ok(());
+    }
+
+    let vault_balance = vault_payable_balance(vault, None)?;
+    if vault_balance < quiz_set.reward_amount {
+        emit!(VaultUnderfunded {
+            quiz_set: quiz_set.key(),
+            winner,
+            reward_amount: quiz_set.reward_amount,
+            vault_balance,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("⚠️ Vault holds {} of the {} reward", vault_balance, quiz_set.reward_amount);
+    }
+    Ok(())
+}
+
+// Free the author's active slot once a quiz's reward is settled. Quizzes
+// created before quotas existed hold no slot.
+fn release_active_slot(quiz_set: &mut QuizSet, author_quota: &mut AuthorQuota) {
+    if !quiz_set.is_active {
+        return;
+    }
+    quiz_set.is_active = false;
+    author_quota.active_quiz_count = author_quota.active_quiz_count.saturating_sub(1);
+}
+
+// Move a topic's escrowed total for `mint` by a deposit and/or a payout
+fn record_escrow_change(
+    reward_stats: &mut TopicRewardStats,
+    mint: Pubkey,
+    deposited: u64,
+    paid_out: u64,
+) -> Result<()> {
+    let index = match reward_stats.entries.iter().position(|entry| entry.mint == mint) {
+        Some(index) => index,
+        None => {
+            require!(reward_stats.entries.len() < MAX_REWARD_MINTS, QuizError::TooManyRewardMints);
+            reward_stats.entries.push(RewardMintTotal { mint, escrowed: 0, total_deposited: 0 });
+            reward_stats.entries.len() - 1
+        }
+    };
+
+    let entry = &mut reward_stats.entries[index];
+    entry.escrowed = entry.escrowed.saturating_add(deposited).saturating_sub(paid_out);
+    entry.total_deposited = entry.total_deposited.saturating_add(deposited);
+    Ok(())
+}
+
+// Charge the quiz's per-answer fee, if any, into the Arcium fee pool so players
+// fund the computations they trigger. A prepaid credit left by an earlier
+// prefund_next submission covers this answer; with prefund_next the payer also
+// pays for their next answer now, so consecutive questions need a single
+// funding transfer between them.
+fn collect_answer_fee<'info>(
+    quiz_set: &Account<'info, QuizSet>,
+    player_progress: &mut PlayerProgress,
+    payer: &Signer<'info>,
+    pool_account: &Account<'info, FeePool>,
+    system_program: &Program<'info, System>,
+    prefund_next: bool,
+) -> Result<()> {
+    if quiz_set.answer_fee == 0 {
+        return Ok(());
+    }
+
+    let used_prepaid = player_progress.prepaid_answers > 0;
+    let mut fees = 0u64;
+    if used_prepaid {
+        player_progress.prepaid_answers -= 1;
+    } else {
+        fees += 1;
+    }
+    if prefund_next {
+        player_progress.prepaid_answers = player_progress
+            .prepaid_answers
+            .checked_add(1)
+            .ok_or(QuizError::TooManyPrepaidAnswers)?;
+        fees += 1;
+    }
+
+    let amount = quiz_set.answer_fee.saturating_mul(fees);
+    if amount > 0 {
+        let transfer_ctx = CpiContext::new(
+            system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: payer.to_account_info(),
+                to: pool_account.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(transfer_ctx, amount)?;
+    }
+
+    emit!(AnswerFeeCollected {
+        quiz_set: quiz_set.key(),
+        payer: payer.key(),
+        amount,
+        used_prepaid,
+        prepaid_answers: player_progress.prepaid_answers,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Move data encrypted under old_nonce to new_nonce (additive nonce scheme)
+fn reencrypt(data: &mut [u8], old_nonce: u128, new_nonce: u128) {
+    nonce_decrypt(data, old_nonce);
+    nonce_encrypt(data, new_nonce);
+}
+
+// Log the quiz state behind a failed claim as an ErrorContext event and return
+// the error. The event only holds public account state, never answers.
+fn claim_failure(quiz_set: &Account<QuizSet>, claimer: Pubkey, vault_balance: u64, code: QuizError) -> Error {
+    emit!(ErrorContext {
+        quiz_set: quiz_set.key(),
+        claimer,
+        error_code: anchor_lang::error::ERROR_CODE_OFFSET + code as u32,
+        winner: quiz_set.winner,
+        is_reward_claimed: quiz_set.is_reward_claimed,
+        vault_balance,
+    });
+    code.into()
+}
+
+// SPL accounts moving a token reward between a quiz's vault and a user
+struct RewardTokenAccounts<'a, 'info> {
+    mint: &'a InterfaceAccount<'info, Mint>,
+    vault_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    user_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    token_program: &'a Interface<'info, TokenInterface>,
+}
+
+// Resolve the SPL accounts of a token-reward quiz (None on native SOL quizzes).
+// The vault's token account must be owned by the vault PDA and the user's by `user`.
+fn reward_token_accounts<'a, 'info>(
+    reward_mint: Option<Pubkey>,
+    mint: Option<&'a InterfaceAccount<'info, Mint>>,
+    vault_token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    user_token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    token_program: Option<&'a Interface<'info, TokenInterface>>,
+    vault: Pubkey,
+    user: Pubkey,
+) -> Result<Option<RewardTokenAccounts<'a, 'info>>> {
+    let Some(reward_mint) = reward_mint else {
+        return Ok(None);
+    };
+
+    let (Some(mint), Some(vault_token_account), Some(user_token_account), Some(token_program)) =
+        (mint, vault_token_account, user_token_account, token_program)
+    else {
+        return err!(QuizError::MissingRewardTokenAccounts);
+    };
+    require!(mint.key() == reward_mint, QuizError::InvalidRewardTokenAccount);
+    require!(
+        vault_token_account.mint == reward_mint && vault_token_account.owner == vault,
+        QuizError::InvalidRewardTokenAccount
+    );
+    require!(
+        user_token_account.mint == reward_mint && user_token_account.owner == user,
+        QuizError::InvalidRewardTokenAccount
+    );
+
+    Ok(Some(RewardTokenAccounts { mint, vault_token_account, user_token_account, token_program }))
+}
+
+// What a quiz's vault can pay out: its token balance on token-reward quizzes,
+// otherwise the lamports above the rent-exempt minimum, so a payout never
+// leaves the vault to be closed mid-quiz
+fn vault_payable_balance(vault: &UncheckedAccount, reward_tokens: Option<&RewardTokenAccounts>) -> Result<u64> {
+    Ok(match reward_tokens {
+        Some(tokens) => tokens.vault_token_account.amount,
+        None => vault.lamports().saturating_sub(Rent::get()?.minimum_balance(0)),
+    })
+}
+
+// Pay `amount` out of a quiz's vault: tokens signed for by the vault PDA on
+// token-reward quizzes, lamports otherwise
+fn pay_from_vault<'info>(
+    vault: &UncheckedAccount<'info>,
+    vault_seeds: &[&[u8]],
+    payout: &AccountInfo<'info>,
+    reward_tokens: Option<&RewardTokenAccounts<'_, 'info>>,
+    amount: u64,
+) -> Result<()> {
+    require!(vault_payable_balance(vault, reward_tokens)? >= amount, QuizError::InsufficientVaultBalance);
+
+    match reward_tokens {
+        Some(tokens) => {
+            let transfer_ctx = CpiContext::new_with_signer(
+                tokens.token_program.to_account_info(),
+                TransferChecked {
+                    from: tokens.vault_token_account.to_account_info(),
+                    mint: tokens.mint.to_account_info(),
+                    to: tokens.user_token_account.to_account_info(),
+                    authority: vault.to_account_info(),
+                },
+                &[vault_seeds],
+            );
+            token_interface::transfer_checked(transfer_ctx, amount, tokens.mint.decimals)
+        }
+        None => {
+            **vault.to_account_info().try_borrow_mut_lamports()? -= amount;
+            **payout.try_borrow_mut_lamports()? += amount;
+            Ok(())
+        }
+    }
+}
+
+// Book a payout against the topic escrow, the claimer's lifetime stats and
+// their receipt. Per-question claims may repeat, so receipts accumulate.
+fn record_claim(
+    quiz_set: Pubkey,
+    reward_mint: Option<Pubkey>,
+    claimer: Pubkey,
+    amount: u64,
+    reward_stats: &mut Account<TopicRewardStats>,
+    global_stats: &mut Account<GlobalUserStats>,
+    claim_receipt: &mut Account<ClaimReceipt>,
+) -> Result<()> {
+    record_escrow_change(reward_stats, reward_mint.unwrap_or(NATIVE_SOL_MINT), 0, amount)?;
+
+    global_stats.user = claimer;
+    global_stats.total_rewards = global_stats.total_rewards.saturating_add(amount);
+
+    claim_receipt.quiz_set = quiz_set;
+    claim_receipt.claimer = claimer;
+    claim_receipt.amount = claim_receipt.amount.saturating_add(amount);
+    claim_receipt.claimed_at = Clock::get()?.unix_timestamp;
+    Ok(())
+}
+
+// Whether the authority can cover create_quiz_set: the quiz set's rent plus the
+// initial deposit, in lamports or in reward tokens. The vault and author quota
+// rent, when still owed, is left to the system program to check.
+fn can_fund_quiz_creation(
+    authority: &Signer,
+    authority_token_account: Option<&InterfaceAccount<TokenAccount>>,
+    initial_funding: u64,
+) -> Result<bool> {
+    let quiz_set_rent = Rent::get()?.minimum_balance(QuizSet::LEN);
+    Ok(match authority_token_account {
+        Some(tokens) => tokens.amount >= initial_funding && authority.lamports() >= quiz_set_rent,
+        None => authority.lamports() >= quiz_set_rent.saturating_add(initial_funding),
+    })
+}
+
+// Message the password-derived key signs to let `player` join `quiz_set`
+fn join_message(player: &Pubkey, quiz_set: &Pubkey) -> Vec<u8> {
+    [b"k3hoot:join".as_ref(), player.as_ref(), quiz_set.as_ref()].concat()
+}
+
+// Create the zero-data, program-owned vault PDA for a quiz set. Anyone can send
+// lamports to the address beforehand, which would make create_account fail, so
+// only the shortfall is topped up before allocating and assigning it.
+fn create_vault<'info>(
+    payer: &Signer<'info>,
+    vault: &UncheckedAccount<'info>,
+    system_program: &Program<'info, System>,
+    quiz_set: &Pubkey,
+    bump: u8,
+) -> Result<()> {
+    let seeds: &[&[u8]] = &[b"vault", quiz_set.as_ref(), &[bump]];
+
+    let shortfall = Rent::get()?.minimum_balance(0).saturating_sub(vault.lamports());
+    if shortfall > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: vault.to_account_info(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+
+    anchor_lang::system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            anchor_lang::system_program::Allocate {
+                account_to_allocate: vault.to_account_info(),
+            },
+            &[seeds],
+        ),
+        0,
+    )?;
+    anchor_lang::system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            anchor_lang::system_program::Assign {
+                account_to_assign: vault.to_account_info(),
+            },
+            &[seeds],
+        ),
+        &crate::ID,
+    )
+}
+
+// A fresh question block for `payload`, checked against the quiz set
+fn new_question_block(
+    quiz_set: &Account<QuizSet>,
+    payload: &QuestionBlockPayload,
+    arcium_pubkey: [u8; 32],
+) -> Result<QuestionBlock> {
+    require!(
+        payload.question_index > 0 && payload.question_index <= quiz_set.question_count,
+        QuizError::InvalidQuestionIndex
+    );
+    require!(!quiz_set.is_initialized, QuizError::QuizSetAlreadyInitialized);
+    require!((payload.category as usize) < MAX_CATEGORIES, QuizError::InvalidCategory);
+
+    Ok(QuestionBlock {
+        quiz_set: quiz_set.key(),
+        question_index: payload.question_index as u32,
+        encrypted_x_coordinate: payload.encrypted_x_coordinate,
+        encrypted_y_coordinate: payload.encrypted_y_coordinate,
+        arcium_pubkey,
+        nonce: payload.nonce,
+        created_at: Clock::get()?.unix_timestamp,
+        version: QUESTION_BLOCK_VERSION,
+        attempt_count: 0,
+        correct_count: 0,
+        weight: payload.weight,
+        valid_until: 0,
+        is_bonus: payload.is_bonus,
+        hints: [[0u8; HINT_SIZE]; MAX_HINTS],
+        hint_count: 0,
+        hint_nonce: 0,
+        alt_answers: [[0u8; 64]; MAX_ALT_ANSWERS],
+        alt_answer_count: 0,
+        category: payload.category,
+    })
+}
+
+// Fold a new block into the quiz set's totals and content commitment, and
+// mark the quiz initialized once its last question lands
+fn register_question_block(
+    quiz_set: &mut Account<QuizSet>,
+    topic: &Topic,
+    block_key: Pubkey,
+    question_block: &QuestionBlock,
+) -> Result<()> {
+    quiz_set.category_totals[question_block.category as usize] += 1;
+    quiz_set.assigned_weight = quiz_set
+        .assigned_weight
+        .checked_add(question_block.weight)
+        .ok_or(QuizError::WeightMismatch)?;
+    if question_block.is_bonus {
+        quiz_set.bonus_count += 1;
+    }
+    validate_quiz_config(quiz_set)?;
+
+    // Fold this block into the quiz content commitment
+    xor_into(&mut quiz_set.content_hash, &question_block_hash(question_block));
+
+    // Remember whether any two correct answers differ, for the topic's quality gate
+    let answer_hash = hashv(&[&decrypt_correct_answer(question_block)[..]]).to_bytes();
+    if quiz_set.first_answer_hash == [0u8; 32] {
+        quiz_set.first_answer_hash = answer_hash;
+    } else if quiz_set.first_answer_hash != answer_hash {
+        quiz_set.has_distinct_answers = true;
+    }
+
+    if question_block.question_index == quiz_set.question_count as u32 {
+        // Weighted quizzes must hand out exactly the target score
+        require!(
+            quiz_set.total_weight == 0 || quiz_set.assigned_weight == quiz_set.total_weight,
+            QuizError::WeightMismatch
+        );
+        require!(
+            !topic.require_distinct_answers || quiz_set.has_distinct_answers,
+            QuizError::TrivialQuizRejected
+        );
+        quiz_set.is_initialized = true;
+    }
+
+    emit!(QuestionBlockAdded {
+        question_block: block_key,
+        quiz_set: quiz_set.key(),
+        question_index: question_block.question_index,
+        timestamp: question_block.created_at,
+    });
+    Ok(())
+}
+
+// Create a question block PDA passed as a remaining account (batch adds)
+fn create_question_block<'info>(
+    payer: &Signer<'info>,
+    question_block: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    quiz_set: &Pubkey,
+    question_index: u8,
+    bump: u8,
+) -> Result<()> {
+    let seeds: &[&[u8]] = &[b"question_block", quiz_set.as_ref(), &[question_index], &[bump]];
+    anchor_lang::system_program::create_account(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            anchor_lang::system_program::CreateAccount {
+                from: payer.to_account_info(),
+                to: question_block.clone(),
+            },
+            &[seeds],
+        ),
+        Rent::get()?.minimum_balance(QuestionBlock::LEN),
+        QuestionBlock::LEN as u64,
+        &crate::ID,
+    )
+}
+
+// ===== ACCOUNT STRUCTURES =====
+
+// ===== PROGRAM CONFIG ACCOUNTS =====
+
+#[derive(Accounts)]
+pub struct InitializeProgramConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = ProgramConfig::LEN,
+        seeds = [b"program_config"],
+        bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    // Only the upgrade authority may take the admin role
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ QuizError::Unauthorized
+    )]
+    pub program: Program<'info, crate::program::K3HootProgramArcium>,
+    
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ QuizError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureProgram<'info> {
+    #[account(
+        mut,
+        seeds = [b"program_config"],
+        bump,
+        has_one = admin @ QuizError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+// ===== TOPIC MANAGEMENT ACCOUNTS =====
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct CreateTopic<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = Topic::LEN,
+        seeds = [b"topic", name.as_bytes()],
+        bump
+    )]
+    pub topic: Account<'info, Topic>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = TopicRewardStats::LEN,
+        seeds = [b"reward_stats", topic.key().as_ref()],
+        bump
+    )]
+    pub reward_stats: Account<'info, TopicRewardStats>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TransferTopicOwnership<'info> {
+    #[account(
+        mut,
+        seeds = [b"topic", topic.name.as_bytes()],
+        bump,
+        has_one = owner @ QuizError::NotTopicOwner
+    )]
+    pub topic: Account<'info, Topic>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// has_one is checked against the topic as it is when the transaction executes,
+// so a toggle the previous owner signed before a transfer fails once it lands
+#[derive(Accounts)]
+pub struct ToggleTopicStatus<'info> {
+    #[account(
+        mut,
+        seeds = [b"topic", topic.name.as_bytes()],
+        bump,
+        has_one = owner @ QuizError::NotTopicOwner
+    )]
+    pub topic: Account<'info, Topic>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureTopic<'info> {
+    #[account(
+        mut,
+        seeds = [b"topic", topic.name.as_bytes()],
+        bump,
+        has_one = owner @ QuizError::NotTopicOwner
+    )]
+    pub topic: Account<'info, Topic>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetLeaderboardMinScore<'info> {
+    #[account(
+        mut,
+        seeds = [b"topic", topic.name.as_bytes()],
+        bump,
+        has_one = owner @ QuizError::NotTopicOwner
+    )]
+    pub topic: Account<'info, Topic>,
+    
+    #[account(
+        mut,
+        seeds = [b"leaderboard", topic.key().as_ref()],
+        bump
+    )]
+    pub leaderboard: Option<Account<'info, TopicLeaderboard>>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureTopicAsAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"topic", topic.name.as_bytes()],
+        bump,
+        has_one = owner @ QuizError::NotTopicOwner
+    )]
+    pub topic: Account<'info, Topic>,
+    
+    pub owner: Signer<'info>,
+    
+    #[account(
+        seeds = [b"program_config"],
+        bump,
+        has_one = admin @ QuizError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+// ===== QUIZ MANAGEMENT ACCOUNTS =====
+
+#[derive(Accounts)]
+#[instruction(name: String, question_count: u8, unique_id: u8, reward_amount: u64, initial_funding: u64)]
+pub struct CreateQuizSet<'info> {
+    // Declared ahead of the `init` accounts so a short balance fails before any is created
+    #[account(mut)]
+    pub authority_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = can_fund_quiz_creation(&authority, authority_token_account.as_ref(), initial_funding)?
+            @ QuizError::InsufficientFunds
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = QuizSet::LEN,
+        seeds = [b"quiz_set", authority.key().as_ref(), &[unique_id]],
+        bump
+    )]
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    #[account(
+        seeds = [b"topic", topic.name.as_bytes()],
+        bump,
+        constraint = topic.is_active @ QuizError::TopicNotActive,
+        constraint = topic.owner == authority.key() @ QuizError::NotTopicOwner
+    )]
+    pub topic: Account<'info, Topic>,
+    
+    // Created in the handler unless init_quiz_vault already did; the seeds tie it to this quiz
+    #[account(
+        mut,
+        seeds = [b"vault", quiz_set.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is a vault account for storing SOL rewards
+    pub vault: UncheckedAccount<'info>,
+    
+    // Topic escrow statistics, booked on every escrow movement
+    #[account(
+        mut,
+        seeds = [b"reward_stats", topic.key().as_ref()],
+        bump
+    )]
+    pub reward_stats: Account<'info, TopicRewardStats>,
+    
+    // Program-wide limits, set by the program admin
+    #[account(
+        seeds = [b"program_config"],
+        bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    
+    // Quizzes this author has open, checked against the program-wide cap
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AuthorQuota::LEN,
+        seeds = [b"author", authority.key().as_ref()],
+        bump
+    )]
+    pub author_quota: Account<'info, AuthorQuota>,
+    
+    // SPL reward: the mint, the vault's token account (owned by the vault PDA) and
+    // authority_token_account above funding it. Omitted for a native SOL reward.
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(unique_id: u8)]
+pub struct InitQuizVault<'info> {
+    #[account(
+        seeds = [b"quiz_set", authority.key().as_ref(), &[unique_id]],
+        bump
+    )]
+    /// CHECK: Quiz set PDA the vault belongs to; it need not exist yet
+    pub quiz_set: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 0,  // No data space - pure SOL storage
+        seeds = [b"vault", quiz_set.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is a vault account for storing SOL rewards
+    pub vault: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundQuiz<'info> {
+    #[account(
+        mut,
+        seeds = [b"quiz_set", quiz_set.authority.as_ref(), &[quiz_set.unique_id]],
+        bump,
+        constraint = !quiz_set.is_funded @ QuizError::InvalidFundingAmount
+    )]
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", quiz_set.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is a vault account for storing SOL rewards
+    pub vault: UncheckedAccount<'info>,
+    
+    // Topic escrow statistics, booked on every escrow movement
+    #[account(
+        mut,
+        seeds = [b"reward_stats", quiz_set.topic.as_ref()],
+        bump
+    )]
+    pub reward_stats: Account<'info, TopicRewardStats>,
+    
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(question_index: u8)]
+pub struct AddEncryptedQuestionBlock<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = QuestionBlock::LEN,
+        seeds = [
+            b"question_block",
+            quiz_set.key().as_ref(),
+            &[question_index]
+        ],
+        bump
+    )]
+    pub question_block: Account<'info, QuestionBlock>,
+    
+    #[account(mut, has_one = topic @ QuizError::QuizNotInTopic)]
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    pub topic: Account<'info, Topic>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddEncryptedQuestionBlocksBatch<'info> {
+    #[account(mut, has_one = topic @ QuizError::QuizNotInTopic)]
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    pub topic: Account<'info, Topic>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateQuestionBlock<'info> {
+    #[account(mut, owner = crate::ID)]
+    /// CHECK: May still use an older, smaller layout; discriminator and quiz_set are checked in the handler
+    pub question_block: UncheckedAccount<'info>,
+    
+    #[account(has_one = authority @ QuizError::Unauthorized)]
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetQuestionExpiry<'info> {
+    #[account(
+        mut,
+        has_one = quiz_set @ QuizError::InvalidQuestionBlock
+    )]
+    pub question_block: Account<'info, QuestionBlock>,
+    
+    #[account(has_one = authority @ QuizError::Unauthorized)]
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetQuestionHints<'info> {
+    #[account(
+        mut,
+        has_one = quiz_set @ QuizError::InvalidQuestionBlock
+    )]
+    pub question_block: Account<'info, QuestionBlock>,
+    
+    #[account(has_one = authority @ QuizError::Unauthorized)]
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetAcceptableAnswers<'info> {
+    #[account(
+        mut,
+        has_one = quiz_set @ QuizError::InvalidQuestionBlock
+    )]
+    pub question_block: Account<'info, QuestionBlock>,
+    
+    // Once anyone has answered, the accepted answers are fixed
+    #[account(
+        mut,
+        has_one = authority @ QuizError::Unauthorized,
+        constraint = quiz_set.winner.is_none() @ QuizError::WinnerAlreadySet,
+        constraint = quiz_set.participant_count == 0 @ QuizError::AnswersLocked
+    )]
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    pub authority: Signer<'info>,
+}
+
+// ===== QUIZ CONFIGURATION ACCOUNTS =====
+
+#[derive(Accounts)]
+pub struct ConfigureQuizSet<'info> {
+    #[account(
+        mut,
+        has_one = authority @ QuizError::Unauthorized,
+        constraint = !quiz_set.is_initialized @ QuizError::QuizSetAlreadyInitialized
+    )]
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    pub authority: Signer<'info>,
+}
+
+// Quiz sets are created at their full LEN, so the realloc only changes the
+// account if QuizSet::LEN has grown since it was created. It runs after the
+// account is deserialized, so it can't rescue one that no longer loads.
+#[derive(Accounts)]
+pub struct RenameQuizSet<'info> {
+    #[account(
+        mut,
+        has_one = authority @ QuizError::Unauthorized,
+        realloc = QuizSet::LEN,
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeSettlement<'info> {
+    #[account(mut)]
+    pub quiz_set: Account<'info, QuizSet>,
+
+    pub caller: Signer<'info>, // Anyone can publish the settlement once the quiz is settled
+}
+
+#[derive(Accounts)]
+pub struct AnnounceWinner<'info> {
+    #[account(
+        mut,
+        constraint = quiz_set.winner.is_some() @ QuizError::QuizNotCompleted,
+        constraint = !quiz_set.is_winner_announced @ QuizError::WinnerAlreadyAnnounced
+    )]
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    pub caller: Signer<'info>, // Anyone can trigger the announcement once it's due
+}
+
+// ===== SCORING SYSTEM ACCOUNTS =====
+
+#[derive(Accounts)]
 pub struct RecordQuizCompletion<'info> {
     #[account(
         init_if_needed,
-        payer = user,
-        space = UserScore::LEN,
-        seeds = [b"user_score", user.key().as_ref(), topic.key().as_ref()],
+        payer = user,
+        space = UserScore::LEN,
+        seeds = [b"user_score", user.key().as_ref(), topic.key().as_ref()],
+        bump
+    )]
+    pub user_score: Account<'info, UserScore>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = QuizHistory::LEN,
+        seeds = [
+            b"quiz_history", 
+            user.key().as_ref(), 
+            quiz_set.key().as_ref(),
+            &user_score.total_completed.to_le_bytes() // One history per completion in the topic
+        ],
+        bump
+    )]
+    pub quiz_history: Account<'info, QuizHistory>,
+    
+    // Marks the completion as recorded, so each quiz set counts once per player
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = PlayerProgress::LEN,
+        seeds = [b"progress", user.key().as_ref(), quiz_set.key().as_ref()],
+        bump,
+        constraint = !player_progress.completion_recorded @ QuizError::CompletionAlreadyRecorded
+    )]
+    pub player_progress: Account<'info, PlayerProgress>,
+    
+    #[account(
+        seeds = [b"quiz_set", quiz_set.authority.as_ref(), &[quiz_set.unique_id]],
+        bump,
+        constraint = quiz_set.topic == topic.key() @ QuizError::QuizNotInTopic
+    )]
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    #[account(
+        mut,
+        seeds = [b"topic", topic.name.as_bytes()],
+        bump
+    )]
+    pub topic: Account<'info, Topic>,
+    
+    #[account(
+        mut,
+        seeds = [b"leaderboard", topic.key().as_ref()],
+        bump
+    )]
+    pub leaderboard: Option<Account<'info, TopicLeaderboard>>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = GlobalUserStats::LEN,
+        seeds = [b"global", user.key().as_ref()],
+        bump
+    )]
+    pub global_stats: Account<'info, GlobalUserStats>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitTopicLeaderboard<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = TopicLeaderboard::LEN,
+        seeds = [b"leaderboard", topic.key().as_ref()],
+        bump
+    )]
+    pub leaderboard: Account<'info, TopicLeaderboard>,
+    
+    #[account(
+        seeds = [b"topic", topic.name.as_bytes()],
+        bump,
+        has_one = owner @ QuizError::NotTopicOwner
+    )]
+    pub topic: Account<'info, Topic>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct JoinQuiz<'info> {
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProgress::LEN,
+        seeds = [b"progress", player.key().as_ref(), quiz_set.key().as_ref()],
+        bump
+    )]
+    pub player_progress: Account<'info, PlayerProgress>,
+    
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    #[account(mut)]
+    pub player: Signer<'info>,
+    
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterProofKey<'info> {
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProgress::LEN,
+        seeds = [b"progress", player.key().as_ref(), quiz_set.key().as_ref()],
+        bump
+    )]
+    pub player_progress: Account<'info, PlayerProgress>,
+    
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    #[account(mut)]
+    pub player: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EnqueueAnswer<'info> {
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = AnswerQueue::LEN,
+        seeds = [b"answer_queue", player.key().as_ref(), quiz_set.key().as_ref()],
+        bump
+    )]
+    pub answer_queue: Account<'info, AnswerQueue>,
+    
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProgress::LEN,
+        seeds = [b"progress", player.key().as_ref(), quiz_set.key().as_ref()],
+        bump
+    )]
+    pub player_progress: Account<'info, PlayerProgress>,
+    
+    /// CHECK: May not exist if the author skipped this index; loaded via load_question_block
+    pub question_block: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    // Player's token account for the quiz's gate mint, required only on gated quizzes
+    pub gate_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub player: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResetPlayerProgress<'info> {
+    #[account(
+        mut,
+        seeds = [b"progress", player.key().as_ref(), quiz_set.key().as_ref()],
+        bump
+    )]
+    pub player_progress: Account<'info, PlayerProgress>,
+    
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    pub player: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AttestCompletion<'info> {
+    #[account(
+        init,
+        payer = user,
+        space = Attestation::LEN,
+        seeds = [b"attestation", quiz_set.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+    
+    #[account(
+        seeds = [b"progress", user.key().as_ref(), quiz_set.key().as_ref()],
+        bump
+    )]
+    pub player_progress: Account<'info, PlayerProgress>,
+    
+    #[account(
+        constraint = quiz_set.winner.is_some() @ QuizError::QuizNotCompleted
+    )]
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetTopicRewardStats<'info> {
+    #[account(
+        seeds = [b"reward_stats", reward_stats.topic.as_ref()],
+        bump
+    )]
+    pub reward_stats: Account<'info, TopicRewardStats>,
+}
+
+#[derive(Accounts)]
+pub struct GetUserRank<'info> {
+    #[account(
+        seeds = [b"leaderboard", topic.key().as_ref()],
+        bump
+    )]
+    pub leaderboard: Account<'info, TopicLeaderboard>,
+    
+    pub topic: Account<'info, Topic>,
+    
+    /// CHECK: Only the key is used to look up the leaderboard entry
+    pub user: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct LinkEphemeralScore<'info> {
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserScore::LEN,
+        seeds = [b"user_score", user.key().as_ref(), topic.key().as_ref()],
+        bump
+    )]
+    pub main_score: Account<'info, UserScore>,
+    
+    #[account(
+        mut,
+        close = ephemeral,
+        seeds = [b"user_score", ephemeral.key().as_ref(), topic.key().as_ref()],
+        bump
+    )]
+    pub ephemeral_score: Account<'info, UserScore>,
+    
+    #[account(mut)]
+    /// CHECK: Ephemeral key, proven by the preceding Ed25519 signature instruction
+    pub ephemeral: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"topic", topic.name.as_bytes()],
+        bump
+    )]
+    pub topic: Account<'info, Topic>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetTimeRemaining<'info> {
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    #[account(
+        constraint = player_progress.quiz_set == quiz_set.key() @ QuizError::ProgressNotInQuiz
+    )]
+    pub player_progress: Account<'info, PlayerProgress>,
+}
+
+#[derive(Accounts)]
+pub struct GetUserGlobalStats<'info> {
+    /// CHECK: Only used to derive the stats PDA
+    pub user: AccountInfo<'info>,
+    
+    #[account(
+        seeds = [b"global", user.key().as_ref()],
+        bump
+    )]
+    pub global_stats: Account<'info, GlobalUserStats>,
+}
+
+#[derive(Accounts)]
+pub struct SetWinnerForDevnet<'info> {
+    #[account(
+        mut,
+        has_one = authority,
+        constraint = quiz_set.is_initialized @ QuizError::QuizNotInitialized
+    )]
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"vault", quiz_set.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Vault holding the quiz's SOL reward; read to warn on underfunding
+    pub vault: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetWinnerForUser<'info> {
+    #[account(
+        mut,
+        constraint = quiz_set.is_initialized @ QuizError::QuizNotInitialized,
+        constraint = quiz_set.winner.is_none()
+            || quiz_set.won_slot == Clock::get()?.slot
+            || quiz_set.max_winners > 1 @ QuizError::WinnerAlreadySet,
+        constraint = quiz_set.result_authority == setter.key() @ QuizError::UnauthorizedSetter
+    )]
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    #[account(mut)]
+    pub setter: Signer<'info>, // The quiz's result_authority
+    
+    #[account(
+        seeds = [b"vault", quiz_set.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Vault holding the quiz's SOL reward; read to warn on underfunding
+    pub vault: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(question_index: u8)]
+pub struct CommitAnswer<'info> {
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = AnswerCommitment::LEN,
+        seeds = [b"answer_commit", quiz_set.key().as_ref(), user.key().as_ref(), &[question_index]],
+        bump
+    )]
+    pub answer_commitment: Account<'info, AnswerCommitment>,
+    
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("validate_answer", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, user_answer: String, question_index: u8)]
+pub struct RevealAnswer<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    // Program-wide signer PDA shared by all payers; created once, never per payer
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = AnswerSubmission::LEN,
+        seeds = [b"submission", &computation_offset.to_le_bytes()],
+        bump
+    )]
+    pub answer_submission: Account<'info, AnswerSubmission>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = PlayerProgress::LEN,
+        seeds = [b"progress", payer.key().as_ref(), quiz_set.key().as_ref()],
+        bump
+    )]
+    pub player_progress: Account<'info, PlayerProgress>,
+    
+    /// CHECK: May not exist if nothing was committed; opened via open_answer_commitment and closed on reveal
+    #[account(
+        mut,
+        seeds = [b"answer_commit", quiz_set.key().as_ref(), payer.key().as_ref(), &[question_index]],
+        bump
+    )]
+    pub answer_commitment: UncheckedAccount<'info>,
+    
+    /// CHECK: May not exist if the author skipped this index; loaded via load_question_block
+    pub question_block: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    // Payer's token account for the quiz's gate mint, required only on gated quizzes
+    pub gate_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: This is a mempool account managed by Arcium
+    pub mempool_account: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: This is an execution pool account managed by Arcium
+    pub executing_pool: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: This is a computation account managed by Arcium
+    pub computation_account: UncheckedAccount<'info>,
+    
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VALIDATE_ANSWER)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[queue_computation_accounts("validate_answer", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct ProcessQueuedAnswers<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    // Program-wide signer PDA shared by all payers; created once, never per payer
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"answer_queue", payer.key().as_ref(), quiz_set.key().as_ref()],
+        bump
+    )]
+    pub answer_queue: Account<'info, AnswerQueue>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = AnswerSubmission::LEN,
+        seeds = [b"submission", &computation_offset.to_le_bytes()],
+        bump
+    )]
+    pub answer_submission: Account<'info, AnswerSubmission>,
+    
+    #[account(
+        mut,
+        seeds = [b"progress", payer.key().as_ref(), quiz_set.key().as_ref()],
+        bump
+    )]
+    pub player_progress: Account<'info, PlayerProgress>,
+    
+    /// CHECK: Must be the block of the queued answer's index; loaded via load_question_block
+    pub question_block: UncheckedAccount<'info>,
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: This is a mempool account managed by Arcium
+    pub mempool_account: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: This is an execution pool account managed by Arcium
+    pub executing_pool: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: This is a computation account managed by Arcium
+    pub computation_account: UncheckedAccount<'info>,
+    
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VALIDATE_ANSWER)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[callback_accounts("validate_answer")]
+#[derive(Accounts)]
+pub struct ValidateAnswerCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VALIDATE_ANSWER)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    // Accounts below follow the CallbackAccount order used when queuing
+    #[account(
+        mut,
+        address = answer_submission.submitter @ QuizError::SubmitterMismatch
+    )]
+    /// CHECK: Original submitter, checked against the answer submission record
+    pub payer: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = payer,
+        has_one = quiz_set @ QuizError::SubmitterMismatch
+    )]
+    pub answer_submission: Account<'info, AnswerSubmission>,
+    #[account(
+        mut,
+        constraint = player_progress.player == answer_submission.submitter @ QuizError::SubmitterMismatch,
+        constraint = player_progress.quiz_set == quiz_set.key() @ QuizError::SubmitterMismatch
+    )]
+    pub player_progress: Account<'info, PlayerProgress>,
+    #[account(mut)]
+    pub question_block: Account<'info, QuestionBlock>,
+    #[account(mut)]
+    pub quiz_set: Account<'info, QuizSet>,
+}
+
+#[queue_computation_accounts("validate_answer_short", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, user_answer: String, question_index: u8)]
+pub struct ValidateAnswerShortOnchain<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = AnswerSubmission::LEN,
+        seeds = [b"submission", &computation_offset.to_le_bytes()],
+        bump
+    )]
+    pub answer_submission: Account<'info, AnswerSubmission>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = PlayerProgress::LEN,
+        seeds = [b"progress", payer.key().as_ref(), quiz_set.key().as_ref()],
+        bump
+    )]
+    pub player_progress: Account<'info, PlayerProgress>,
+    
+    /// CHECK: May not exist if nothing was committed; opened via open_answer_commitment and closed on reveal
+    #[account(
+        mut,
+        seeds = [b"answer_commit", quiz_set.key().as_ref(), payer.key().as_ref(), &[question_index]],
+        bump
+    )]
+    pub answer_commitment: UncheckedAccount<'info>,
+    
+    /// CHECK: May not exist if the author skipped this index; loaded via load_question_block
+    pub question_block: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    // Payer's token account for the quiz's gate mint, required only on gated quizzes
+    pub gate_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: This is a mempool account managed by Arcium
+    pub mempool_account: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: This is an execution pool account managed by Arcium
+    pub executing_pool: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: This is a computation account managed by Arcium
+    pub computation_account: UncheckedAccount<'info>,
+    
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VALIDATE_ANSWER_SHORT)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[callback_accounts("validate_answer_short")]
+#[derive(Accounts)]
+pub struct ValidateAnswerShortCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VALIDATE_ANSWER_SHORT)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    // Accounts below follow the CallbackAccount order used when queuing
+    #[account(
+        mut,
+        address = answer_submission.submitter @ QuizError::SubmitterMismatch
+    )]
+    /// CHECK: Original submitter, checked against the answer submission record
+    pub payer: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = payer,
+        has_one = quiz_set @ QuizError::SubmitterMismatch
+    )]
+    pub answer_submission: Account<'info, AnswerSubmission>,
+    #[account(
+        mut,
+        constraint = player_progress.player == answer_submission.submitter @ QuizError::SubmitterMismatch,
+        constraint = player_progress.quiz_set == quiz_set.key() @ QuizError::SubmitterMismatch
+    )]
+    pub player_progress: Account<'info, PlayerProgress>,
+    #[account(mut)]
+    pub question_block: Account<'info, QuestionBlock>,
+    #[account(mut)]
+    pub quiz_set: Account<'info, QuizSet>,
+}
+
+#[queue_computation_accounts("verify_correct_answer", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, question_index: u8)]
+pub struct VerifyCorrectAnswer<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    
+    #[account(
+        seeds = [b"question_block", quiz_set.key().as_ref(), &[question_index]],
+        bump
+    )]
+    pub question_block: Account<'info, QuestionBlock>,
+    
+    // Only the host, and only once the quiz is over
+    #[account(
+        constraint = quiz_set.authority == payer.key() @ QuizError::Unauthorized,
+        constraint = quiz_set.winner.is_some() @ QuizError::QuizNotCompleted
+    )]
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: This is a mempool account managed by Arcium
+    pub mempool_account: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: This is an execution pool account managed by Arcium
+    pub executing_pool: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: This is a computation account managed by Arcium
+    pub computation_account: UncheckedAccount<'info>,
+    
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VERIFY_CORRECT_ANSWER)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[callback_accounts("verify_correct_answer")]
+#[derive(Accounts)]
+pub struct VerifyCorrectAnswerCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VERIFY_CORRECT_ANSWER)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    // Accounts below follow the CallbackAccount order used when queuing
+    #[account(has_one = quiz_set)]
+    pub question_block: Account<'info, QuestionBlock>,
+    pub quiz_set: Account<'info, QuizSet>,
+}
+
+#[queue_computation_accounts("crosscheck_circuit", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct CrosscheckCircuit<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: This is a mempool account managed by Arcium
+    pub mempool_account: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: This is an execution pool account managed by Arcium
+    pub executing_pool: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: This is a computation account managed by Arcium
+    pub computation_account: UncheckedAccount<'info>,
+    
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_CROSSCHECK_CIRCUIT)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[callback_accounts("crosscheck_circuit")]
+#[derive(Accounts)]
+pub struct CrosscheckCircuitCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_CROSSCHECK_CIRCUIT)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct BeginKeyRotation<'info> {
+    #[account(
+        mut,
+        has_one = authority @ QuizError::Unauthorized,
+        constraint = quiz_set.is_initialized @ QuizError::QuizNotInitialized
+    )]
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    // First block, for the quiz's current key
+    #[account(
+        seeds = [b"question_block", quiz_set.key().as_ref(), &[1]],
+        bump
+    )]
+    pub question_block: Account<'info, QuestionBlock>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AbortKeyRotation<'info> {
+    #[account(
+        mut,
+        has_one = authority @ QuizError::Unauthorized
+    )]
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[queue_computation_accounts("rotate_block", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, question_index: u8)]
+pub struct RotateBlock<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    
+    #[account(
+        seeds = [b"question_block", quiz_set.key().as_ref(), &[question_index]],
+        bump
+    )]
+    pub question_block: Account<'info, QuestionBlock>,
+    
+    #[account(
+        mut,
+        constraint = quiz_set.authority == payer.key() @ QuizError::Unauthorized
+    )]
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: This is a mempool account managed by Arcium
+    pub mempool_account: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: This is an execution pool account managed by Arcium
+    pub executing_pool: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: This is a computation account managed by Arcium
+    pub computation_account: UncheckedAccount<'info>,
+    
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_ROTATE_BLOCK)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[callback_accounts("rotate_block")]
+#[derive(Accounts)]
+pub struct RotateBlockCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_ROTATE_BLOCK)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    // Accounts below follow the CallbackAccount order used when queuing
+    #[account(mut, has_one = quiz_set)]
+    pub question_block: Account<'info, QuestionBlock>,
+    #[account(mut)]
+    pub quiz_set: Account<'info, QuizSet>,
+}
+
+#[queue_computation_accounts("reveal_hint", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, question_index: u8)]
+pub struct RequestNextHint<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = PlayerProgress::LEN,
+        seeds = [b"progress", payer.key().as_ref(), quiz_set.key().as_ref()],
+        bump
+    )]
+    pub player_progress: Account<'info, PlayerProgress>,
+    
+    #[account(
+        seeds = [b"question_block", quiz_set.key().as_ref(), &[question_index]],
+        bump
+    )]
+    pub question_block: Account<'info, QuestionBlock>,
+    
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: This is a mempool account managed by Arcium
+    pub mempool_account: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: This is an execution pool account managed by Arcium
+    pub executing_pool: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: This is a computation account managed by Arcium
+    pub computation_account: UncheckedAccount<'info>,
+    
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_HINT)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[queue_computation_accounts("reveal_answer", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, question_index: u8)]
+pub struct RevealMyMistakes<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    
+    // Seeded by the payer, so only the player themselves can ask for a reveal
+    #[account(
+        mut,
+        seeds = [b"progress", payer.key().as_ref(), quiz_set.key().as_ref()],
+        bump
+    )]
+    pub player_progress: Account<'info, PlayerProgress>,
+    
+    #[account(
+        seeds = [b"question_block", quiz_set.key().as_ref(), &[question_index]],
+        bump
+    )]
+    pub question_block: Account<'info, QuestionBlock>,
+    
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: This is a mempool account managed by Arcium
+    pub mempool_account: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: This is an execution pool account managed by Arcium
+    pub executing_pool: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: This is a computation account managed by Arcium
+    pub computation_account: UncheckedAccount<'info>,
+    
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_ANSWER)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[callback_accounts("reveal_answer")]
+#[derive(Accounts)]
+pub struct RevealAnswerCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_ANSWER)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    // Accounts below follow the CallbackAccount order used when queuing
+    pub player_progress: Account<'info, PlayerProgress>,
+    #[account(constraint = question_block.quiz_set == player_progress.quiz_set @ QuizError::InvalidQuestionBlock)]
+    pub question_block: Account<'info, QuestionBlock>,
+}
+
+#[callback_accounts("reveal_hint")]
+#[derive(Accounts)]
+pub struct RevealHintCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_HINT)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    // Accounts below follow the CallbackAccount order used when queuing
+    pub player_progress: Account<'info, PlayerProgress>,
+    #[account(constraint = question_block.quiz_set == player_progress.quiz_set @ QuizError::InvalidQuestionBlock)]
+    pub question_block: Account<'info, QuestionBlock>,
+}
+
+#[callback_accounts("encrypt_quiz")]
+#[derive(Accounts)]
+pub struct EncryptQuizCallback<'info> {
+    
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_ENCRYPT_QUIZ)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    pub question_block: Account<'info, QuestionBlock>,
+}
+
+#[callback_accounts("decrypt_quiz")]
+#[derive(Accounts)]
+pub struct DecryptQuizCallback<'info> {
+    
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_DECRYPT_QUIZ)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    pub question_block: Account<'info, QuestionBlock>,
+}
+
+#[queue_computation_accounts("encrypt_quiz", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct EncryptQuizData<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    
+    pub question_block: Account<'info, QuestionBlock>,
+    
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: This is a mempool account managed by Arcium
+    pub mempool_account: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: This is an execution pool account managed by Arcium
+    pub executing_pool: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: This is a computation account managed by Arcium
+    pub computation_account: UncheckedAccount<'info>,
+    
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_ENCRYPT_QUIZ)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[queue_computation_accounts("decrypt_quiz", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct DecryptQuizData<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    
+    pub question_block: Account<'info, QuestionBlock>,
+    
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: This is a mempool account managed by Arcium
+    pub mempool_account: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: This is an execution pool account managed by Arcium
+    pub executing_pool: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: This is a computation account managed by Arcium
+    pub computation_account: UncheckedAccount<'info>,
+    
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_DECRYPT_QUIZ)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+// ===== ARCIUM INITIALIZATION STRUCTURES =====
+
+#[init_computation_definition_accounts("validate_answer", payer)]
+#[derive(Accounts)]
+pub struct InitValidateAnswerCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[init_computation_definition_accounts("validate_answer_short", payer)]
+#[derive(Accounts)]
+pub struct InitValidateAnswerShortCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[init_computation_definition_accounts("verify_correct_answer", payer)]
+#[derive(Accounts)]
+pub struct InitVerifyCorrectAnswerCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[init_computation_definition_accounts("crosscheck_circuit", payer)]
+#[derive(Accounts)]
+pub struct InitCrosscheckCircuitCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[init_computation_definition_accounts("rotate_block", payer)]
+#[derive(Accounts)]
+pub struct InitRotateBlockCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[init_computation_definition_accounts("reveal_hint", payer)]
+#[derive(Accounts)]
+pub struct InitRevealHintCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PreflightArcium<'info> {
+    /// CHECK: Compared against the derived MXE address in the handler
+    pub mxe_account: UncheckedAccount<'info>,
+    /// CHECK: Compared against the MXE's cluster address in the handler
+    pub cluster_account: UncheckedAccount<'info>,
+    /// CHECK: Compared against the Arcium fee pool address in the handler
+    pub pool_account: UncheckedAccount<'info>,
+    /// CHECK: Compared against the Arcium clock address in the handler
+    pub clock_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[init_computation_definition_accounts("reveal_answer", payer)]
+#[derive(Accounts)]
+pub struct InitRevealAnswerCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[init_computation_definition_accounts("encrypt_quiz", payer)]
+#[derive(Accounts)]
+pub struct InitEncryptQuizCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[init_computation_definition_accounts("decrypt_quiz", payer)]
+#[derive(Accounts)]
+pub struct InitDecryptQuizCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    /// Can't check it here as it's not initialized yet.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(new_winner: Pubkey)]
+pub struct ReassignWinner<'info> {
+    #[account(
+        mut,
+        has_one = authority @ QuizError::Unauthorized,
+        constraint = quiz_set.winner.is_some() @ QuizError::QuizNotCompleted,
+        // Shares are cut from the current winner list, so it is frozen once any is paid
+        constraint = !quiz_set.has_paid_shares() @ QuizError::RewardAlreadyClaimed
+    )]
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    #[account(
+        seeds = [b"progress", new_winner.as_ref(), quiz_set.key().as_ref()],
+        bump
+    )]
+    pub new_winner_progress: Account<'info, PlayerProgress>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RefundUnclaimedReward<'info> {
+    #[account(
+        mut,
+        seeds = [b"quiz_set", quiz_set.authority.as_ref(), &[quiz_set.unique_id]],
+        bump,
+        has_one = authority @ QuizError::Unauthorized,
+        // Never while a winner could still be owed their share. Proportional and
+        // per-question quizzes have no winner share, so whatever is left after
+        // the deadline goes back.
+        constraint = quiz_set.winner.is_none() || !quiz_set.pays_winner_share() @ QuizError::WinnerAlreadySet
+    )]
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", quiz_set.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is a vault account for storing SOL rewards
+    pub vault: UncheckedAccount<'info>,
+    
+    // Topic escrow statistics, booked on every escrow movement
+    #[account(
+        mut,
+        seeds = [b"reward_stats", quiz_set.topic.as_ref()],
+        bump
+    )]
+    pub reward_stats: Account<'info, TopicRewardStats>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    // Token-reward quizzes only: the reward mint and the vault's and authority's token accounts
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub authority_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    // Author's active quiz count, released when the reward is settled (created
+    // here for authors whose quizzes all predate quotas)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AuthorQuota::LEN,
+        seeds = [b"author", quiz_set.authority.as_ref()],
+        bump
+    )]
+    pub author_quota: Account<'info, AuthorQuota>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimUnclaimedWinnerReward<'info> {
+    #[account(
+        mut,
+        seeds = [b"quiz_set", quiz_set.authority.as_ref(), &[quiz_set.unique_id]],
+        bump,
+        has_one = authority @ QuizError::Unauthorized,
+        constraint = quiz_set.winner.is_some() @ QuizError::QuizNotCompleted
+    )]
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", quiz_set.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is a vault account for storing SOL rewards
+    pub vault: UncheckedAccount<'info>,
+    
+    // Topic escrow statistics, booked on every escrow movement
+    #[account(
+        mut,
+        seeds = [b"reward_stats", quiz_set.topic.as_ref()],
+        bump
+    )]
+    pub reward_stats: Account<'info, TopicRewardStats>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    // Token-reward quizzes only: the reward mint and the vault's and authority's token accounts
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub authority_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    // Author's active quiz count, released when the reward is settled (created
+    // here for authors whose quizzes all predate quotas)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AuthorQuota::LEN,
+        seeds = [b"author", quiz_set.authority.as_ref()],
+        bump
+    )]
+    pub author_quota: Account<'info, AuthorQuota>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseQuizSet<'info> {
+    #[account(
+        mut,
+        seeds = [b"quiz_set", quiz_set.authority.as_ref(), &[quiz_set.unique_id]],
+        bump,
+        has_one = authority @ QuizError::Unauthorized,
+        close = authority
+    )]
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", quiz_set.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is a vault account for storing SOL rewards
+    pub vault: UncheckedAccount<'info>,
+    
+    // Topic escrow statistics, booked on every escrow movement
+    #[account(
+        mut,
+        seeds = [b"reward_stats", quiz_set.topic.as_ref()],
+        bump
+    )]
+    pub reward_stats: Account<'info, TopicRewardStats>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    // Records the quiz's unique_id as retired so the address isn't reused
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AuthorQuota::LEN,
+        seeds = [b"author", quiz_set.authority.as_ref()],
+        bump
+    )]
+    pub author_quota: Account<'info, AuthorQuota>,
+    
+    // Token-reward quizzes only: the reward mint, the vault's token account
+    // (closed to the authority) and the authority's token account
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub authority_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct KeeperSettle<'info> {
+    #[account(
+        mut,
+        seeds = [b"quiz_set", quiz_set.authority.as_ref(), &[quiz_set.unique_id]],
+        bump,
+        has_one = authority @ QuizError::Unauthorized,
+        close = authority
+    )]
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", quiz_set.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is a vault account for storing SOL rewards
+    pub vault: UncheckedAccount<'info>,
+    
+    // Topic escrow statistics, booked on every escrow movement
+    #[account(
+        mut,
+        seeds = [b"reward_stats", quiz_set.topic.as_ref()],
+        bump
+    )]
+    pub reward_stats: Account<'info, TopicRewardStats>,
+    
+    #[account(mut)]
+    /// CHECK: Receives the refund and the quiz set's rent; matched by has_one
+    pub authority: UncheckedAccount<'info>,
+    
+    // Anyone may settle; the keeper is paid for doing so
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+    
+    // Token-reward quizzes only: the reward mint, the vault's token account
+    // (closed to the authority) and the keeper's and authority's token accounts
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub keeper_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub authority_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    // Author's active quiz count, released when the reward is settled; also
+    // records the quiz's unique_id as retired so the address isn't reused
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = AuthorQuota::LEN,
+        seeds = [b"author", quiz_set.authority.as_ref()],
+        bump
+    )]
+    pub author_quota: Account<'info, AuthorQuota>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimReward<'info> {
+    #[account(
+        mut,
+        seeds = [b"quiz_set", quiz_set.authority.as_ref(), &[quiz_set.unique_id]],
+        bump,
+        constraint = quiz_set.is_initialized @ QuizError::QuizNotInitialized,
+        constraint = Clock::get()?.unix_timestamp >= quiz_set.announce_after @ QuizError::WinnerNotYetAnnounced
+    )]
+    pub quiz_set: Account<'info, QuizSet>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", quiz_set.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is a vault account for storing SOL rewards
+    pub vault: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+    
+    // Claimer's token account for the quiz's gate mint, required only on gated quizzes
+    pub gate_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    // Topic escrow statistics, booked on every escrow movement
+    #[account(
+        mut,
+        seeds = [b"reward_stats", quiz_set.topic.as_ref()],
+        bump
+    )]
+    pub reward_stats: Account<'info, TopicRewardStats>,
+    
+    #[account(mut)]
+    /// CHECK: Receives the payout; must match the `destination` argument when one is given
+    pub destination_account: Option<UncheckedAccount<'info>>,
+    
+    #[account(
+        init_if_needed,
+        payer = claimer,
+        space = GlobalUserStats::LEN,
+        seeds = [b"global", claimer.key().as_ref()],
         bump
     )]
-    pub user_score: Account<'info, UserScore>,
+    pub global_stats: Account<'info, GlobalUserStats>,
+    
+    #[account(
+        init_if_needed,
+        payer = claimer,
+        space = ClaimReceipt::LEN,
+        seeds = [b"claim", quiz_set.key().as_ref(), claimer.key().as_ref()],
+        bump
+    )]
+    pub claim_receipt: Account<'info, ClaimReceipt>,
+    
+    // Claimer's progress, required on per-question reward, speed-bracket and
+    // claim-proof quizzes
+    #[account(
+        mut,
+        seeds = [b"progress", claimer.key().as_ref(), quiz_set.key().as_ref()],
+        bump
+    )]
+    pub player_progress: Option<Account<'info, PlayerProgress>>,
+    
+    // Claim-proof quizzes only: the claimer's registered proof key
+    pub proof_signer: Option<Signer<'info>>,
+    
+    // Proportional quizzes only: the claimer's recorded completion
+    pub quiz_history: Option<Account<'info, QuizHistory>>,
     
+    // Token-reward quizzes only: the reward mint, the vault's token account and
+    // the payout wallet's token account
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub claimer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    // Author's active quiz count, released when the reward is settled (created
+    // here for authors whose quizzes all predate quotas)
+    #[account(
+        init_if_needed,
+        payer = claimer,
+        space = AuthorQuota::LEN,
+        seeds = [b"author", quiz_set.authority.as_ref()],
+        bump
+    )]
+    pub author_quota: Account<'info, AuthorQuota>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// ===== QUIZ SERIES ACCOUNTS =====
+
+#[derive(Accounts)]
+#[instruction(series_id: u8)]
+pub struct CreateSeries<'info> {
     #[account(
         init,
-        payer = user,
-        space = QuizHistory::LEN,
-        seeds = [
-            b"quiz_history", 
-            user.key().as_ref(), 
-            quiz_set.key().as_ref(),
-            &timestamp_seed.to_le_bytes()
-        ],
+        payer = authority,
+        space = QuizSeries::LEN,
+        seeds = [b"series", authority.key().as_ref(), &[series_id]],
         bump
     )]
-    pub quiz_history: Account<'info, QuizHistory>,
+    pub series: Account<'info, QuizSeries>,
     
     #[account(
-        seeds = [b"quiz_set", quiz_set.authority.as_ref(), &[quiz_set.unique_id]],
+        init,
+        payer = authority,
+        space = 0,
+        seeds = [b"series_vault", series.key().as_ref()],
         bump
     )]
+    /// CHECK: This is a vault account for storing the pooled series reward
+    pub series_vault: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddQuizToSeries<'info> {
+    #[account(
+        mut,
+        seeds = [b"series", series.authority.as_ref(), &[series.series_id]],
+        bump,
+        has_one = authority @ QuizError::Unauthorized
+    )]
+    pub series: Account<'info, QuizSeries>,
+    
+    #[account(has_one = authority @ QuizError::Unauthorized)]
     pub quiz_set: Account<'info, QuizSet>,
     
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimSeriesReward<'info> {
+    #[account(
+        mut,
+        seeds = [b"series", series.authority.as_ref(), &[series.series_id]],
+        bump,
+        constraint = !series.is_reward_claimed @ QuizError::RewardAlreadyClaimed
+    )]
+    pub series: Account<'info, QuizSeries>,
+    
     #[account(
-        seeds = [b"topic", topic.name.as_bytes()],
+        mut,
+        seeds = [b"series_vault", series.key().as_ref()],
         bump
     )]
+    /// CHECK: This is a vault account for storing the pooled series reward
+    pub series_vault: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// ===== READ-ONLY ACCOUNTS =====
+
+#[derive(Accounts)]
+pub struct GetTopicQuizSummaries<'info> {
     pub topic: Account<'info, Topic>,
+}
+
+#[derive(Accounts)]
+pub struct GetLiveStandings<'info> {
+    pub quiz_set: Account<'info, QuizSet>,
+}
+
+#[derive(Accounts)]
+pub struct GetQuestionMetadata<'info> {
+    pub quiz_set: Account<'info, QuizSet>,
     
+    #[account(
+        constraint = question_block.quiz_set == quiz_set.key() @ QuizError::InvalidQuestionBlock
+    )]
+    pub question_block: Account<'info, QuestionBlock>,
+}
+
+// ===== OPERATOR ACCOUNTS =====
+
+#[derive(Accounts)]
+pub struct OperatorTopupFees<'info> {
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub operator: Signer<'info>,
+    
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    
+    #[account(
+        init_if_needed,
+        payer = operator,
+        space = FeeTopupLedger::LEN,
+        seeds = [b"fee_topup", operator.key().as_ref()],
+        bump
+    )]
+    pub fee_ledger: Account<'info, FeeTopupLedger>,
     
     pub system_program: Program<'info, System>,
 }
 
-#[derive(Accounts)]
-pub struct GetUserGlobalStats<'info> {
-    /// CHECK: This is just for IDL generation, no constraints needed
-    pub user: AccountInfo<'info>,
+// ===== DATA STRUCTURES =====
+
+#[account]
+pub struct Topic {
+    pub owner: Pubkey,                // Topic creator
+    pub name: String,                 // Topic name (unique)
+    pub created_at: i64,              // Creation timestamp
+    pub total_quizzes: u32,           // Total quizzes in this topic
+    pub total_participants: u32,      // Total unique participants
+    pub is_active: bool,              // Whether topic is active
+    pub min_reward_amount: u64,       // Minimum reward for valid quiz (0.01 SOL = 10M lamports)
+    pub min_question_count: u8,       // Minimum questions for valid quiz (3)
+    pub allowed_reward_mint: Option<Pubkey>, // Required reward mint (None = any/SOL)
+    pub require_distinct_answers: bool, // Reject quizzes whose correct answers are all identical
+    pub allow_authority_play: bool,   // Quiz authorities may answer and win their own quizzes
+    pub leaderboard_min_score: u32,   // Minimum topic score (quizzes won) to appear on the leaderboard (0 = none)
+}
+
+impl Topic {
+    pub const LEN: usize = 8 + 32 + 4 + MAX_NAME_LEN + 8 + 4 + 4 + 1 + 8 + 1 + 33 + 1 + 1 + 4; // ~209 bytes
+}
+
+// Program-wide settings, one per deployment
+#[account]
+pub struct ProgramConfig {
+    pub admin: Pubkey,                // Upgrade authority at initialization; signs config changes
+    pub max_active_quizzes: u16,      // Cap on each author's unsettled quizzes (0 = none)
+}
+
+impl ProgramConfig {
+    pub const LEN: usize = 8 + 32 + 2;
+}
+
+// Quizzes an author has created whose reward hasn't been settled yet
+#[account]
+pub struct AuthorQuota {
+    pub author: Pubkey,               // Quiz set authority
+    pub active_quiz_count: u16,       // Quizzes still holding an active slot
+    pub retired_ids: [u8; 32],        // Bitmap of unique_ids whose quiz set was closed; never reused
+}
+
+impl AuthorQuota {
+    pub const LEN: usize = 8 + 32 + 2 + 32;
+
+    // A closed quiz set's address can't be taken again, or its leftover
+    // receipts, progress and submissions would carry over to the new quiz
+    pub fn is_retired(&self, unique_id: u8) -> bool {
+        self.retired_ids[(unique_id / 8) as usize] & (1 << (unique_id % 8)) != 0
+    }
+
+    pub fn retire(&mut self, unique_id: u8) {
+        self.retired_ids[(unique_id / 8) as usize] |= 1 << (unique_id % 8);
+    }
+}
+
+#[account]
+pub struct UserScore {
+    pub user: Pubkey,                 // User's public key
+    pub topic: Pubkey,                // Topic public key
+    pub score: u32,                   // Number of quizzes won
+    pub total_completed: u32,         // Total quizzes completed (win + lose)
+    pub last_activity: i64,           // Last quiz completion time
+    pub total_rewards: u64,           // Total SOL rewards earned
+    pub best_score: u32,              // Best single-quiz weighted score in this topic
+    pub best_score_quiz: Pubkey,      // Quiz set where the best score was achieved
+    pub link_nonce: u32,              // Ephemeral links merged so far; signed into each link message
+}
+
+impl UserScore {
+    pub const LEN: usize = 8 + 32 + 32 + 4 + 4 + 8 + 8 + 4 + 32 + 4; // ~136 bytes
+}
+
+// Permanent record of a payout; blocks a second claim even if the quiz set's
+// own claimed flags are ever reset (e.g. the quiz is closed and recreated)
+#[account]
+pub struct ClaimReceipt {
+    pub quiz_set: Pubkey,             // Quiz set the reward came from
+    pub claimer: Pubkey,              // Winner, co-winner, runner-up or per-question earner who claimed
+    pub amount: u64,                  // Reward paid out (running total for per-question claims)
+    pub claimed_at: i64,              // Latest claim time
+}
+
+impl ClaimReceipt {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8;
+}
+
+// Per-user totals across every topic
+#[account]
+pub struct GlobalUserStats {
+    pub user: Pubkey,                 // User's public key
+    pub total_rewards: u64,           // Lamports claimed across all topics
+    pub total_wins: u32,              // Quizzes won across all topics
+    pub total_completions: u32,       // Quizzes completed across all topics
+    pub last_activity: i64,           // Last recorded completion time
+}
+
+impl GlobalUserStats {
+    pub const LEN: usize = 8 + 32 + 8 + 4 + 4 + 8;
+}
+
+#[account]
+pub struct QuizHistory {
+    pub user: Pubkey,                 // User who completed
+    pub quiz_set: Pubkey,             // Quiz set completed
+    pub topic: Pubkey,                // Topic of the quiz
+    pub completed_at: i64,            // Completion timestamp
+    pub score: u8,                    // Questions answered correctly
+    pub total_questions: u8,          // Total questions in quiz
+    pub is_winner: bool,              // Whether user won (100% correct)
+    pub reward_claimed: u64,          // Reward amount claimed (0 if lost)
+}
+
+impl QuizHistory {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 1 + 1 + 1 + 8; // ~123 bytes
+}
+
+#[account]
+pub struct QuizSet {
+    pub authority: Pubkey,
+    pub topic: Pubkey,                // Associated topic
+    pub name: String,
+    pub question_count: u8,
+    pub created_at: i64,
+    pub is_initialized: bool,
+    pub reward_amount: u64,           // SOL amount in lamports
+    pub is_reward_claimed: bool,      // Whether reward has been claimed
+    pub winner: Option<Pubkey>,       // Winner's public key
+    pub correct_answers_count: u8,    // Count of correct answers
+    pub unique_id: u8,                // Unique ID for PDA
+    pub won_at: i64,                  // When the winner was determined (0 if none)
+    pub announce_after: i64,          // Winner/claim held back until this time (0 = immediately)
+    pub is_winner_announced: bool,    // Whether QuizCompleted/RewardClaimable were emitted
+    pub buffer_size: u8,              // Answer buffer size in bytes (selects the validation circuit)
+    pub content_hash: [u8; 32],       // Commitment over all question blocks, checked at claim
+    pub stats_public: bool,           // Whether events publish per-question correctness stats (always readable on-chain)
+    pub runner_up_amount: u64,        // Portion of reward_amount reserved for the runner-up
+    pub runner_up: Option<Pubkey>,    // Second player to complete the quiz
+    pub is_runner_up_claimed: bool,   // Whether the runner-up reward has been claimed
+    pub sudden_death: bool,           // First wrong answer eliminates the player
+    pub claim_window_seconds: i64,    // Time the winner has to claim (0 = no deadline)
+    pub claim_grace_seconds: i64,     // Extra time past the window for congestion
+    pub won_slot: u64,                // Slot in which the winner finished
+    pub co_winners: Vec<Pubkey>,      // Players who finished in the same slot as the winner
+    pub co_winners_claimed: u8,       // Bitmask of co-winners who have claimed
+    pub total_weight: u16,            // Target score across all questions (0 = unweighted)
+    pub assigned_weight: u16,         // Sum of weights of the blocks added so far
+    pub gate_mint: Option<Pubkey>,    // Token players must hold to answer or claim (None = open)
+    pub is_practice: bool,            // Replayable; players may reset their progress
+    pub answer_fee: u64,              // Lamports charged per answer, paid to the fee pool
+    pub end_on_wrong: bool,           // First wrong answer ends the player's run
+    pub bonus_count: u8,              // Question blocks marked as bonus (not required to win)
+    pub retry_window_seconds: i64,    // Penalty-free window for retrying a missed question (0 = off)
+    pub rotation_target_key: [u8; 32], // MXE key blocks are being moved to
+    pub rotation_in_progress: bool,   // Answers are paused until every block is rotated
+    pub rotated_count: u8,            // Blocks re-encrypted in the current rotation
+    pub rotation_pending_index: u8,   // Block with a rotation in flight (0 = none)
+    pub rotation_pending_nonce: u128, // Nonce the in-flight block is re-encrypted under
+    pub reveal_on_wrong: bool,        // Reveal the correct answer after a miss (practice only)
+    pub keeper_delay_seconds: i64,    // Time after the claim deadline before keepers may settle (0 = never)
+    pub funded_amount: u64,           // Lamports deposited into the vault towards reward_amount
+    pub is_funded: bool,              // Full reward deposited; answers and claims are open
+    pub duration_seconds: i64,        // Time each player has from their first submission (0 = untimed)
+    pub first_answer_hash: [u8; 32],  // Hash of the first block's decrypted answer
+    pub has_distinct_answers: bool,   // Some block's answer differs from the first
+    pub password_key: Option<Pubkey>, // Key derived from the join password (None = open)
+    pub per_question_reward: u64,     // Reward credited per correct answer instead of a completion prize (0 = off)
+    pub reward_mint: Option<Pubkey>,  // SPL mint the reward is paid in (None = native SOL)
+    pub deadline: i64,                // Claims close and an unwon reward becomes refundable after this (0 = never)
+    pub is_active: bool,              // Holds one of the author's active quiz slots until settled
+    pub result_authority: Pubkey,     // Only signer allowed to call set_winner_for_user
+    pub is_refunded: bool,            // Unwon reward returned via refund_unclaimed_reward
+    pub max_winners: u8,              // First finishers sharing the reward (winner plus co_winners)
+    pub max_attempts: u8,             // Answers a player may submit per question (0 = unlimited)
+    pub attempt_cooldown_seconds: i64, // Minimum time between a player's submissions (0 = none)
+    pub result_grace_seconds: i64,    // Time after the win in-flight answer results still count
+    pub category_totals: [u8; MAX_CATEGORIES], // Questions per category
+    pub allow_authority_play: bool,   // Copied from the topic; authority may answer and win
+    pub reward_brackets: Vec<RewardBracket>, // Completion-time scaling of winner/co-winner shares only (max MAX_REWARD_BRACKETS, empty = off)
+    pub min_participants: u16,        // Distinct players needed before rewards can be claimed (0 = none)
+    pub participant_count: u16,       // Distinct players who have submitted an answer
+    pub require_claim_proof: bool,    // Claims must be co-signed by the player's registered proof key
+    pub reward_mode: RewardMode,      // Winner takes all, or every claimant paid by score
+    pub proportional_paid: u64,       // Paid out so far under RewardMode::Proportional
+    pub settlement_finalized: bool,   // QuizSettlement has been emitted
+    pub commit_deadline: i64,         // Commits close and reveals open after this (0 = no commit phase)
+    pub per_question_credited: u64,   // Per-question rewards credited to players so far (capped at reward_amount)
+    pub per_question_paid: u64,       // Per-question rewards claimed so far
+}
+
+impl QuizSet {
+    pub const LEN: usize = 8 + 32 + 32 + 4 + MAX_NAME_LEN + 1 + 8 + 1 + 8 + 1 + 33 + 1 + 1
+        + 8 + 8 + 1 // won_at, announce_after, is_winner_announced
+        + 1 // buffer_size
+        + 32 // content_hash
+        + 1 // stats_public
+        + 8 + 33 + 1 // runner_up_amount, runner_up, is_runner_up_claimed
+        + 1 // sudden_death
+        + 8 + 8 // claim_window_seconds, claim_grace_seconds
+        + 8 + 4 + 32 * MAX_CO_WINNERS + 1 // won_slot, co_winners, co_winners_claimed
+        + 2 + 2 // total_weight, assigned_weight
+        + 33 // gate_mint
+        + 1 // is_practice
+        + 8 // answer_fee
+        + 1 // end_on_wrong
+        + 1 // bonus_count
+        + 8 // retry_window_seconds
+        + 32 + 1 + 1 + 1 + 16 // rotation_target_key, rotation_in_progress, rotated_count, rotation_pending_index, rotation_pending_nonce
+        + 1 // reveal_on_wrong
+        + 8 // keeper_delay_seconds
+        + 8 + 1 // funded_amount, is_funded
+        + 8 // duration_seconds
+        + 32 + 1 // first_answer_hash, has_distinct_answers
+        + 33 // password_key
+        + 8 // per_question_reward
+        + 33 // reward_mint
+        + 8 // deadline
+        + 1 // is_active
+        + 32 // result_authority
+        + 1 // is_refunded
+        + 1 // max_winners
+        + 1 + 8 // max_attempts, attempt_cooldown_seconds
+        + 8 // result_grace_seconds
+        + MAX_CATEGORIES // category_totals
+        + 1 // allow_authority_play
+        + 4 + (4 + 2) * MAX_REWARD_BRACKETS // reward_brackets
+        + 2 + 2 // min_participants, participant_count
+        + 1 // require_claim_proof
+        + 1 + 8 // reward_mode, proportional_paid
+        + 1 // settlement_finalized
+        + 8 // commit_deadline
+        + 8 + 8; // per_question_credited, per_question_paid
+
+    // Whether finishing first earns a winner share; proportional and per-question
+    // quizzes pay every claimant from the pool instead
+    pub fn pays_winner_share(&self) -> bool {
+        self.reward_mode == RewardMode::WinnerTakesAll && self.per_question_reward == 0
+    }
+
+    // Questions a player must answer correctly to finish; bonus questions are optional
+    pub fn required_question_count(&self) -> u8 {
+        self.question_count - self.bonus_count
+    }
+
+    // Equal share of the winner pool; the primary winner also takes the rounding dust.
+    // Multi-winner quizzes divide by max_winners up front, so an early claim
+    // can't take the shares of winners still to finish; unfilled shares stay
+    // in the vault until the quiz is closed.
+    pub fn winner_share(&self, is_primary: bool) -> u64 {
+        let pool = self.reward_amount - self.runner_up_amount;
+        let winners = if self.max_winners > 1 {
+            self.max_winners as u64
+        } else {
+            1 + self.co_winners.len() as u64
+        };
+        if is_primary {
+            pool / winners + pool % winners
+        } else {
+            pool / winners
+        }
+    }
+
+    // Whether the player holds the winner's or a co-winner's share
+    pub fn is_winner(&self, player: &Pubkey) -> bool {
+        self.winner.as_ref() == Some(player) || self.co_winners.contains(player)
+    }
+
+    // Share of a winner's payout (basis points) for completing in
+    // `completion_seconds`: the first bracket that covers it, else nothing
+    pub fn bracket_bps(&self, completion_seconds: i64) -> u16 {
+        self.reward_brackets
+            .iter()
+            .find(|bracket| completion_seconds <= bracket.max_seconds as i64)
+            .map_or(0, |bracket| bracket.bps)
+    }
+
+    // Whether every share has been paid out or the reward refunded
+    pub fn is_settled(&self) -> bool {
+        let co_winners_settled = self.co_winners_claimed.count_ones() as usize == self.co_winners.len();
+        let runner_up_settled = self.runner_up.is_none() || self.is_runner_up_claimed;
+        let pool_exhausted = (self.reward_mode == RewardMode::Proportional && self.proportional_paid >= self.reward_amount)
+            || (self.per_question_reward > 0 && self.per_question_paid >= self.reward_amount);
+        self.is_refunded || pool_exhausted || (self.is_reward_claimed && co_winners_settled && runner_up_settled)
+    }
+
+    // Whether any share of the reward has already been paid out
+    pub fn has_paid_shares(&self) -> bool {
+        self.is_reward_claimed
+            || self.co_winners_claimed != 0
+            || self.is_runner_up_claimed
+            || self.proportional_paid > 0
+            || self.per_question_paid > 0
+    }
+
+    // Whether a finisher in `slot` can still join the winners: any finisher up to
+    // max_winners on multi-winner quizzes, whose shares are fixed, and only an
+    // exact tie in the winning slot otherwise. A tie splits the pool by the
+    // list's length, so that list closes at the first claim.
+    pub fn has_open_winner_slot(&self, slot: u64) -> bool {
+        if self.max_winners > 1 {
+            (1 + self.co_winners.len()) < self.max_winners as usize
+        } else {
+            slot == self.won_slot
+                && self.co_winners.len() < MAX_CO_WINNERS
+                && !self.is_reward_claimed
+                && self.co_winners_claimed == 0
+        }
+    }
+
+    // Whether the winner could claim right now
+    pub fn is_claimable(&self, now: i64) -> Result<bool> {
+        Ok(self.winner.is_some()
+            && !self.is_reward_claimed
+            && now >= self.announce_after
+            && self.claim_deadline()?.map_or(true, |deadline| now <= deadline))
+    }
+
+    // Last moment a player who started at `started_at` may answer (None = untimed)
+    pub fn run_deadline(&self, started_at: i64) -> Result<Option<i64>> {
+        if self.duration_seconds == 0 {
+            return Ok(None);
+        }
+        let deadline = started_at
+            .checked_add(self.duration_seconds)
+            .ok_or(QuizError::DeadlineOverflow)?;
+        Ok(Some(deadline))
+    }
+
+    // Last moment the winner can claim, counting from when the win became public
+    pub fn claim_deadline(&self) -> Result<Option<i64>> {
+        if self.claim_window_seconds == 0 || self.winner.is_none() {
+            return Ok(None);
+        }
+        let claim_opens = std::cmp::max(self.won_at, self.announce_after);
+        let deadline = claim_opens
+            .checked_add(self.claim_window_seconds)
+            .and_then(|deadline| deadline.checked_add(self.claim_grace_seconds))
+            .ok_or(QuizError::DeadlineOverflow)?;
+        Ok(Some(deadline))
+    }
+}
+
+#[account]
+pub struct QuestionBlock {
+    pub quiz_set: Pubkey,
+    pub question_index: u32,
+    pub encrypted_x_coordinate: [u8; 64],
+    pub encrypted_y_coordinate: [u8; 64],
+    pub arcium_pubkey: [u8; 32],
+    pub nonce: u128,
+    pub created_at: i64,
+    // Fields below were appended after the original layout; see migrate_question_block
+    pub version: u8,
+    pub attempt_count: u32,           // Validations processed for this question (v2)
+    pub correct_count: u32,           // Correct validations for this question (v2)
+    pub weight: u16,                  // Points for a correct answer in weighted quizzes (v3)
+    pub valid_until: i64,             // Answers rejected after this time (0 = no expiry) (v4)
+    pub is_bonus: bool,               // Adds points but isn't required to win (v5)
+    pub hints: [[u8; HINT_SIZE]; MAX_HINTS], // Encrypted progressive hints (v6)
+    pub hint_count: u8,               // Hints set by the author (v6)
+    pub hint_nonce: u128,             // Nonce the hints are encrypted under (v6)
+    pub alt_answers: [[u8; 64]; MAX_ALT_ANSWERS], // Other acceptable encrypted answers (v7)
+    pub alt_answer_count: u8,         // Acceptable answers in use besides the primary (v7)
+    pub category: u8,                 // Subtopic the question belongs to, < MAX_CATEGORIES (v8)
+}
+
+impl QuestionBlock {
+    pub const LEN: usize = 8 + 32 + 4 + 64 + 64 + 32 + 16 + 8
+        + 1 // version
+        + 4 + 4 // attempt_count, correct_count
+        + 2 // weight
+        + 8 // valid_until
+        + 1 // is_bonus
+        + HINT_SIZE * MAX_HINTS + 1 + 16 // hints, hint_count, hint_nonce
+        + 64 * MAX_ALT_ANSWERS + 1 // alt_answers, alt_answer_count
+        + 1; // category
+}
+
+#[account]
+pub struct QuizSeries {
+    pub authority: Pubkey,            // Series organizer
+    pub series_id: u8,                // Unique ID for PDA
+    pub quiz_sets: Vec<Pubkey>,       // Member quiz sets (max MAX_SERIES_QUIZZES)
+    pub reward_amount: u64,           // Pooled SOL reward in lamports
+    pub winner: Option<Pubkey>,       // Overall series winner once claimed
+    pub is_reward_claimed: bool,      // Whether the pooled reward has been claimed
+    pub created_at: i64,
+}
+
+impl QuizSeries {
+    pub const LEN: usize = 8 + 32 + 1 + 4 + 32 * MAX_SERIES_QUIZZES + 8 + 33 + 1 + 8;
+}
+
+// Per-computation record of who submitted an answer, closed by the callback
+#[account]
+pub struct AnswerSubmission {
+    pub submitter: Pubkey,            // Player who queued the validation
+    pub quiz_set: Pubkey,             // Quiz set being answered
+    pub question_index: u8,           // Question being answered
+    pub computation_offset: u64,      // Arcium computation offset (PDA seed)
+    pub submitted_at: i64,
+}
+
+impl AnswerSubmission {
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 8 + 8;
+}
+
+// Hidden answer awaiting its reveal, one per player and question
+#[account]
+pub struct AnswerCommitment {
+    pub user: Pubkey,                 // Player who committed
+    pub quiz_set: Pubkey,             // Quiz set being answered
+    pub question_index: u8,           // Question being answered
+    pub commitment: [u8; 32],         // sha256(answer || salt || user)
+    pub committed_slot: u64,          // Reveal must come in a later slot
 }
 
-#[derive(Accounts)]
-pub struct SetWinnerForDevnet<'info> {
-    #[account(
-        mut,
-        has_one = authority
-    )]
-    pub quiz_set: Account<'info, QuizSet>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
+impl AnswerCommitment {
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 32 + 8;
 }
 
-#[derive(Accounts)]
-pub struct SetWinnerForUser<'info> {
-    #[account(
-        mut,
-        constraint = quiz_set.is_initialized @ QuizError::QuizNotInitialized,
-        constraint = quiz_set.winner.is_none() @ QuizError::WinnerAlreadySet
-    )]
-    pub quiz_set: Account<'info, QuizSet>,
-    
-    #[account(mut)]
-    pub setter: Signer<'info>, // Anyone can set winner, not just authority
-    
-    pub system_program: Program<'info, System>,
+// Per-player, per-quiz answering state
+#[account]
+pub struct PlayerProgress {
+    pub player: Pubkey,               // Player answering
+    pub quiz_set: Pubkey,             // Quiz set being played
+    pub correct_count: u8,            // Correct answers so far
+    pub wrong_count: u8,              // Wrong answers so far
+    pub eliminated: bool,             // Knocked out (sudden death)
+    pub started_at: i64,              // First submission time
+    pub last_activity: i64,           // Last processed result
+    pub score: u32,                   // Weighted score (one point per answer if unweighted)
+    pub submission_count: u32,        // Answers queued so far; feeds derive_computation_offset
+    pub finished: bool,               // Run ended by a wrong answer (end_on_wrong)
+    pub answered_mask: u64,           // Bit (index - 1) set per required question answered correctly
+    pub last_wrong_at: i64,           // Time of the last wrong result, for the retry window
+    pub last_wrong_question: u8,      // Question of the last wrong result (0 = none)
+    pub hints_used: [u8; MAX_QUESTION_COUNT as usize],   // Hints revealed per question
+    pub hint_penalty: u32,            // Points deducted for hints so far
+    pub joined: bool,                 // Proved the password of a protected quiz
+    pub scored_mask: u64,             // Bit (index - 1) set per question scored, bonus included
+    pub claimable: u64,               // Per-question rewards earned and not yet claimed
+    pub wrong_mask: u64,              // Bit (index - 1) set per question answered wrong at least once
+    pub attempts: [u8; MAX_QUESTION_COUNT as usize], // Answers submitted per question, capped by max_attempts
+    pub last_attempt: i64,            // Time of the last submission, for the attempt cooldown
+    pub category_correct: [u8; MAX_CATEGORIES], // Questions scored per category
+    pub prepaid_answers: u8,          // Answer fees paid ahead via prefund_next
+    pub completed_at: i64,            // Time every required question was answered correctly (0 = not yet)
+    pub is_participant: bool,         // Counted in the quiz's participant_count
+    pub proof_key: Pubkey,            // Key that must co-sign claim-proof claims (default = none)
+    pub answers_revealed: bool,       // Saw a correct answer via reveal_my_mistakes; no more answers or resets
+    pub hint_debt: u32,               // Hint penalty points not yet deducted, taken from the next points scored
+    pub pending_knockout: bool,       // Missed under sudden death / end-on-wrong, retry window still open
+    pub completion_recorded: bool,    // record_quiz_completion already ran for this quiz set; survives resets
 }
 
-#[queue_computation_accounts("validate_answer", payer)]
-#[derive(Accounts)]
-#[instruction(computation_offset: u64)]
-pub struct ValidateAnswerOnchain<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
+impl PlayerProgress {
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 1 + 1 + 8 + 8 + 4 + 4 + 1 + 8 + 8 + 1
+        + MAX_QUESTION_COUNT as usize + 4 // hints_used, hint_penalty
+        + 1 // joined
+        + 8 // scored_mask
+        + 8 // claimable
+        + 8 // wrong_mask
+        + MAX_QUESTION_COUNT as usize + 8 // attempts, last_attempt
+        + MAX_CATEGORIES // category_correct
+        + 1 // prepaid_answers
+        + 8 // completed_at
+        + 1 // is_participant
+        + 32 // proof_key
+        + 1 // answers_revealed
+        + 4 // hint_debt
+        + 1 // pending_knockout
+        + 1; // completion_recorded
+}
 
-    #[account(
-        init_if_needed,
-        space = 9,
-        payer = payer,
-        seeds = [&SIGN_PDA_SEED],
-        bump,
-        address = derive_sign_pda!(),
-    )]
-    pub sign_pda_account: Account<'info, SignerAccount>,
-    
-    pub question_block: Account<'info, QuestionBlock>,
-    pub quiz_set: Account<'info, QuizSet>,
-    
-    #[account(
-        address = derive_mxe_pda!()
-    )]
-    pub mxe_account: Account<'info, MXEAccount>,
-    
-    #[account(
-        mut,
-        address = derive_mempool_pda!()
-    )]
-    /// CHECK: This is a mempool account managed by Arcium
-    pub mempool_account: UncheckedAccount<'info>,
-    
-    #[account(
-        mut,
-        address = derive_execpool_pda!()
-    )]
-    /// CHECK: This is an execution pool account managed by Arcium
-    pub executing_pool: UncheckedAccount<'info>,
-    
-    #[account(
-        mut,
-        address = derive_comp_pda!(computation_offset)
-    )]
-    /// CHECK: This is a computation account managed by Arcium
-    pub computation_account: UncheckedAccount<'info>,
-    
-    #[account(
-        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VALIDATE_ANSWER)
-    )]
-    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
-    
-    #[account(
-        mut,
-        address = derive_cluster_pda!(mxe_account)
-    )]
-    pub cluster_account: Account<'info, Cluster>,
-    
-    #[account(
-        mut,
-        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS
-    )]
-    pub pool_account: Account<'info, FeePool>,
-    
-    #[account(
-        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
-    )]
-    pub clock_account: Account<'info, ClockAccount>,
-    
-    pub system_program: Program<'info, System>,
-    pub arcium_program: Program<'info, Arcium>,
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct LeaderboardEntry {
+    pub user: Pubkey,
+    pub score: u32,                   // Quizzes won in the topic
+    pub total_completed: u32,         // Quizzes completed in the topic
+    pub total_rewards: u64,           // SOL rewards earned in the topic
 }
 
-#[callback_accounts("validate_answer")]
-#[derive(Accounts)]
-pub struct ValidateAnswerCallback<'info> {
+#[account]
+pub struct TopicLeaderboard {
+    pub topic: Pubkey,                // Topic this leaderboard ranks
+    pub entries: Vec<LeaderboardEntry>, // Sorted best-first (max MAX_LEADERBOARD_ENTRIES)
+}
 
-    #[account(mut)]
-    pub payer: Signer<'info>,
+impl TopicLeaderboard {
+    pub const LEN: usize = 8 + 32 + 4 + (32 + 4 + 4 + 8) * MAX_LEADERBOARD_ENTRIES;
+}
 
-    pub arcium_program: Program<'info, Arcium>,
-    #[account(
-        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VALIDATE_ANSWER)
-    )]
-    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+// Program-written record of a player's result, for external credentialing
+#[account]
+pub struct Attestation {
+    pub user: Pubkey,
+    pub quiz_set: Pubkey,
+    pub score: u32,                   // Weighted score from PlayerProgress
+    pub correct_count: u8,
+    pub question_count: u8,
+    pub is_winner: bool,              // Winner or co-winner of the quiz
+    pub attested_at: i64,
+    pub digest: [u8; 32],             // attestation_digest over the fields above
+    pub bump: u8,
+}
 
-    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
-    /// CHECK: instructions_sysvar, checked by the account constraint
-    pub instructions_sysvar: AccountInfo<'info>,
-    pub question_block: Account<'info, QuestionBlock>,
-    #[account(mut)]
-    pub quiz_set: Account<'info, QuizSet>,
+impl Attestation {
+    pub const LEN: usize = 8 + 32 + 32 + 4 + 1 + 1 + 1 + 8 + 32 + 1;
 }
 
-#[callback_accounts("encrypt_quiz")]
-#[derive(Accounts)]
-pub struct EncryptQuizCallback<'info> {
-    
-    pub arcium_program: Program<'info, Arcium>,
-    #[account(
-        address = derive_comp_def_pda!(COMP_DEF_OFFSET_ENCRYPT_QUIZ)
-    )]
-    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+// Running total of one operator's fee pool contributions
+#[account]
+pub struct FeeTopupLedger {
+    pub operator: Pubkey,
+    pub total_topped_up: u64,         // Lamports sent to the fee pool
+    pub topup_count: u32,
+    pub last_topup_at: i64,
+}
 
-    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
-    /// CHECK: instructions_sysvar, checked by the account constraint
-    pub instructions_sysvar: AccountInfo<'info>,
-    pub question_block: Account<'info, QuestionBlock>,
+impl FeeTopupLedger {
+    pub const LEN: usize = 8 + 32 + 8 + 4 + 8;
 }
 
-#[callback_accounts("decrypt_quiz")]
-#[derive(Accounts)]
-pub struct DecryptQuizCallback<'info> {
-    
-    pub arcium_program: Program<'info, Arcium>,
-    #[account(
-        address = derive_comp_def_pda!(COMP_DEF_OFFSET_DECRYPT_QUIZ)
-    )]
-    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
-    
-    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
-    /// CHECK: instructions_sysvar, checked by the account constraint
-    pub instructions_sysvar: AccountInfo<'info>,
-    pub question_block: Account<'info, QuestionBlock>,
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct RewardMintTotal {
+    pub mint: Pubkey,                 // NATIVE_SOL_MINT for lamport rewards
+    pub escrowed: u64,                // Currently locked in quiz vaults
+    pub total_deposited: u64,         // Ever deposited into quiz vaults
+}
+
+impl RewardMintTotal {
+    pub const LEN: usize = 32 + 8 + 8;
+}
+
+// Per-currency reward escrow totals for one topic
+#[account]
+pub struct TopicRewardStats {
+    pub topic: Pubkey,
+    pub entries: Vec<RewardMintTotal>, // One per reward currency (max MAX_REWARD_MINTS)
+}
+
+impl TopicRewardStats {
+    pub const LEN: usize = 8 + 32 + 4 + RewardMintTotal::LEN * MAX_REWARD_MINTS;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct StandingEntry {
+    pub player: Pubkey,
+    pub correct_count: u8,
+    pub points: u32,                  // Weighted score, see PlayerProgress.score
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RewardMode {
+    WinnerTakesAll,                   // Only the winner(s) and runner-up are paid
+    Proportional,                     // Each claimant gets reward_amount * score / question_count
+}
+
+// Winners and co-winners completing within max_seconds of their first
+// submission receive bps / 10_000 of their share; no one else is paid by bracket
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct RewardBracket {
+    pub max_seconds: u32,
+    pub bps: u16,
+}
+
+// One question in add_encrypted_question_blocks_batch; fields as in
+// add_encrypted_question_block, with arcium_pubkey shared by the batch
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct QuestionBlockPayload {
+    pub question_index: u8,
+    pub encrypted_x_coordinate: [u8; 64],
+    pub encrypted_y_coordinate: [u8; 64],
+    pub nonce: u128,
+    pub weight: u16,
+    pub is_bonus: bool,
+    pub category: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct QuestionSettlementStats {
+    pub question_index: u32,
+    pub attempt_count: u32,           // Validations processed
+    pub correct_count: u32,           // Correct validations
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct QueuedAnswer {
+    pub question_index: u8,           // Question being answered
+    pub commitment: [u8; 32],         // sha256(answer || salt || player); revealed when processed
+    pub queued_at: i64,               // Time the answer is judged at
+    pub queued_slot: u64,             // Reveal must come in a later slot
+}
+
+// Answer commitments recorded while the Arcium cluster was unavailable, oldest first
+#[account]
+pub struct AnswerQueue {
+    pub player: Pubkey,               // Player who queued the answers
+    pub quiz_set: Pubkey,             // Quiz set being answered
+    pub entries: Vec<QueuedAnswer>,   // Pending answers (max MAX_QUEUED_ANSWERS)
+}
+
+impl AnswerQueue {
+    pub const LEN: usize = 8 + 32 + 32 + 4 + (1 + 32 + 8 + 8) * MAX_QUEUED_ANSWERS;
+}
+
+// ===== EVENTS =====
+
+// ===== TOPIC EVENTS =====
+
+#[event]
+pub struct TopicCreated {
+    pub topic: Pubkey,
+    pub owner: Pubkey,
+    pub name: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TopicOwnershipTransferred {
+    pub topic: Pubkey,
+    pub old_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TopicStatusToggled {
+    pub topic: Pubkey,
+    pub is_active: bool,
+    pub timestamp: i64,
+}
+
+// ===== QUIZ EVENTS =====
+
+#[event]
+pub struct QuizSetCreated {
+    pub quiz_set: Pubkey,
+    pub topic: Pubkey,
+    pub authority: Pubkey,
+    pub name: String,
+    pub question_count: u8,
+    pub reward_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct QuestionBlockAdded {
+    pub question_block: Pubkey,
+    pub quiz_set: Pubkey,
+    pub question_index: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct QuestionBlockMigrated {
+    pub question_block: Pubkey,
+    pub quiz_set: Pubkey,
+    pub old_len: u32,
+    pub new_len: u32,
+    pub old_version: u8,
+    pub new_version: u8,
+}
+
+#[event]
+pub struct QuestionStatsUpdated {
+    pub quiz_set: Pubkey,
+    pub question_index: u32,
+    pub attempt_count: u32,
+    pub correct_count: u32,
+    pub correct_rate_bps: u16,        // correct_count / attempt_count in basis points
+}
+
+#[event]
+pub struct QuizSummary {
+    pub topic: Pubkey,
+    pub quiz_set: Pubkey,
+    pub name: String,
+    pub reward_amount: u64,
+    pub question_count: u8,
+    pub is_initialized: bool,
+    pub has_winner: bool,
+    pub is_claimable: bool,
+}
+
+// Aggregate result of get_topic_quiz_summaries. summary_hash is the sha256 of
+// every quiz's quiz_summary_record, in the order the accounts were passed.
+#[event]
+pub struct BatchCompleted {
+    pub topic: Pubkey,
+    pub count: u8,
+    pub summary_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct QuestionMetadata {
+    pub quiz_set: Pubkey,
+    pub question_block: Pubkey,
+    pub question_index: u32,
+    pub created_at: i64,
+    pub stats_public: bool,
+    pub attempt_count: Option<u32>,
+    pub correct_count: Option<u32>,
+    pub correct_rate_bps: Option<u16>, // correct_count / attempt_count in basis points
 }
 
-#[queue_computation_accounts("encrypt_quiz", payer)]
-#[derive(Accounts)]
-#[instruction(computation_offset: u64)]
-pub struct EncryptQuizData<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
+#[event]
+pub struct AnswerVerifiedEvent {
+    pub question_index: u32,
+    pub is_correct: bool,
+    pub is_stale: bool,               // Arrived after the quiz or the player's run ended; not scored
+    pub computation_offset: u64,      // Matches the AnswerSubmittedEvent of the same answer
+    pub timestamp: i64,
+}
 
-    #[account(
-        init_if_needed,
-        space = 9,
-        payer = payer,
-        seeds = [&SIGN_PDA_SEED],
-        bump,
-        address = derive_sign_pda!(),
-    )]
-    pub sign_pda_account: Account<'info, SignerAccount>,
-    
-    pub question_block: Account<'info, QuestionBlock>,
-    
-    #[account(
-        address = derive_mxe_pda!()
-    )]
-    pub mxe_account: Account<'info, MXEAccount>,
-    
-    #[account(
-        mut,
-        address = derive_mempool_pda!()
-    )]
-    /// CHECK: This is a mempool account managed by Arcium
-    pub mempool_account: UncheckedAccount<'info>,
-    
-    #[account(
-        mut,
-        address = derive_execpool_pda!()
-    )]
-    /// CHECK: This is an execution pool account managed by Arcium
-    pub executing_pool: UncheckedAccount<'info>,
-    
-    #[account(
-        mut,
-        address = derive_comp_pda!(computation_offset)
-    )]
-    /// CHECK: This is a computation account managed by Arcium
-    pub computation_account: UncheckedAccount<'info>,
-    
-    #[account(
-        address = derive_comp_def_pda!(COMP_DEF_OFFSET_ENCRYPT_QUIZ)
-    )]
-    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
-    
-    #[account(
-        mut,
-        address = derive_cluster_pda!(mxe_account)
-    )]
-    pub cluster_account: Account<'info, Cluster>,
-    
-    #[account(
-        mut,
-        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS
-    )]
-    pub pool_account: Account<'info, FeePool>,
-    
-    #[account(
-        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
-    )]
-    pub clock_account: Account<'info, ClockAccount>,
-    
-    pub system_program: Program<'info, System>,
-    pub arcium_program: Program<'info, Arcium>,
+// Emitted when an answer is queued for validation, before its result is known
+#[event]
+pub struct AnswerSubmittedEvent {
+    pub user: Pubkey,
+    pub quiz_set: Pubkey,
+    pub question_index: u8,
+    pub computation_offset: u64,
+    pub timestamp: i64,
 }
 
-#[queue_computation_accounts("decrypt_quiz", payer)]
-#[derive(Accounts)]
-#[instruction(computation_offset: u64)]
-pub struct DecryptQuizData<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
+#[event]
+pub struct QuizDataEncryptedEvent {
+    pub question_block: Pubkey,
+    pub chunk_index: u8,              // Position of this chunk in the question
+    pub chunk_count: u8,              // Chunks the question spans
+    pub encrypted_data: [u8; 64],     // Chunk under the additive nonce scheme, as stored on question blocks
+    pub timestamp: i64,
+}
 
-    #[account(
-        init_if_needed,
-        space = 9,
-        payer = payer,
-        seeds = [&SIGN_PDA_SEED],
-        bump,
-        address = derive_sign_pda!(),
-    )]
-    pub sign_pda_account: Account<'info, SignerAccount>,
-    
-    pub question_block: Account<'info, QuestionBlock>,
-    
-    #[account(
-        address = derive_mxe_pda!()
-    )]
-    pub mxe_account: Account<'info, MXEAccount>,
-    
-    #[account(
-        mut,
-        address = derive_mempool_pda!()
-    )]
-    /// CHECK: This is a mempool account managed by Arcium
-    pub mempool_account: UncheckedAccount<'info>,
-    
-    #[account(
-        mut,
-        address = derive_execpool_pda!()
-    )]
-    /// CHECK: This is an execution pool account managed by Arcium
-    pub executing_pool: UncheckedAccount<'info>,
-    
-    #[account(
-        mut,
-        address = derive_comp_pda!(computation_offset)
-    )]
-    /// CHECK: This is a computation account managed by Arcium
-    pub computation_account: UncheckedAccount<'info>,
-    
-    #[account(
-        address = derive_comp_def_pda!(COMP_DEF_OFFSET_DECRYPT_QUIZ)
-    )]
-    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
-    
-    #[account(
-        mut,
-        address = derive_cluster_pda!(mxe_account)
-    )]
-    pub cluster_account: Account<'info, Cluster>,
-    
-    #[account(
-        mut,
-        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS
-    )]
-    pub pool_account: Account<'info, FeePool>,
-    
-    #[account(
-        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
-    )]
-    pub clock_account: Account<'info, ClockAccount>,
-    
-    pub system_program: Program<'info, System>,
-    pub arcium_program: Program<'info, Arcium>,
+#[event]
+pub struct QuizDataDecryptedEvent {
+    pub decrypted_data: [u8; 64],     // Plaintext chunk, zero-padded
+    pub timestamp: i64,
 }
 
-// ===== ARCIUM INITIALIZATION STRUCTURES =====
+#[event]
+pub struct QuizCompleted {
+    pub quiz_set: Pubkey,
+    pub winner: Pubkey,
+    pub reward_amount: u64,
+    pub timestamp: i64,
+}
 
-#[init_computation_definition_accounts("validate_answer", payer)]
-#[derive(Accounts)]
-pub struct InitValidateAnswerCompDef<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    #[account(
-        mut,
-        address = derive_mxe_pda!()
-    )]
-    pub mxe_account: Box<Account<'info, MXEAccount>>,
-    #[account(mut)]
-    /// CHECK: comp_def_account, checked by arcium program.
-    /// Can't check it here as it's not initialized yet.
-    pub comp_def_account: UncheckedAccount<'info>,
-    pub arcium_program: Program<'info, Arcium>,
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct PlayerEliminatedEvent {
+    pub quiz_set: Pubkey,
+    pub player: Pubkey,
+    pub question_index: u32,
+    pub timestamp: i64,
 }
 
-#[init_computation_definition_accounts("encrypt_quiz", payer)]
-#[derive(Accounts)]
-pub struct InitEncryptQuizCompDef<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    #[account(
-        mut,
-        address = derive_mxe_pda!()
-    )]
-    pub mxe_account: Box<Account<'info, MXEAccount>>,
-    #[account(mut)]
-    /// CHECK: comp_def_account, checked by arcium program.
-    /// Can't check it here as it's not initialized yet.
-    pub comp_def_account: UncheckedAccount<'info>,
-    pub arcium_program: Program<'info, Arcium>,
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct PlayerRunEnded {
+    pub quiz_set: Pubkey,
+    pub player: Pubkey,
+    pub question_index: u32,
+    pub score: u32,
+    pub timestamp: i64,
 }
 
-#[init_computation_definition_accounts("decrypt_quiz", payer)]
-#[derive(Accounts)]
-pub struct InitDecryptQuizCompDef<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    #[account(
-        mut,
-        address = derive_mxe_pda!()
-    )]
-    pub mxe_account: Box<Account<'info, MXEAccount>>,
-    #[account(mut)]
-    /// CHECK: comp_def_account, checked by arcium program.
-    /// Can't check it here as it's not initialized yet.
-    pub comp_def_account: UncheckedAccount<'info>,
-    pub arcium_program: Program<'info, Arcium>,
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct RunnerUpRecorded {
+    pub quiz_set: Pubkey,
+    pub runner_up: Pubkey,
+    pub reward_amount: u64,
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-pub struct ClaimReward<'info> {
-    #[account(
-        mut,
-        seeds = [b"quiz_set", quiz_set.authority.as_ref(), &[quiz_set.unique_id]],
-        bump,
-        constraint = quiz_set.is_initialized @ QuizError::QuizNotInitialized,
-        constraint = quiz_set.winner.is_some() @ QuizError::QuizNotCompleted,
-        constraint = !quiz_set.is_reward_claimed @ QuizError::RewardAlreadyClaimed,
-        constraint = quiz_set.winner.unwrap() == claimer.key() @ QuizError::NotWinner
-    )]
-    pub quiz_set: Account<'info, QuizSet>,
-    
-    #[account(
-        mut,
-        seeds = [b"vault", quiz_set.key().as_ref()],
-        bump
-    )]
-    /// CHECK: This is a vault account for storing SOL rewards
-    pub vault: UncheckedAccount<'info>,
-    
-    #[account(mut)]
-    pub claimer: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct RewardClaimable {
+    pub quiz_set: Pubkey,
+    pub winner: Pubkey,
+    pub reward_amount: u64,
+    pub claimable_from: i64,
+}
+
+#[event]
+pub struct AnswerFeeCollected {
+    pub quiz_set: Pubkey,
+    pub payer: Pubkey,
+    pub amount: u64,                  // Zero when a prepaid credit covered the answer
+    pub used_prepaid: bool,
+    pub prepaid_answers: u8,          // Credits left after this submission
+    pub timestamp: i64,
 }
 
-// ===== DATA STRUCTURES =====
+#[event]
+pub struct TopicRewardTotal {
+    pub topic: Pubkey,
+    pub mint: Pubkey,
+    pub is_native_sol: bool,
+    pub escrowed: u64,
+    pub total_deposited: u64,
+}
 
-#[account]
-pub struct Topic {
-    pub owner: Pubkey,                // Topic creator
-    pub name: String,                 // Topic name (unique)
-    pub created_at: i64,              // Creation timestamp
-    pub total_quizzes: u32,           // Total quizzes in this topic
-    pub total_participants: u32,      // Total unique participants
-    pub is_active: bool,              // Whether topic is active
-    pub min_reward_amount: u64,       // Minimum reward for valid quiz (0.01 SOL = 10M lamports)
-    pub min_question_count: u8,       // Minimum questions for valid quiz (3)
+#[event]
+pub struct PlayerProgressReset {
+    pub quiz_set: Pubkey,
+    pub player: Pubkey,
+    pub timestamp: i64,
 }
 
-impl Topic {
-    pub const LEN: usize = 8 + 32 + 4 + 100 + 8 + 4 + 4 + 1 + 8 + 1; // ~170 bytes
+#[event]
+pub struct FeesToppedUp {
+    pub operator: Pubkey,
+    pub pool_account: Pubkey,
+    pub amount: u64,
+    pub total_topped_up: u64,
+    pub pool_balance: u64,
+    pub timestamp: i64,
 }
 
-#[account]
-pub struct UserScore {
-    pub user: Pubkey,                 // User's public key
-    pub topic: Pubkey,                // Topic public key
-    pub score: u32,                   // Number of quizzes won
-    pub total_completed: u32,         // Total quizzes completed (win + lose)
-    pub last_activity: i64,           // Last quiz completion time
-    pub total_rewards: u64,           // Total SOL rewards earned
+#[event]
+pub struct CompletionAttestation {
+    pub attestation: Pubkey,
+    pub user: Pubkey,
+    pub quiz_set: Pubkey,
+    pub score: u32,
+    pub correct_count: u8,
+    pub question_count: u8,
+    pub is_winner: bool,
+    pub attested_at: i64,
+    pub digest: [u8; 32],
 }
 
-impl UserScore {
-    pub const LEN: usize = 8 + 32 + 32 + 4 + 4 + 8 + 8; // ~96 bytes
+#[event]
+pub struct CorrectAnswerVerified {
+    pub quiz_set: Pubkey,
+    pub question_index: u32,
+    pub ok: bool,
+    pub timestamp: i64,
 }
 
-#[account]
-pub struct QuizHistory {
-    pub user: Pubkey,                 // User who completed
-    pub quiz_set: Pubkey,             // Quiz set completed
-    pub topic: Pubkey,                // Topic of the quiz
-    pub completed_at: i64,            // Completion timestamp
-    pub score: u8,                    // Questions answered correctly
-    pub total_questions: u8,          // Total questions in quiz
-    pub is_winner: bool,              // Whether user won (100% correct)
-    pub reward_claimed: u64,          // Reward amount claimed (0 if lost)
+#[event]
+pub struct CoWinnerRecorded {
+    pub quiz_set: Pubkey,
+    pub co_winner: Pubkey,
+    pub co_winner_count: u8,
+    pub slot: u64,
+    pub timestamp: i64,
 }
 
-impl QuizHistory {
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 1 + 1 + 1 + 8; // ~123 bytes
+#[event]
+pub struct RewardRefunded {
+    pub quiz_set: Pubkey,
+    pub authority: Pubkey,
+    pub reward_mint: Option<Pubkey>,
+    pub amount: u64,
+    pub timestamp: i64,
 }
 
-#[account]
-pub struct QuizSet {
+#[event]
+pub struct WinnerRewardReclaimed {
+    pub quiz_set: Pubkey,
+    pub winner: Pubkey,
     pub authority: Pubkey,
-    pub topic: Pubkey,                // Associated topic
-    pub name: String,
-    pub question_count: u8,
-    pub created_at: i64,
-    pub is_initialized: bool,
-    pub reward_amount: u64,           // SOL amount in lamports
-    pub is_reward_claimed: bool,      // Whether reward has been claimed
-    pub winner: Option<Pubkey>,       // Winner's public key
-    pub correct_answers_count: u8,    // Count of correct answers
-    pub unique_id: u8,                // Unique ID for PDA
+    pub amount: u64,
+    pub timestamp: i64,
 }
 
-impl QuizSet {
-    pub const LEN: usize = 8 + 32 + 32 + 4 + 100 + 1 + 8 + 1 + 8 + 1 + 33 + 1 + 1; // +32 for topic
+#[event]
+pub struct RewardClaimed {
+    pub quiz_set: Pubkey,
+    pub winner: Pubkey,
+    pub reward_amount: u64,
+    pub timestamp: i64,
 }
 
-#[account]
-pub struct QuestionBlock {
+// ===== SCORING EVENTS =====
+
+#[event]
+pub struct QuizCompletionRecorded {
+    pub user: Pubkey,
     pub quiz_set: Pubkey,
-    pub question_index: u32,
-    pub encrypted_x_coordinate: [u8; 64],
-    pub encrypted_y_coordinate: [u8; 64],
-    pub arcium_pubkey: [u8; 32],
-    pub nonce: u128,
-    pub created_at: i64,
+    pub topic: Pubkey,
+    pub is_winner: bool,
+    pub score: u8,
+    pub total_questions: u8,
+    pub reward_amount: u64,
+    pub best_score: u32,
+    pub best_score_quiz: Pubkey,
+    pub is_personal_best: bool,
+    pub timestamp: i64,
 }
 
-impl QuestionBlock {
-    pub const LEN: usize = 8 + 32 + 4 + 64 + 64 + 32 + 16 + 8;
+#[event]
+pub struct EphemeralScoreLinked {
+    pub user: Pubkey,
+    pub ephemeral: Pubkey,
+    pub topic: Pubkey,
+    pub score: u32,
+    pub total_completed: u32,
+    pub timestamp: i64,
 }
 
-// ===== EVENTS =====
+// ===== SERIES EVENTS =====
 
-// ===== TOPIC EVENTS =====
+#[event]
+pub struct SeriesCreated {
+    pub series: Pubkey,
+    pub authority: Pubkey,
+    pub reward_amount: u64,
+    pub timestamp: i64,
+}
 
 #[event]
-pub struct TopicCreated {
-    pub topic: Pubkey,
-    pub owner: Pubkey,
-    pub name: String,
+pub struct QuizAddedToSeries {
+    pub series: Pubkey,
+    pub quiz_set: Pubkey,
+    pub quiz_count: u8,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct TopicOwnershipTransferred {
-    pub topic: Pubkey,
-    pub old_owner: Pubkey,
-    pub new_owner: Pubkey,
+pub struct SeriesRewardClaimed {
+    pub series: Pubkey,
+    pub winner: Pubkey,
+    pub reward_amount: u64,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct TopicStatusToggled {
+pub struct UserRank {
+    pub user: Pubkey,
     pub topic: Pubkey,
-    pub is_active: bool,
+    pub rank: u32,                    // 1-based, UNRANKED (0) if not on the leaderboard
+    pub total_participants: u32,
+}
+
+#[event]
+pub struct QuizSetRenamed {
+    pub quiz_set: Pubkey,
+    pub old_name: String,
+    pub new_name: String,
+}
+
+#[event]
+pub struct LiveStandings {
+    pub quiz_set: Pubkey,
+    pub standings: Vec<StandingEntry>, // Sorted best first
     pub timestamp: i64,
 }
 
-// ===== QUIZ EVENTS =====
+#[event]
+pub struct CircuitCrosscheck {
+    pub result: [u8; 32],             // Raw circuit output for the sample
+    pub result_hash: [u8; 32],        // sha256("k3hoot:crosscheck" || result)
+    pub timestamp: i64,
+}
 
 #[event]
-pub struct QuizSetCreated {
+pub struct KeyRotationStarted {
     pub quiz_set: Pubkey,
-    pub topic: Pubkey,
+    pub new_mxe_pubkey: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct QuestionBlockRotated {
+    pub quiz_set: Pubkey,
+    pub question_index: u32,
+    pub rotated_count: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct KeyRotationCompleted {
+    pub quiz_set: Pubkey,
+    pub new_mxe_pubkey: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct KeyRotationAborted {
+    pub quiz_set: Pubkey,
+    pub target_mxe_pubkey: [u8; 32],
+    pub rotated_count: u8,            // Blocks already moved to the target key
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CorrectAnswerRevealed {
+    pub quiz_set: Pubkey,
+    pub player: Pubkey,               // Player who may now call reveal_my_mistakes mid-run
+    pub question_index: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UserGlobalStats {
+    pub user: Pubkey,
+    pub total_rewards: u64,
+    pub total_wins: u32,
+    pub total_completions: u32,
+    pub win_rate_bps: u16,            // total_wins / total_completions in basis points
+    pub topic_count: u8,              // UserScore accounts summed below
+    pub topic_score: u32,             // Sum of UserScore.score (one point per quiz won)
+    pub topic_completions: u32,       // Sum of UserScore.total_completed
+    pub topic_rewards: u64,           // Sum of UserScore.total_rewards
+}
+
+#[event]
+pub struct QuizSettledByKeeper {
+    pub quiz_set: Pubkey,
+    pub keeper: Pubkey,
     pub authority: Pubkey,
-    pub name: String,
-    pub question_count: u8,
-    pub reward_amount: u64,
+    pub keeper_fee: u64,
+    pub refunded: u64,                // Returned to the authority, including vault rent
     pub timestamp: i64,
 }
 
 #[event]
-pub struct QuestionBlockAdded {
-    pub question_block: Pubkey,
+pub struct QuizFunded {
     pub quiz_set: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub funded_amount: u64,
+    pub is_funded: bool,
+}
+
+#[event]
+pub struct HintRequested {
+    pub quiz_set: Pubkey,
+    pub player: Pubkey,
+    pub question_index: u32,
+    pub hint_number: u8,              // 1-based position of the hint
+    pub penalty: u32,                 // Points deducted for this hint
+}
+
+#[event]
+pub struct HintRevealed {
+    pub quiz_set: Pubkey,
+    pub player: Pubkey,
     pub question_index: u32,
+    pub encryption_key: [u8; 32],     // Player key the hint is encrypted to
+    pub nonce: u128,
+    pub ciphertexts: [[u8; 32]; 32],  // Zero-padded hint bytes, encrypted to the player
     pub timestamp: i64,
 }
 
 #[event]
-pub struct AnswerVerifiedEvent {
+pub struct MistakeRevealed {
+    pub quiz_set: Pubkey,
+    pub player: Pubkey,
     pub question_index: u32,
-    pub is_correct: bool,
+    pub encryption_key: [u8; 32],     // Player key the answer is encrypted to
+    pub nonce: u128,
+    pub ciphertexts: [[u8; 32]; 64],  // Correct answer bytes, encrypted to the player
     pub timestamp: i64,
 }
 
 #[event]
-pub struct QuizDataEncryptedEvent {
-    pub encrypted_data: [u8; 8],
+pub struct WinnerReassigned {
+    pub quiz_set: Pubkey,
+    pub previous_winner: Pubkey,      // Disqualified winner
+    pub new_winner: Pubkey,
+    pub authority: Pubkey,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct QuizDataDecryptedEvent {
-    pub decrypted_data: [u8; 8],
+pub struct TimeRemaining {
+    pub quiz_set: Pubkey,
+    pub player: Pubkey,
+    pub seconds_left: i64,            // 0 once expired
+    pub expired: bool,
+}
+
+#[event]
+pub struct ErrorContext {
+    pub quiz_set: Pubkey,
+    pub claimer: Pubkey,
+    pub error_code: u32,              // Anchor error code returned right after
+    pub winner: Option<Pubkey>,
+    pub is_reward_claimed: bool,
+    pub vault_balance: u64,           // Payable balance (lamports above rent, or tokens)
+}
+
+#[event]
+pub struct QuizSetClosed {
+    pub quiz_set: Pubkey,
+    pub authority: Pubkey,
+    pub question_blocks: u8,
+    pub reclaimed_lamports: u64,      // Vault, question blocks and quiz set rent combined
     pub timestamp: i64,
 }
 
 #[event]
-pub struct QuizCompleted {
+pub struct AnswerQueued {
     pub quiz_set: Pubkey,
-    pub winner: Pubkey,
-    pub reward_amount: u64,
+    pub player: Pubkey,
+    pub question_index: u8,
+    pub queued: u8,                   // Answers now pending in the player's queue
     pub timestamp: i64,
 }
 
+
 #[event]
-pub struct RewardClaimed {
+pub struct VaultUnderfunded {
     pub quiz_set: Pubkey,
     pub winner: Pubkey,
     pub reward_amount: u64,
+    pub vault_balance: u64,
     pub timestamp: i64,
 }
 
-// ===== SCORING EVENTS =====
 
 #[event]
-pub struct QuizCompletionRecorded {
-    pub user: Pubkey,
+pub struct ArciumPreflight {
+    pub all_ok: bool,
+    pub missing_mask: u32,            // Bit set per account that is missing or misderived
+    pub timestamp: i64,
+}
+
+
+#[event]
+pub struct StaleComputation {
+    pub quiz_set: Pubkey,
+    pub player: Pubkey,
+    pub question_index: u32,
+    pub timestamp: i64,
+}
+
+
+#[event]
+pub struct CategoryScores {
+    pub quiz_set: Pubkey,
+    pub player: Pubkey,
+    pub correct: [u8; MAX_CATEGORIES], // Questions scored per category
+    pub totals: [u8; MAX_CATEGORIES],  // Questions per category
+    pub timestamp: i64,
+}
+
+// Single end-of-quiz record: players, winners, payouts and per-question stats
+#[event]
+pub struct QuizSettlement {
     pub quiz_set: Pubkey,
     pub topic: Pubkey,
-    pub is_winner: bool,
-    pub score: u8,
-    pub total_questions: u8,
+    pub authority: Pubkey,
+    pub participant_count: u16,       // Distinct players who submitted an answer
+    pub winner: Option<Pubkey>,
+    pub co_winners: Vec<Pubkey>,
+    pub runner_up: Option<Pubkey>,
     pub reward_amount: u64,
+    pub reward_mint: Option<Pubkey>,  // None = native SOL
+    pub winner_claimed: bool,
+    pub co_winners_claimed: u8,       // Bitmask over co_winners
+    pub runner_up_claimed: bool,
+    pub proportional_paid: u64,       // Paid under RewardMode::Proportional
+    pub refunded: bool,
+    pub question_stats: Vec<QuestionSettlementStats>, // Sorted by question index
     pub timestamp: i64,
 }
 
@@ -1368,6 +8731,236 @@ pub enum QuizError {
     InsufficientQuestions,
     #[msg("Insufficient reward amount for this topic")]
     InsufficientReward,
+    #[msg("Invalid announcement time")]
+    InvalidAnnounceTime,
+    #[msg("Winner not yet announced")]
+    WinnerNotYetAnnounced,
+    #[msg("Winner already announced")]
+    WinnerAlreadyAnnounced,
+    #[msg("Invalid answer buffer size (must be 32 or 64)")]
+    InvalidBufferSize,
+    #[msg("Answer buffer size does not match this validation circuit")]
+    BufferSizeMismatch,
+    #[msg("Answer exceeds the quiz's answer buffer size")]
+    AnswerTooLong,
+    #[msg("Quiz question blocks changed since the quiz was finalized")]
+    QuizContentChanged,
+    #[msg("Missing or invalid ephemeral key link proof")]
+    InvalidLinkProof,
+    #[msg("Account is not a question block of this quiz set")]
+    InvalidQuestionBlock,
+    #[msg("Series already has the maximum number of quizzes")]
+    SeriesFull,
+    #[msg("Quiz set already belongs to this series")]
+    QuizAlreadyInSeries,
+    #[msg("Series reward already settled")]
+    SeriesAlreadySettled,
+    #[msg("Not every quiz in the series has a winner yet")]
+    SeriesNotFinished,
+    #[msg("Series member quiz sets missing or out of order")]
+    InvalidSeriesMembers,
+    #[msg("Callback payer does not match the original answer submitter")]
+    SubmitterMismatch,
+    #[msg("Payer balance is below the estimated computation fee")]
+    InsufficientComputeFee,
+    #[msg("Runner-up amount must be less than the reward amount")]
+    InvalidRunnerUpAmount,
+    #[msg("Reward mint not allowed for this topic")]
+    RewardMintNotAllowed,
+    #[msg("Player has been eliminated from this quiz")]
+    PlayerEliminated,
+    #[msg("Insufficient funds to cover the reward and account rent")]
+    InsufficientFunds,
+    #[msg("Claim window and grace must not be negative")]
+    InvalidClaimWindow,
+    #[msg("Claim window has expired")]
+    ClaimWindowExpired,
+    #[msg("Claim window is still open")]
+    ClaimWindowOpen,
+    #[msg("No question block exists for this question index")]
+    QuestionBlockMissing,
+    #[msg("Question weights do not add up to the quiz total weight")]
+    WeightMismatch,
+    #[msg("Top-up amount must be greater than 0")]
+    InvalidTopUpAmount,
+    #[msg("Question expiry must not be negative")]
+    InvalidExpiry,
+    #[msg("This question has expired")]
+    QuestionExpired,
+    #[msg("Quiz set does not belong to this topic")]
+    QuizNotInTopic,
+    #[msg("The same account was passed for two distinct roles")]
+    DuplicateAccounts,
+    #[msg("Gate token requirement not met")]
+    GateRequirementNotMet,
+    #[msg("Computation offset does not match derive_computation_offset")]
+    InvalidComputationOffset,
+    #[msg("Progress can only be reset on practice quizzes or before a winner is set")]
+    ResetNotAllowed,
+    #[msg("Topic already tracks the maximum number of reward currencies")]
+    TooManyRewardMints,
+    #[msg("Destination account does not match the requested payout address")]
+    InvalidDestination,
+    #[msg("Player progress does not belong to this quiz set")]
+    ProgressNotInQuiz,
+    #[msg("Player's run has ended after a wrong answer")]
+    RunEnded,
+    #[msg("A quiz set needs at least one required (non-bonus) question")]
+    InvalidBonusCount,
+    #[msg("Retry window must not be negative")]
+    InvalidRetryWindow,
+    #[msg("A key rotation is in progress for this quiz set")]
+    KeyRotationInProgress,
+    #[msg("No key rotation is in progress for this quiz set")]
+    NoKeyRotation,
+    #[msg("Question block is already encrypted under the rotation key")]
+    BlockAlreadyRotated,
+    #[msg("Revealing answers is only allowed on unrewarded practice quizzes")]
+    RevealRequiresPractice,
+    #[msg("Quiz has not been abandoned long enough for keeper settlement")]
+    QuizNotAbandoned,
+    #[msg("Keeper delay must not be negative")]
+    InvalidKeeperDelay,
+    #[msg("Options must not be empty")]
+    EmptyOption,
+    #[msg("Options must be distinct (case-insensitive)")]
+    DuplicateOption,
+    #[msg("Quiz reward has not been fully funded yet")]
+    QuizNotFunded,
+    #[msg("Funding amount must be positive and within the unfunded reward")]
+    InvalidFundingAmount,
+    #[msg("A claim receipt already exists for this quiz set and claimer")]
+    ClaimReceiptExists,
+    #[msg("A question can have at most three hints")]
+    TooManyHints,
+    #[msg("No hints left for this question")]
+    NoHintsLeft,
+    #[msg("A question can have at most three additional acceptable answers")]
+    TooManyAcceptableAnswers,
+    #[msg("New winner is the current winner")]
+    InvalidWinner,
+    #[msg("Player has not completed the quiz")]
+    WinnerNotQualified,
+    #[msg("Too many accounts in one batch")]
+    BatchTooLarge,
+    #[msg("Quiz duration must not be negative")]
+    InvalidDuration,
+    #[msg("Quiz is not timed")]
+    QuizNotTimed,
+    #[msg("Time to answer this quiz has run out")]
+    TimeExpired,
+    #[msg("Every question has the same correct answer")]
+    TrivialQuizRejected,
+    #[msg("Wrong password for this quiz")]
+    WrongPassword,
+    #[msg("Quiz is not password protected")]
+    NoPassword,
+    #[msg("Join this password-protected quiz before answering")]
+    NotJoined,
+    #[msg("No per-question rewards to claim")]
+    NothingToClaim,
+    #[msg("Token reward quizzes need the reward mint, token accounts and token program")]
+    MissingRewardTokenAccounts,
+    #[msg("Token account does not match the quiz's reward mint or owner")]
+    InvalidRewardTokenAccount,
+    #[msg("Only available for quizzes paying native SOL")]
+    NativeRewardOnly,
+    #[msg("Deadline must be in the future (or 0 for none)")]
+    InvalidDeadline,
+    #[msg("The quiz deadline has passed")]
+    QuizDeadlinePassed,
+    #[msg("The quiz deadline has not passed yet")]
+    DeadlineNotReached,
+    #[msg("Author has too many active quizzes; settle one first")]
+    TooManyActiveQuizzes,
+    #[msg("Signer is not this quiz's result authority")]
+    UnauthorizedSetter,
+    #[msg("Quiz rewards are not fully claimed or refunded yet")]
+    QuizNotSettled,
+    #[msg("Every question block of the quiz must be passed to close it")]
+    QuestionBlocksMissing,
+    #[msg("Offline answer queue is full")]
+    AnswerQueueFull,
+    #[msg("No queued answers to process")]
+    AnswerQueueEmpty,
+    #[msg("Question text and options exceed the maximum encrypted size")]
+    QuestionContentTooLong,
+    #[msg("Question chunk is out of range or malformed")]
+    InvalidQuestionChunk,
+    #[msg("Player hasn't completed the quiz yet")]
+    PlayerNotCompleted,
+    #[msg("Question wasn't answered wrong by this player")]
+    NotAMistake,
+    #[msg("Max winners must be between 1 and MAX_WINNERS")]
+    InvalidMaxWinners,
+    #[msg("Every winner slot is taken or payouts have started")]
+    WinnersFull,
+    #[msg("Attempt cooldown must not be negative")]
+    InvalidAttemptCooldown,
+    #[msg("No attempts left for this question")]
+    TooManyAttempts,
+    #[msg("Wait for the attempt cooldown before answering again")]
+    AttemptCooldown,
+    #[msg("Result grace must not be negative")]
+    InvalidResultGrace,
+    #[msg("Category must be below MAX_CATEGORIES")]
+    InvalidCategory,
+    #[msg("UserScore account doesn't belong to the user or repeats a topic")]
+    InvalidUserScore,
+    #[msg("The quiz authority can't answer or win their own quiz on this topic")]
+    AuthorityCannotParticipate,
+    #[msg("Reward brackets must be ascending by time, at most 10000 bps and at most MAX_REWARD_BRACKETS")]
+    InvalidRewardBrackets,
+    #[msg("Speed-bracket rewards need the claimer's completed PlayerProgress")]
+    CompletionTimeUnknown,
+    #[msg("Not enough players have taken part for rewards to be claimed")]
+    InsufficientParticipants,
+    #[msg("Question text must not be empty")]
+    EmptyQuestion,
+    #[msg("This quiz requires the claim to be co-signed by the registered proof key")]
+    ClaimProofMissing,
+    #[msg("Proof key must be set and differ from the player's wallet")]
+    InvalidProofKey,
+    #[msg("A proof key is already registered")]
+    ProofKeyAlreadySet,
+    #[msg("Proof keys must be registered before completing the quiz")]
+    ProofKeyTooLate,
+    #[msg("Proportional rewards can't be combined with per-question rewards")]
+    InvalidRewardMode,
+    #[msg("Quiz history doesn't belong to the claimer and quiz set")]
+    InvalidQuizHistory,
+    #[msg("The proportional reward pool has been fully paid out")]
+    RewardPoolExhausted,
+    #[msg("The settlement record has already been emitted")]
+    SettlementAlreadyFinalized,
+    #[msg("No answer has been committed for this question")]
+    AnswerNotCommitted,
+    #[msg("Revealed answer does not match the commitment")]
+    CommitmentMismatch,
+    #[msg("An answer cannot be revealed in the slot it was committed")]
+    RevealTooEarly,
+    #[msg("The commit phase of this quiz has closed")]
+    CommitPhaseClosed,
+    #[msg("Answers cannot be revealed until the commit phase closes")]
+    CommitPhaseOpen,
+    #[msg("Commit deadline must fall after creation and before the quiz deadline")]
+    InvalidCommitDeadline,
+    #[msg("Correct answers were revealed to this player, who can no longer answer or reset")]
+    AnswersRevealed,
+    #[msg("Rotation target key is already the quiz's current key")]
+    SameRotationKey,
+    #[msg("This quiz id belonged to a closed quiz set and can't be reused")]
+    QuizIdRetired,
+    #[msg("Acceptable answers can't change once players have answered")]
+    AnswersLocked,
+    #[msg("Deadline is too far in the future to compute")]
+    DeadlineOverflow,
+    #[msg("Too many answers are already prepaid")]
+    TooManyPrepaidAnswers,
+    #[msg("The quiz settlement must be finalized before the quiz set is closed")]
+    SettlementNotFinalized,
+    #[msg("This quiz completion has already been recorded")]
+    CompletionAlreadyRecorded,
 }
 
 #[error_code]
@@ -1376,4 +8969,109 @@ pub enum ErrorCode {
     AbortedComputation,
     #[msg("Cluster not set")]
     ClusterNotSet,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_to_bit_maps_question_indices_to_mask_bits() {
+        assert_eq!(index_to_bit(1).unwrap(), 1);
+        assert_eq!(index_to_bit(50).unwrap(), 1u64 << 49);
+        assert_eq!(index_to_bit(64).unwrap(), 1u64 << 63);
+        assert!(index_to_bit(0).is_err());
+        assert!(index_to_bit(65).is_err());
+    }
+
+    #[test]
+    fn proportional_share_pays_by_score() {
+        assert_eq!(proportional_share(10_000_000, 2, 3), 6_666_666);
+        assert_eq!(proportional_share(10_000_000, 3, 3), 10_000_000);
+        assert_eq!(proportional_share(10_000_000, 0, 3), 0);
+        assert_eq!(proportional_share(10_000_000, 5, 3), 10_000_000);
+        assert_eq!(proportional_share(10_000_000, 1, 0), 0);
+    }
+
+    #[test]
+    fn win_rate_bps_rounds_down_to_basis_points() {
+        assert_eq!(win_rate_bps(1, 3), 3333);
+        assert_eq!(win_rate_bps(3, 3), 10_000);
+        assert_eq!(win_rate_bps(0, 0), 0);
+        assert_eq!(win_rate_bps(5, 0), 0);
+        assert_eq!(win_rate_bps(u32::MAX, u32::MAX), 10_000);
+    }
+
+    #[test]
+    fn nonce_decrypt_inverts_nonce_encrypt_for_every_byte() {
+        let mut samples: Vec<Vec<u8>> = ["Capital of France?", "", "ÿþý", "Paris|London|Rome|Berlin"]
+            .iter()
+            .map(|text| text.as_bytes().to_vec())
+            .collect();
+        samples.push((0..=255u8).collect());
+        samples.push(vec![0xFF; 64]);
+        samples.push(vec![0xFE, 0xFF, 0x00, 0x01]);
+
+        for nonce in [0u128, 42, u128::MAX, 0xFF00_FF00_FF00_FF00_FF00_FF00_FF00_FF00] {
+            for sample in samples.iter() {
+                let mut data = sample.clone();
+                nonce_encrypt(&mut data, nonce);
+                nonce_decrypt(&mut data, nonce);
+                assert_eq!(&data, sample);
+
+                let mut rotated = sample.clone();
+                nonce_encrypt(&mut rotated, nonce);
+                reencrypt(&mut rotated, nonce, 7);
+                nonce_decrypt(&mut rotated, 7);
+                assert_eq!(&rotated, sample);
+            }
+        }
+    }
+
+    #[test]
+    fn question_chunks_round_trip_long_content() {
+        let question = "Which scientist formulated the three laws of motion and the law of universal gravitation in the Principia, printed 1687?";
+        let options: Vec<String> = ["Isaac Newton of 1643", "Galileo Galilei 1564", "Johannes Kepler 1571", "Rene Descartes, 1596"]
+            .iter()
+            .map(|option| option.to_string())
+            .collect();
+        assert_eq!(question.len(), 120);
+        assert!(options.iter().all(|option| option.len() == 20));
+
+        let chunks = pack_question_chunks(question, &options).unwrap();
+        assert!(chunks.len() > 1 && chunks.len() <= MAX_QUESTION_CHUNKS);
+
+        let (unpacked_question, unpacked_options) = unpack_question_chunks(&chunks, options.len()).unwrap();
+        assert_eq!(unpacked_question, question);
+        assert_eq!(unpacked_options, options);
+    }
+
+    #[test]
+    fn question_chunks_reject_oversized_content() {
+        let options: Vec<String> = vec!["a".into(), "b".into(), "c".into(), "d".into()];
+        assert!(pack_question_chunks(&"q".repeat(256), &options).is_err());
+        assert!(pack_question_chunks(&"q".repeat(255), &["o".repeat(100), "b".into(), "c".into(), "d".into()]).is_err());
+        assert!(pack_question_chunks(&"q".repeat(200), &options).is_ok());
+    }
+
+    #[test]
+    fn question_chunks_reject_blank_content() {
+        let options: Vec<String> = vec!["a".into(), "b".into(), "c".into(), "d".into()];
+        assert!(pack_question_chunks("", &options).is_err());
+        assert!(pack_question_chunks("   ", &options).is_err());
+        assert!(pack_question_chunks("q", &[String::new(), String::new(), String::new(), String::new()]).is_err());
+        assert!(pack_question_chunks("q", &options).is_ok());
+    }
+
+    #[test]
+    fn answer_commitment_binds_answer_salt_and_user() {
+        let user = Pubkey::new_unique();
+        let salt = [7u8; 32];
+        let commitment = answer_commitment_hash("paris", &salt, &user);
+
+        assert_eq!(commitment, answer_commitment_hash("paris", &salt, &user));
+        assert_ne!(commitment, answer_commitment_hash("london", &salt, &user));
+        assert_ne!(commitment, answer_commitment_hash("paris", &[8u8; 32], &user));
+        assert_ne!(commitment, answer_commitment_hash("paris", &salt, &Pubkey::new_unique()));
+    }
+}
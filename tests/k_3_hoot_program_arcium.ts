@@ -2,8 +2,25 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { K3HootProgramArcium } from "../target/types/k_3_hoot_program_arcium";
 import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, createMint, getAccount, getOrCreateAssociatedTokenAccount, mintTo } from "@solana/spl-token";
 import { expect } from "chai";
 import { BN } from "@coral-xyz/anchor";
+import { createHash, randomBytes } from "crypto";
+import {
+  awaitComputationFinalization,
+  getArciumEnv,
+  getCompDefAccAddress,
+  getClockAccAddress,
+  getCompDefAccOffset,
+  getComputationAccAddress,
+  getExecutingPoolAccAddress,
+  getFeePoolAccAddress,
+  getMempoolAccAddress,
+  getMXEAccAddress,
+  getMXEPublicKey,
+  RescueCipher,
+  x25519,
+} from "@arcium-hq/client";
 
 describe("k_3_hoot_program_arcium", () => {
   const provider = anchor.AnchorProvider.env();
@@ -35,13 +52,25 @@ describe("k_3_hoot_program_arcium", () => {
       ],
       program.programId
     )[0];
+
+    // Program-wide config, created once by the upgrade authority (the provider wallet)
+    if (!(await provider.connection.getAccountInfo(programConfigAddress))) {
+      const [programData] = PublicKey.findProgramAddressSync(
+        [program.programId.toBuffer()],
+        new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+      );
+      await program.methods
+        .initializeProgramConfig(0)
+        .accountsPartial({ admin: provider.wallet.publicKey, programData })
+        .rpc();
+    }
   });
 
   describe("Quiz Management", () => {
     it("Should create a quiz set", async () => {
       try {
         await program.methods
-          .createQuizSet("Math Quiz", 3, 1, new BN(1)) // Add uniqueId as third parameter
+          .createQuizSet("Math Quiz", 3, 1, new BN(1), new BN(1), new BN(0)) // Add uniqueId as third parameter
           .accountsPartial({
             quizSet: quizSetPda,
             authority: authority.publicKey,
@@ -74,7 +103,10 @@ describe("k_3_hoot_program_arcium", () => {
             Array.from(encryptedX),
             Array.from(encryptedY),
             Array.from(arciumPubkey),
-            nonce
+            nonce,
+            0,
+            false,
+            0
           )
           .accountsPartial({
             questionBlock: questionBlockPda,
@@ -120,7 +152,10 @@ describe("k_3_hoot_program_arcium", () => {
             Array.from(encryptedX),
             Array.from(encryptedY),
             Array.from(arciumPubkey),
-            nonce
+            nonce,
+            0,
+            false,
+            0
           )
           .accountsPartial({
             questionBlock: questionPda,
@@ -145,7 +180,5491 @@ describe("k_3_hoot_program_arcium", () => {
     });
 
     it("Should have validation function", async () => {
-      expect(typeof program.methods.validateAnswerOnchain).to.equal("function");
+      expect(typeof program.methods.commitAnswer).to.equal("function");
+      expect(typeof program.methods.revealAnswer).to.equal("function");
+    });
+  });
+
+  // Shared helpers for the feature suites below
+  const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+  const fundedKeypair = async (sol = 2) => {
+    const keypair = Keypair.generate();
+    const signature = await provider.connection.requestAirdrop(keypair.publicKey, sol * anchor.web3.LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(signature);
+    return keypair;
+  };
+
+  // Most tests let the owner play their own quizzes; pass false to keep the default block
+  const createTopic = async (
+    owner: Keypair,
+    name = `Topic ${Date.now() % 1_000_000}`,
+    allowAuthorityPlay = true
+  ) => {
+    const [topicPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("topic"), Buffer.from(name)],
+      program.programId
+    );
+
+    await program.methods
+      .createTopic(name)
+      .accountsPartial({
+        topic: topicPda,
+        owner: owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc();
+
+    if (allowAuthorityPlay) {
+      await program.methods
+        .setTopicAllowAuthorityPlay(true)
+        .accountsPartial({
+          topic: topicPda,
+          owner: owner.publicKey,
+          programConfig: programConfigAddress,
+          admin: provider.wallet.publicKey,
+        })
+        .signers([owner])
+        .rpc();
+    }
+
+    return topicPda;
+  };
+
+  const quizSetAddress = (authority: PublicKey, uniqueId: number) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("quiz_set"), authority.toBuffer(), Buffer.from([uniqueId])],
+      program.programId
+    )[0];
+
+  const vaultAddress = (quizSet: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), quizSet.toBuffer()],
+      program.programId
+    )[0];
+
+  const questionBlockAddress = (quizSet: PublicKey, index: number) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("question_block"), quizSet.toBuffer(), Buffer.from([index])],
+      program.programId
+    )[0];
+
+  const createQuiz = async (
+    owner: Keypair,
+    topicPda: PublicKey,
+    uniqueId: number,
+    questionCount = 3,
+    rewardAmount = new BN(10_000_000),
+    initialFunding = rewardAmount,
+    deadline = new BN(0)
+  ) => {
+    const quizSet = quizSetAddress(owner.publicKey, uniqueId);
+
+    await program.methods
+      .createQuizSet(`Quiz ${uniqueId}`, questionCount, uniqueId, rewardAmount, initialFunding, deadline)
+      .accountsPartial({
+        quizSet,
+        topic: topicPda,
+        vault: vaultAddress(quizSet),
+        authority: owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc();
+
+    return quizSet;
+  };
+
+  // Additive nonce scheme used by the circuits: byte + nonce byte (mod 256)
+  const encryptAnswer = (text: string, nonce: BN) => {
+    const nonceBytes = nonce.toArrayLike(Buffer, "le", 16);
+    const buffer = Buffer.alloc(64);
+    buffer.write(text);
+    return Array.from(buffer, (byte, i) => (byte + nonceBytes[i % 16]) & 0xff);
+  };
+
+  // Every block's correct answer is "answer"
+  const addQuestionBlocks = async (
+    owner: Keypair,
+    quizSet: PublicKey,
+    count: number,
+    weights?: number[],
+    bonusIndices: number[] = [],
+    categories?: number[]
+  ) => {
+    for (let i = 1; i <= count; i++) {
+      const nonce = new BN(123456789 + i);
+      await program.methods
+        .addEncryptedQuestionBlock(
+          i,
+          Array.from(new Uint8Array(64).fill(i)),
+          encryptAnswer("answer", nonce),
+          Array.from(new Uint8Array(32).fill(i + 2)),
+          nonce,
+          weights?.[i - 1] ?? 0,
+          bonusIndices.includes(i),
+          categories?.[i - 1] ?? 0
+        )
+        .accountsPartial({
+          questionBlock: questionBlockAddress(quizSet, i),
+          quizSet,
+          authority: owner.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([owner])
+        .rpc();
+    }
+  };
+
+  const questionBlockMetas = (quizSet: PublicKey, indices: number[]) =>
+    indices.map((index) => ({
+      pubkey: questionBlockAddress(quizSet, index),
+      isSigner: false,
+      isWritable: false,
+    }));
+
+  const programConfigAddress = PublicKey.findProgramAddressSync([Buffer.from("program_config")], program.programId)[0];
+
+  const authorQuotaAddress = (author: PublicKey) =>
+    PublicKey.findProgramAddressSync([Buffer.from("author"), author.toBuffer()], program.programId)[0];
+
+  const claimReward = async (
+    claimer: Keypair,
+    quizSet: PublicKey,
+    blockIndices?: number[],
+    destination?: PublicKey,
+    playerProgress?: PublicKey,
+    quizHistory?: PublicKey
+  ) => {
+    const { questionCount, authority } = await program.account.quizSet.fetch(quizSet);
+    const indices = blockIndices ?? Array.from({ length: questionCount }, (_, i) => i + 1);
+
+    return program.methods
+      .claimReward(destination ?? null)
+      .accountsPartial({
+        quizSet,
+        vault: vaultAddress(quizSet),
+        claimer: claimer.publicKey,
+        destinationAccount: destination ?? null,
+        playerProgress: playerProgress ?? null,
+        quizHistory: quizHistory ?? null,
+        authorQuota: authorQuotaAddress(authority),
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(questionBlockMetas(quizSet, indices))
+      .signers([claimer])
+      .rpc();
+  };
+
+  const finalizeSettlement = async (caller: Keypair, quizSet: PublicKey) => {
+    const { questionCount } = await program.account.quizSet.fetch(quizSet);
+    return program.methods
+      .finalizeSettlement()
+      .accountsPartial({ quizSet, caller: caller.publicKey })
+      .remainingAccounts(questionBlockMetas(quizSet, Array.from({ length: questionCount }, (_, i) => i + 1)))
+      .signers([caller])
+      .rpc();
+  };
+
+  const closeQuizSet = async (owner: Keypair, quizSet: PublicKey) => {
+    const { questionCount } = await program.account.quizSet.fetch(quizSet);
+    return program.methods
+      .closeQuizSet()
+      .accountsPartial({
+        quizSet,
+        vault: vaultAddress(quizSet),
+        authority: owner.publicKey,
+        authorQuota: authorQuotaAddress(owner.publicKey),
+      })
+      .remainingAccounts(
+        Array.from({ length: questionCount }, (_, i) => ({
+          pubkey: questionBlockAddress(quizSet, i + 1),
+          isSigner: false,
+          isWritable: true,
+        }))
+      )
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+  };
+
+  const userScoreAddress = (user: PublicKey, topicPda: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("user_score"), user.toBuffer(), topicPda.toBuffer()],
+      program.programId
+    )[0];
+
+  const recordCompletion = async (
+    user: Keypair,
+    quizSet: PublicKey,
+    topicPda: PublicKey,
+    rewardAmount = new BN(0),
+    leaderboard: PublicKey | null = null
+  ) => {
+    const userScore = await program.account.userScore.fetchNullable(userScoreAddress(user.publicKey, topicPda));
+    const completionIndex = new BN(userScore?.totalCompleted ?? 0);
+    const [quizHistory] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("quiz_history"),
+        user.publicKey.toBuffer(),
+        quizSet.toBuffer(),
+        completionIndex.toArrayLike(Buffer, "le", 4),
+      ],
+      program.programId
+    );
+
+    await program.methods
+      .recordQuizCompletion(rewardAmount)
+      .accountsPartial({
+        userScore: userScoreAddress(user.publicKey, topicPda),
+        quizHistory,
+        playerProgress: playerProgressAddress(user.publicKey, quizSet),
+        quizSet,
+        topic: topicPda,
+        leaderboard,
+        user: user.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user])
+      .rpc();
+
+    return quizHistory;
+  };
+
+  // Accounts shared by every instruction that queues an Arcium computation
+  const queueAccounts = (computationOffset: BN, circuit: string) => ({
+    computationAccount: getComputationAccAddress(program.programId, computationOffset),
+    clusterAccount: getArciumEnv().arciumClusterPubkey,
+    mxeAccount: getMXEAccAddress(program.programId),
+    mempoolAccount: getMempoolAccAddress(program.programId),
+    executingPool: getExecutingPoolAccAddress(program.programId),
+    compDefAccount: getCompDefAccAddress(
+      program.programId,
+      Buffer.from(getCompDefAccOffset(circuit)).readUInt32LE()
+    ),
+  });
+
+  const newComputationOffset = () => new BN(randomBytes(8), "hex");
+
+  const answerSubmissionAddress = (computationOffset: BN) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("submission"), computationOffset.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+
+  const playerProgressAddress = (player: PublicKey, quizSet: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("progress"), player.toBuffer(), quizSet.toBuffer()],
+      program.programId
+    )[0];
+
+  // Mirrors derive_computation_offset: answer submissions must use this offset
+  const computationOffsetFor = async (player: PublicKey, quizSet: PublicKey, questionIndex: number) => {
+    const progress = await program.account.playerProgress.fetchNullable(playerProgressAddress(player, quizSet));
+    const submissionCount = Buffer.alloc(4);
+    submissionCount.writeUInt32LE(progress ? progress.submissionCount : 0);
+
+    const hash = createHash("sha256")
+      .update(Buffer.from("k3hoot:offset"))
+      .update(player.toBuffer())
+      .update(quizSet.toBuffer())
+      .update(Buffer.from([questionIndex]))
+      .update(submissionCount)
+      .digest();
+    return new BN(hash.subarray(0, 8), "le");
+  };
+
+  const answerCommitmentAddress = (player: PublicKey, quizSet: PublicKey, questionIndex: number) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("answer_commit"), quizSet.toBuffer(), player.toBuffer(), Buffer.from([questionIndex])],
+      program.programId
+    )[0];
+
+  // Mirrors answer_commitment_hash: sha256(answer || salt || user)
+  const answerCommitmentHash = (answer: string, salt: number[], player: PublicKey) =>
+    createHash("sha256").update(Buffer.from(answer)).update(Buffer.from(salt)).update(player.toBuffer()).digest();
+
+  // Commit to `answer` and return the salt needed to reveal it
+  const commitAnswer = async (player: Keypair, quizSet: PublicKey, questionIndex: number, answer: string) => {
+    const salt = Array.from(randomBytes(32));
+    await program.methods
+      .commitAnswer(questionIndex, Array.from(answerCommitmentHash(answer, salt, player.publicKey)))
+      .accountsPartial({
+        answerCommitment: answerCommitmentAddress(player.publicKey, quizSet, questionIndex),
+        quizSet,
+        user: player.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([player])
+      .rpc({ commitment: "confirmed" });
+    return salt;
+  };
+
+  // Reveal a committed answer, queueing its 64-byte validation
+  const revealAnswer = async (
+    player: Keypair,
+    quizSet: PublicKey,
+    questionIndex: number,
+    answer: string,
+    salt: number[],
+    prefundNext = false
+  ) => {
+    const computationOffset = await computationOffsetFor(player.publicKey, quizSet, questionIndex);
+    await program.methods
+      .revealAnswer(computationOffset, answer, questionIndex, salt, prefundNext)
+      .accountsPartial({
+        payer: player.publicKey,
+        answerSubmission: answerSubmissionAddress(computationOffset),
+        playerProgress: playerProgressAddress(player.publicKey, quizSet),
+        answerCommitment: answerCommitmentAddress(player.publicKey, quizSet, questionIndex),
+        questionBlock: questionBlockAddress(quizSet, questionIndex),
+        quizSet,
+        ...queueAccounts(computationOffset, "validate_answer"),
+      })
+      .signers([player])
+      .rpc({ commitment: "confirmed" });
+    return computationOffset;
+  };
+
+  // Commit and reveal a 64-byte answer for `player`
+  const submitAnswer = async (
+    player: Keypair,
+    quizSet: PublicKey,
+    questionIndex: number,
+    answer: string,
+    prefundNext = false
+  ) => {
+    const salt = await commitAnswer(player, quizSet, questionIndex, answer);
+    return revealAnswer(player, quizSet, questionIndex, answer, salt, prefundNext);
+  };
+
+  // Answer questions 1..n in order, right or wrong, waiting for each verdict
+  const answerQuestions = async (player: Keypair, quizSet: PublicKey, answers: string[]) => {
+    for (const [i, answer] of answers.entries()) {
+      const offset = await submitAnswer(player, quizSet, i + 1, answer);
+      await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+    }
+  };
+
+  const awaitEvent = async (eventName: string) => {
+    let listenerId: number;
+    const event = await new Promise<any>((resolve) => {
+      listenerId = program.addEventListener(eventName as any, (event: any) => resolve(event));
+    });
+    await program.removeEventListener(listenerId!);
+    return event;
+  };
+
+  describe("Winner Announcement Delay", () => {
+    it("Should block claims until the announcement time passes", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 11);
+
+      const announceAfter = Math.floor(Date.now() / 1000) + 5;
+      await program.methods
+        .setAnnounceAfter(new BN(announceAfter))
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      await program.methods
+        .setWinnerForDevnet(["a", "b", "c"], ["a", "b", "c"])
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+
+      const quizSetAccount = await program.account.quizSet.fetch(quizSet);
+      expect(quizSetAccount.wonAt.toNumber()).to.be.greaterThan(0);
+      expect(quizSetAccount.isWinnerAnnounced).to.equal(false);
+
+      try {
+        await claimReward(owner, quizSet);
+        expect.fail("claim should be blocked before the announcement");
+      } catch (error: any) {
+        expect(error.toString()).to.include("WinnerNotYetAnnounced");
+      }
+
+      await sleep(6000);
+      await claimReward(owner, quizSet);
+
+      const claimed = await program.account.quizSet.fetch(quizSet);
+      expect(claimed.isRewardClaimed).to.equal(true);
+    });
+  });
+
+  describe("Personal Best Tracking", () => {
+    it("Should improve the best score across two completions", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const firstQuiz = await createQuiz(owner, topicPda, 21);
+      const secondQuiz = await createQuiz(owner, topicPda, 22);
+      await addQuestionBlocks(owner, firstQuiz, 3);
+      await addQuestionBlocks(owner, secondQuiz, 3);
+
+      // The best score comes from the verified answers, not from the caller
+      await answerQuestions(player, firstQuiz, ["answer", "wrong", "wrong"]);
+      await recordCompletion(player, firstQuiz, topicPda);
+      let userScore = await program.account.userScore.fetch(userScoreAddress(player.publicKey, topicPda));
+      expect(userScore.bestScore).to.equal(1);
+      expect(userScore.bestScoreQuiz.toString()).to.equal(firstQuiz.toString());
+
+      await answerQuestions(player, secondQuiz, ["answer", "answer", "answer"]);
+      await recordCompletion(player, secondQuiz, topicPda);
+      userScore = await program.account.userScore.fetch(userScoreAddress(player.publicKey, topicPda));
+      expect(userScore.bestScore).to.equal(3);
+      expect(userScore.bestScoreQuiz.toString()).to.equal(secondQuiz.toString());
+      expect(userScore.totalCompleted).to.equal(2);
+    });
+  });
+
+  describe("Short Answer Buffers", () => {
+    it("Should validate short answers through the 32-byte circuit", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 31);
+
+      await program.methods
+        .setBufferSize(32)
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const quizSetAccount = await program.account.quizSet.fetch(quizSet);
+      expect(quizSetAccount.bufferSize).to.equal(32);
+
+      // The 64-byte circuit refuses quizzes configured for short answers
+      const longOffset = await computationOffsetFor(player.publicKey, quizSet, 1);
+      try {
+        await program.methods
+          .revealAnswer(longOffset, "Paris", 1, new Array(32).fill(0), false)
+          .accountsPartial({
+            payer: player.publicKey,
+            answerSubmission: answerSubmissionAddress(longOffset),
+            answerCommitment: answerCommitmentAddress(player.publicKey, quizSet, 1),
+            questionBlock: questionBlockAddress(quizSet, 1),
+            quizSet,
+            ...queueAccounts(longOffset, "validate_answer"),
+          })
+          .signers([player])
+          .rpc();
+        expect.fail("64-byte validation should be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("BufferSizeMismatch");
+      }
+
+      // Short answers are normalized like long ones: case and surrounding spaces don't matter
+      const salt = await commitAnswer(player, quizSet, 1, " ANSWER ");
+      const computationOffset = await computationOffsetFor(player.publicKey, quizSet, 1);
+      const verified = awaitEvent("answerVerifiedEvent");
+      await program.methods
+        .validateAnswerShortOnchain(computationOffset, " ANSWER ", 1, salt)
+        .accountsPartial({
+          payer: player.publicKey,
+          answerSubmission: answerSubmissionAddress(computationOffset),
+          answerCommitment: answerCommitmentAddress(player.publicKey, quizSet, 1),
+          questionBlock: questionBlockAddress(quizSet, 1),
+          quizSet,
+          ...queueAccounts(computationOffset, "validate_answer_short"),
+        })
+        .signers([player])
+        .rpc({ commitment: "confirmed" });
+
+      await awaitComputationFinalization(provider, computationOffset, program.programId, "confirmed");
+      const event = await verified;
+      expect(event.questionIndex).to.equal(1);
+      expect(event.isCorrect).to.equal(true);
+    });
+  });
+
+  describe("Question Block Content Commitment", () => {
+    it("Should reject a claim when the question blocks no longer match the commitment", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 41);
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const quizSetAccount = await program.account.quizSet.fetch(quizSet);
+      expect(Buffer.from(quizSetAccount.contentHash).equals(Buffer.alloc(32))).to.equal(false);
+
+      await program.methods
+        .setWinnerForDevnet(["a", "b", "c"], ["a", "b", "c"])
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+
+      // No instruction can rewrite a block yet, so present a block set that
+      // differs from the committed one (question 3 swapped for question 1)
+      try {
+        await claimReward(owner, quizSet, [1, 2, 1]);
+        expect.fail("claim against changed content should fail");
+      } catch (error: any) {
+        expect(error.toString()).to.include("QuizContentChanged");
+      }
+
+      await claimReward(owner, quizSet);
+      const claimed = await program.account.quizSet.fetch(quizSet);
+      expect(claimed.isRewardClaimed).to.equal(true);
+    });
+  });
+
+  describe("Ephemeral Score Linking", () => {
+    it("Should merge an ephemeral score into the main account", async () => {
+      const owner = await fundedKeypair();
+      const mainUser = await fundedKeypair();
+      const ephemeral = await fundedKeypair(1);
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 51);
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      await answerQuestions(ephemeral, quizSet, ["answer", "answer", "wrong"]);
+      await answerQuestions(mainUser, quizSet, ["answer", "wrong", "wrong"]);
+      await recordCompletion(ephemeral, quizSet, topicPda);
+      await recordCompletion(mainUser, quizSet, topicPda);
+
+      // The main score's link nonce is signed in, so each proof links once
+      const { linkNonce } = await program.account.userScore.fetch(userScoreAddress(mainUser.publicKey, topicPda));
+      const message = Buffer.concat([
+        Buffer.from("k3hoot:link"),
+        mainUser.publicKey.toBuffer(),
+        topicPda.toBuffer(),
+        new BN(linkNonce).toArrayLike(Buffer, "le", 4),
+      ]);
+      const proofIx = anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+        privateKey: ephemeral.secretKey,
+        message,
+      });
+
+      const link = () =>
+        program.methods
+          .linkEphemeralScore()
+          .accountsPartial({
+            mainScore: userScoreAddress(mainUser.publicKey, topicPda),
+            ephemeralScore: userScoreAddress(ephemeral.publicKey, topicPda),
+            ephemeral: ephemeral.publicKey,
+            topic: topicPda,
+            user: mainUser.publicKey,
+            instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: SystemProgram.programId,
+          })
+          .preInstructions([proofIx])
+          .signers([mainUser])
+          .rpc();
+      await link();
+
+      const merged = await program.account.userScore.fetch(userScoreAddress(mainUser.publicKey, topicPda));
+      expect(merged.totalCompleted).to.equal(2);
+      expect(merged.bestScore).to.equal(2);
+
+      const closed = await provider.connection.getAccountInfo(userScoreAddress(ephemeral.publicKey, topicPda));
+      expect(closed).to.equal(null);
+
+      // A later ephemeral score can't be merged by replaying the spent proof
+      expect(merged.linkNonce).to.equal(linkNonce + 1);
+      await recordCompletion(ephemeral, await createQuiz(owner, topicPda, 52), topicPda);
+      try {
+        await link();
+        expect.fail("a spent link proof should be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidLinkProof");
+      }
+    });
+  });
+
+  describe("Question Block Migration", () => {
+    it("Should migrate a question block and expose the new fields", async () => {
+      const owner = await fundedKeypair();
+      const stranger = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 61);
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const questionBlock = questionBlockAddress(quizSet, 1);
+
+      try {
+        await program.methods
+          .migrateQuestionBlock()
+          .accountsPartial({ questionBlock, quizSet, authority: stranger.publicKey })
+          .signers([stranger])
+          .rpc();
+        expect.fail("only the quiz authority may migrate blocks");
+      } catch (error: any) {
+        expect(error.toString()).to.include("Unauthorized");
+      }
+
+      // Blocks created by this program version already have the current size,
+      // so migrating is a resize no-op that still stamps the layout version
+      await program.methods
+        .migrateQuestionBlock()
+        .accountsPartial({ questionBlock, quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+
+      const info = await provider.connection.getAccountInfo(questionBlock);
+      expect(info!.data.length).to.equal(program.account.questionBlock.size);
+
+      const migrated = await program.account.questionBlock.fetch(questionBlock);
+      expect(migrated.version).to.equal(8);
+      expect(migrated.questionIndex).to.equal(1);
+    });
+  });
+
+  describe("Quiz Series", () => {
+    const createSeries = async (owner: Keypair, seriesId: number, reward: BN, quizSets: PublicKey[]) => {
+      const [series] = PublicKey.findProgramAddressSync(
+        [Buffer.from("series"), owner.publicKey.toBuffer(), Buffer.from([seriesId])],
+        program.programId
+      );
+      const [seriesVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("series_vault"), series.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .createSeries(seriesId, reward)
+        .accountsPartial({ series, seriesVault, authority: owner.publicKey, systemProgram: SystemProgram.programId })
+        .signers([owner])
+        .rpc();
+
+      for (const quizSet of quizSets) {
+        await program.methods
+          .addQuizToSeries()
+          .accountsPartial({ series, quizSet, authority: owner.publicKey })
+          .signers([owner])
+          .rpc();
+      }
+
+      return { series, seriesVault };
+    };
+
+    // Member quiz sets in order, then the contenders' progress accounts
+    const claimSeries = (
+      claimer: Keypair,
+      series: PublicKey,
+      seriesVault: PublicKey,
+      quizSets: PublicKey[],
+      progress: PublicKey[]
+    ) =>
+      program.methods
+        .claimSeriesReward()
+        .accountsPartial({ series, seriesVault, claimer: claimer.publicKey, systemProgram: SystemProgram.programId })
+        .remainingAccounts(
+          [...quizSets, ...progress].map((pubkey) => ({ pubkey, isSigner: false, isWritable: false }))
+        )
+        .signers([claimer])
+        .rpc();
+
+    it("Should pay the pooled prize to the best aggregate scorer across the series", async () => {
+      const owner = await fundedKeypair(3);
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const firstQuiz = await createQuiz(owner, topicPda, 71);
+      const secondQuiz = await createQuiz(owner, topicPda, 72);
+      await addQuestionBlocks(owner, firstQuiz, 3);
+      await addQuestionBlocks(owner, secondQuiz, 3);
+
+      const quizSets = [firstQuiz, secondQuiz];
+      const seriesReward = new BN(50_000_000);
+      const { series, seriesVault } = await createSeries(owner, 7, seriesReward, quizSets);
+
+      for (const quizSet of quizSets) {
+        await answerQuestions(player, quizSet, ["answer", "answer", "answer"]);
+      }
+
+      const before = await provider.connection.getBalance(player.publicKey);
+      await claimSeries(
+        player,
+        series,
+        seriesVault,
+        quizSets,
+        quizSets.map((quizSet) => playerProgressAddress(player.publicKey, quizSet))
+      );
+
+      const after = await provider.connection.getBalance(player.publicKey);
+      expect(after - before).to.be.greaterThan(seriesReward.toNumber() - 10_000);
+
+      const seriesAccount = await program.account.quizSeries.fetch(series);
+      expect(seriesAccount.isRewardClaimed).to.equal(true);
+      expect(seriesAccount.winner!.toString()).to.equal(player.publicKey.toString());
+    });
+
+    it("Should rank by summed score rather than by quizzes won", async () => {
+      const owner = await fundedKeypair(3);
+      const mostWins = await fundedKeypair();
+      const topScorer = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const firstQuiz = await createQuiz(owner, topicPda, 73);
+      const secondQuiz = await createQuiz(owner, topicPda, 74);
+      const weightedQuiz = await createQuiz(owner, topicPda, 75);
+      await addQuestionBlocks(owner, firstQuiz, 3);
+      await addQuestionBlocks(owner, secondQuiz, 3);
+      await program.methods
+        .setTotalWeight(100)
+        .accountsPartial({ quizSet: weightedQuiz, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, weightedQuiz, 3, [20, 30, 50]);
+
+      const quizSets = [firstQuiz, secondQuiz, weightedQuiz];
+      const { series, seriesVault } = await createSeries(owner, 8, new BN(50_000_000), quizSets);
+
+      // Two wins worth 3 points each against one win worth 100
+      await answerQuestions(mostWins, firstQuiz, ["answer", "answer", "answer"]);
+      await answerQuestions(mostWins, secondQuiz, ["answer", "answer", "answer"]);
+      await answerQuestions(topScorer, weightedQuiz, ["answer", "answer", "answer"]);
+      const mostWinsProgress = [firstQuiz, secondQuiz].map((quizSet) =>
+        playerProgressAddress(mostWins.publicKey, quizSet)
+      );
+      const topScorerProgress = [playerProgressAddress(topScorer.publicKey, weightedQuiz)];
+
+      try {
+        await claimSeries(mostWins, series, seriesVault, quizSets, [...mostWinsProgress, ...topScorerProgress]);
+        expect.fail("the player with the most wins is not the top scorer");
+      } catch (error: any) {
+        expect(error.toString()).to.include("NotWinner");
+      }
+
+      // Leaving another quiz's winner out of the comparison is refused
+      try {
+        await claimSeries(topScorer, series, seriesVault, quizSets, topScorerProgress);
+        expect.fail("a member quiz's winner must be among the contenders");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidSeriesMembers");
+      }
+
+      await claimSeries(topScorer, series, seriesVault, quizSets, [...mostWinsProgress, ...topScorerProgress]);
+      const seriesAccount = await program.account.quizSeries.fetch(series);
+      expect(seriesAccount.winner!.toString()).to.equal(topScorer.publicKey.toString());
+    });
+  });
+
+  describe("Answer Submitter Binding", () => {
+    it("Should reject a callback whose payer is not the original submitter", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const attacker = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 81);
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const computationOffset = await submitAnswer(player, quizSet, 1, "answer");
+      const answerSubmission = answerSubmissionAddress(computationOffset);
+
+      const submission = await program.account.answerSubmission.fetch(answerSubmission);
+      expect(submission.submitter.toString()).to.equal(player.publicKey.toString());
+      expect(submission.questionIndex).to.equal(1);
+
+      let rejected = false;
+      try {
+        await program.methods
+          .validateAnswerCallback({ failure: {} } as any)
+          .accountsPartial({
+            payer: attacker.publicKey,
+            answerSubmission,
+            questionBlock: questionBlockAddress(quizSet, 1),
+            quizSet,
+          })
+          .rpc();
+      } catch (error: any) {
+        rejected = true;
+      }
+      expect(rejected).to.equal(true);
+
+      const quizSetAccount = await program.account.quizSet.fetch(quizSet);
+      expect(quizSetAccount.winner).to.equal(null);
+    });
+  });
+
+  describe("Published vs Unpublished Answer Statistics", () => {
+    const readMetadata = async (quizSet: PublicKey) => {
+      const event = awaitEvent("questionMetadata");
+      await program.methods
+        .getQuestionMetadata()
+        .accountsPartial({ quizSet, questionBlock: questionBlockAddress(quizSet, 1) })
+        .rpc();
+      return event;
+    };
+
+    it("Should leave correctness counts out of events unless published", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 91);
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const metadata = await readMetadata(quizSet);
+      expect(metadata.statsPublic).to.equal(false);
+      expect(metadata.attemptCount).to.equal(null);
+      expect(metadata.correctCount).to.equal(null);
+    });
+
+    it("Should expose correctness counts once the host opts in", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 92);
+
+      await program.methods
+        .setStatsPublic(true)
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const metadata = await readMetadata(quizSet);
+      expect(metadata.statsPublic).to.equal(true);
+      expect(metadata.attemptCount).to.equal(0);
+      expect(metadata.correctCount).to.equal(0);
+    });
+  });
+
+  describe("Computation Fee Pre-check", () => {
+    it("Should reject an underfunded payer with a friendly error", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 101);
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      // Enough for the commitment and answer records' rent, not for the computation itself
+      const player = await fundedKeypair(0.007);
+      const salt = await commitAnswer(player, quizSet, 1, "answer");
+
+      const computationOffset = await computationOffsetFor(player.publicKey, quizSet, 1);
+      try {
+        await program.methods
+          .revealAnswer(computationOffset, "answer", 1, salt, false)
+          .accountsPartial({
+            payer: player.publicKey,
+            answerSubmission: answerSubmissionAddress(computationOffset),
+            answerCommitment: answerCommitmentAddress(player.publicKey, quizSet, 1),
+            questionBlock: questionBlockAddress(quizSet, 1),
+            quizSet,
+            ...queueAccounts(computationOffset, "validate_answer"),
+          })
+          .signers([player])
+          .rpc();
+        expect.fail("underfunded payer should be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InsufficientComputeFee");
+      }
+    });
+  });
+
+  describe("Runner-up Reward", () => {
+    it("Should pay the winner and the runner-up their configured shares", async () => {
+      const owner = await fundedKeypair();
+      const winner = await fundedKeypair();
+      const runnerUp = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const rewardAmount = new BN(30_000_000);
+      const runnerUpAmount = new BN(10_000_000);
+      const quizSet = await createQuiz(owner, topicPda, 111, 3, rewardAmount);
+
+      await program.methods
+        .setRunnerUpAmount(runnerUpAmount)
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      for (const [player, questions] of [[winner, [1, 2, 3]], [runnerUp, [1]]] as [Keypair, number[]][]) {
+        for (const questionIndex of questions) {
+          const offset = await submitAnswer(player, quizSet, questionIndex, "answer");
+          await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+        }
+      }
+
+      const quizSetAccount = await program.account.quizSet.fetch(quizSet);
+      expect(quizSetAccount.winner!.toString()).to.equal(winner.publicKey.toString());
+      expect(quizSetAccount.runnerUp!.toString()).to.equal(runnerUp.publicKey.toString());
+
+      const winnerBefore = await provider.connection.getBalance(winner.publicKey);
+      await claimReward(winner, quizSet);
+      const winnerAfter = await provider.connection.getBalance(winner.publicKey);
+      expect(winnerAfter - winnerBefore).to.be.closeTo(rewardAmount.sub(runnerUpAmount).toNumber(), 10_000);
+
+      const runnerUpBefore = await provider.connection.getBalance(runnerUp.publicKey);
+      await claimReward(runnerUp, quizSet);
+      const runnerUpAfter = await provider.connection.getBalance(runnerUp.publicKey);
+      expect(runnerUpAfter - runnerUpBefore).to.be.closeTo(runnerUpAmount.toNumber(), 10_000);
+    });
+  });
+
+  describe("Topic Reward Currency Restriction", () => {
+    it("Should reject a quiz whose reward currency the topic disallows", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const requiredMint = Keypair.generate().publicKey;
+
+      await program.methods
+        .setTopicRewardMint(requiredMint)
+        .accountsPartial({ topic: topicPda, owner: owner.publicKey })
+        .signers([owner])
+        .rpc();
+
+      const topic = await program.account.topic.fetch(topicPda);
+      expect(topic.allowedRewardMint!.toString()).to.equal(requiredMint.toString());
+
+      // A native SOL reward is not the mandated mint
+      try {
+        await createQuiz(owner, topicPda, 131);
+        expect.fail("SOL quiz should be rejected in a mint-restricted topic");
+      } catch (error: any) {
+        expect(error.toString()).to.include("RewardMintNotAllowed");
+      }
+    });
+  });
+
+  describe("Sudden Death Mode", () => {
+    it("Should eliminate a player on their first wrong answer", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 141);
+
+      await program.methods
+        .setSuddenDeath(true)
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const offset = await submitAnswer(player, quizSet, 1, "definitely wrong");
+      await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+
+      const progress = await program.account.playerProgress.fetch(playerProgressAddress(player.publicKey, quizSet));
+      expect(progress.eliminated).to.equal(true);
+      expect(progress.wrongCount).to.equal(1);
+
+      try {
+        await submitAnswer(player, quizSet, 2, "another answer");
+        expect.fail("eliminated player should not be able to answer");
+      } catch (error: any) {
+        expect(error.toString()).to.include("PlayerEliminated");
+      }
+    });
+  });
+
+  describe("Topic Leaderboard Rank", () => {
+    it("Should report the rank of a mid-table user", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const [leaderboard] = PublicKey.findProgramAddressSync(
+        [Buffer.from("leaderboard"), topicPda.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .initTopicLeaderboard()
+        .accountsPartial({ leaderboard, topic: topicPda, owner: owner.publicKey, systemProgram: SystemProgram.programId })
+        .signers([owner])
+        .rpc();
+
+      // Wins come from the quiz sets, so each player wins a different number of
+      // three-winner quizzes: top all three, middle two, bottom one
+      const [top, middle, bottom] = [await fundedKeypair(), await fundedKeypair(), await fundedKeypair()];
+      const quizWinners = [[top, middle, bottom], [top, middle], [top]];
+      for (const [i, winners] of quizWinners.entries()) {
+        const quizSet = await createQuiz(owner, topicPda, 151 + i);
+        await program.methods
+          .setMaxWinners(3)
+          .accountsPartial({ quizSet, authority: owner.publicKey })
+          .signers([owner])
+          .rpc();
+        await addQuestionBlocks(owner, quizSet, 3);
+        for (const player of winners) {
+          await program.methods
+            .setWinnerForUser(player.publicKey, 3)
+            .accountsPartial({ quizSet, setter: owner.publicKey, systemProgram: SystemProgram.programId })
+            .signers([owner])
+            .rpc();
+          await recordCompletion(player, quizSet, topicPda, new BN(0), leaderboard);
+        }
+      }
+
+      const rankEvent = awaitEvent("userRank");
+      await program.methods
+        .getUserRank()
+        .accountsPartial({ leaderboard, topic: topicPda, user: middle.publicKey })
+        .rpc();
+      const event = await rankEvent;
+
+      expect(event.rank).to.equal(2);
+      expect(event.totalParticipants).to.equal(3);
+
+      const unrankedEvent = awaitEvent("userRank");
+      await program.methods
+        .getUserRank()
+        .accountsPartial({ leaderboard, topic: topicPda, user: Keypair.generate().publicKey })
+        .rpc();
+      expect((await unrankedEvent).rank).to.equal(0);
+    });
+  });
+
+  describe("Quiz Funding Pre-check", () => {
+    it("Should reject an underfunded authority with InsufficientFunds", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+
+      // Enough for the quiz set and vault rent, far below the 1 SOL reward
+      const poorOwner = await fundedKeypair(0.1);
+      await program.methods
+        .transferTopicOwnership(poorOwner.publicKey)
+        .accountsPartial({ topic: topicPda, owner: owner.publicKey, systemProgram: SystemProgram.programId })
+        .signers([owner])
+        .rpc();
+
+      try {
+        await createQuiz(poorOwner, topicPda, 161, 3, new BN(anchor.web3.LAMPORTS_PER_SOL));
+        expect.fail("underfunded quiz creation should fail");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InsufficientFunds");
+      }
+
+      const quizSetInfo = await provider.connection.getAccountInfo(quizSetAddress(poorOwner.publicKey, 161));
+      expect(quizSetInfo).to.equal(null);
+    });
+  });
+
+  describe("Claim Grace Period", () => {
+    it("Should accept a claim inside the grace period past the nominal deadline", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 171);
+
+      await program.methods
+        .setClaimWindow(new BN(2), new BN(8))
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      await program.methods
+        .setWinnerForDevnet(["a", "b", "c"], ["a", "b", "c"])
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+
+      // Nominal window has passed; the grace period still covers us
+      await sleep(4000);
+
+      try {
+        await program.methods
+          .reclaimUnclaimedWinnerReward()
+          .accountsPartial({ quizSet, vault: vaultAddress(quizSet), authority: owner.publicKey })
+          .signers([owner])
+          .rpc();
+        expect.fail("reclaim must wait for the grace period");
+      } catch (error: any) {
+        expect(error.toString()).to.include("ClaimWindowOpen");
+      }
+
+      await claimReward(owner, quizSet);
+      const claimed = await program.account.quizSet.fetch(quizSet);
+      expect(claimed.isRewardClaimed).to.equal(true);
+    });
+  });
+
+  describe("Co-Winners", () => {
+    it("Should split the reward evenly between players tied in the same slot", async () => {
+      const owner = await fundedKeypair();
+      const first = await fundedKeypair();
+      const second = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 181);
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      // Both finishers land in one transaction, so the slot can't separate them
+      const finish = (player: PublicKey) =>
+        program.methods
+          .setWinnerForUser(player, 3)
+          .accountsPartial({ quizSet, setter: owner.publicKey, systemProgram: SystemProgram.programId })
+          .instruction();
+      const tx = new anchor.web3.Transaction().add(await finish(first.publicKey), await finish(second.publicKey));
+      await provider.sendAndConfirm(tx, [owner]);
+
+      const tied = await program.account.quizSet.fetch(quizSet);
+      expect(tied.winner!.toString()).to.equal(first.publicKey.toString());
+      expect(tied.coWinners.map((key) => key.toString())).to.deep.equal([second.publicKey.toString()]);
+
+      const vault = vaultAddress(quizSet);
+      const start = await provider.connection.getBalance(vault);
+
+      await claimReward(first, quizSet);
+      const afterFirst = await provider.connection.getBalance(vault);
+      await claimReward(second, quizSet);
+      const afterSecond = await provider.connection.getBalance(vault);
+
+      expect(start - afterFirst).to.equal(5_000_000);
+      expect(afterFirst - afterSecond).to.equal(5_000_000);
+
+      const settled = await program.account.quizSet.fetch(quizSet);
+      expect(settled.isRewardClaimed).to.equal(true);
+      expect(settled.coWinnersClaimed).to.equal(1);
+    });
+  });
+
+  describe("Host Correct Answer Verification", () => {
+    // Plaintext the stored correct answer decrypts to (mirrors the circuit's nonce scheme)
+    const storedPlaintext = async (questionBlock: PublicKey) => {
+      const block = await program.account.questionBlock.fetch(questionBlock);
+      const nonceBytes = block.nonce.toArrayLike(Buffer, "le", 16);
+      return Buffer.from(block.encryptedYCoordinate.map((byte, i) => (byte - nonceBytes[i % 16]) & 0xff));
+    };
+
+    const verify = async (owner: Keypair, quizSet: PublicKey, plaintext: Buffer) => {
+      const privateKey = x25519.utils.randomPrivateKey();
+      const publicKey = x25519.getPublicKey(privateKey);
+      const mxePublicKey = await getMXEPublicKey(provider, program.programId);
+      const cipher = new RescueCipher(x25519.getSharedSecret(privateKey, mxePublicKey!));
+
+      const words = [0, 1, 2, 3].map((w) => BigInt("0x" + Buffer.from(plaintext.subarray(w * 16, w * 16 + 16)).reverse().toString("hex")));
+      const nonce = randomBytes(16);
+      const ciphertext = cipher.encrypt(words, nonce);
+
+      const computationOffset = newComputationOffset();
+      const verified = awaitEvent("correctAnswerVerified");
+      await program.methods
+        .verifyCorrectAnswer(
+          computationOffset,
+          1,
+          ciphertext as any,
+          Array.from(publicKey),
+          new BN(nonce, "le")
+        )
+        .accountsPartial({
+          payer: owner.publicKey,
+          questionBlock: questionBlockAddress(quizSet, 1),
+          quizSet,
+          ...queueAccounts(computationOffset, "verify_correct_answer"),
+        })
+        .signers([owner])
+        .rpc({ commitment: "confirmed" });
+
+      await awaitComputationFinalization(provider, computationOffset, program.programId, "confirmed");
+      return verified;
+    };
+
+    it("Should report whether the host's plaintext matches without revealing it", async () => {
+      const compDefAccount = queueAccounts(new BN(0), "verify_correct_answer").compDefAccount;
+      if (!(await provider.connection.getAccountInfo(compDefAccount))) {
+        await program.methods
+          .initVerifyCorrectAnswerCompDef()
+          .accountsPartial({ compDefAccount, mxeAccount: getMXEAccAddress(program.programId) })
+          .rpc();
+      }
+
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 191);
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      await program.methods
+        .setWinnerForDevnet(["a", "b", "c"], ["a", "b", "c"])
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+
+      const plaintext = await storedPlaintext(questionBlockAddress(quizSet, 1));
+
+      const matching = await verify(owner, quizSet, plaintext);
+      expect(matching.ok).to.equal(true);
+      expect(matching.questionIndex).to.equal(1);
+
+      const wrong = Buffer.from(plaintext);
+      wrong[0] ^= 0xff;
+      const mismatching = await verify(owner, quizSet, wrong);
+      expect(mismatching.ok).to.equal(false);
+    });
+  });
+
+  describe("Missing Question Block", () => {
+    it("Should reject an answer for an index the author never created", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 201);
+      await addQuestionBlocks(owner, quizSet, 2);
+
+      try {
+        await submitAnswer(player, quizSet, 3, "answer");
+        expect.fail("answering a missing block should fail");
+      } catch (error: any) {
+        expect(error.toString()).to.include("QuestionBlockMissing");
+      }
+    });
+  });
+
+  describe("Weighted Questions", () => {
+    it("Should reject finalizing when weights miss the target", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 211);
+
+      await program.methods
+        .setTotalWeight(100)
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+
+      try {
+        await addQuestionBlocks(owner, quizSet, 3, [20, 30, 40]);
+        expect.fail("weights summing to 90 should not finalize a 100-point quiz");
+      } catch (error: any) {
+        expect(error.toString()).to.include("WeightMismatch");
+      }
+    });
+
+    it("Should add each correct question's weight to the player's score", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 212);
+
+      await program.methods
+        .setTotalWeight(100)
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3, [20, 30, 50]);
+
+      const quizSetAccount = await program.account.quizSet.fetch(quizSet);
+      expect(quizSetAccount.isInitialized).to.equal(true);
+      expect(quizSetAccount.assignedWeight).to.equal(100);
+
+      for (const questionIndex of [1, 3]) {
+        const offset = await submitAnswer(player, quizSet, questionIndex, "answer");
+        await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+      }
+
+      const progress = await program.account.playerProgress.fetch(playerProgressAddress(player.publicKey, quizSet));
+      expect(progress.correctCount).to.equal(2);
+      expect(progress.score).to.equal(20 + 50);
+    });
+  });
+
+  describe("Completion Attestation", () => {
+    it("Should emit an attestation whose digest matches the program-written record", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 221);
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      for (const questionIndex of [1, 2, 3]) {
+        const offset = await submitAnswer(player, quizSet, questionIndex, "answer");
+        await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+      }
+
+      const [attestation] = PublicKey.findProgramAddressSync(
+        [Buffer.from("attestation"), quizSet.toBuffer(), player.publicKey.toBuffer()],
+        program.programId
+      );
+
+      const attested = awaitEvent("completionAttestation");
+      await program.methods
+        .attestCompletion()
+        .accountsPartial({
+          attestation,
+          playerProgress: playerProgressAddress(player.publicKey, quizSet),
+          quizSet,
+          user: player.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc();
+      const event = await attested;
+
+      expect(event.attestation.toString()).to.equal(attestation.toString());
+      expect(event.user.toString()).to.equal(player.publicKey.toString());
+      expect(event.quizSet.toString()).to.equal(quizSet.toString());
+      expect(event.correctCount).to.equal(3);
+      expect(event.questionCount).to.equal(3);
+      expect(event.isWinner).to.equal(true);
+
+      const score = Buffer.alloc(4);
+      score.writeUInt32LE(event.score);
+      const expectedDigest = createHash("sha256")
+        .update(Buffer.from("k3hoot:attest"))
+        .update(player.publicKey.toBuffer())
+        .update(quizSet.toBuffer())
+        .update(score)
+        .update(Buffer.from([event.correctCount, event.questionCount, 1]))
+        .update(event.attestedAt.toArrayLike(Buffer, "le", 8))
+        .digest();
+      expect(Buffer.from(event.digest).equals(expectedDigest)).to.equal(true);
+
+      const record = await program.account.attestation.fetch(attestation);
+      expect(Buffer.from(record.digest).equals(expectedDigest)).to.equal(true);
+    });
+  });
+
+  describe("Quiz Configuration Validation", () => {
+    const expectError = async (action: Promise<unknown>, errorName: string) => {
+      try {
+        await action;
+        expect.fail(`expected ${errorName}`);
+      } catch (error: any) {
+        expect(error.toString()).to.include(errorName);
+      }
+    };
+
+    it("Should reject a question count above the maximum at creation", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      await expectError(createQuiz(owner, topicPda, 231, 51), "InvalidQuestionCount");
+    });
+
+    it("Should reject inconsistent combinations set after creation", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 232);
+      const configure = (method: any) =>
+        method.accountsPartial({ quizSet, authority: owner.publicKey }).signers([owner]).rpc();
+
+      // Runner-up carve-out would swallow the whole prize
+      await expectError(configure(program.methods.setRunnerUpAmount(new BN(10_000_000))), "InvalidRunnerUpAmount");
+
+      // Grace without a window to extend
+      await expectError(configure(program.methods.setClaimWindow(new BN(0), new BN(60))), "InvalidClaimWindow");
+
+      // Announcement scheduled before the quiz existed
+      await expectError(configure(program.methods.setAnnounceAfter(new BN(1))), "InvalidAnnounceTime");
+
+      // Unsupported answer buffer
+      await expectError(configure(program.methods.setBufferSize(48)), "InvalidBufferSize");
+
+      // Target score below weight already assigned to blocks
+      await addQuestionBlocks(owner, quizSet, 2, [30, 30]);
+      await expectError(configure(program.methods.setTotalWeight(50)), "WeightMismatch");
+
+      const quizSetAccount = await program.account.quizSet.fetch(quizSet);
+      expect(quizSetAccount.runnerUpAmount.toNumber()).to.equal(0);
+      expect(quizSetAccount.totalWeight).to.equal(0);
+    });
+  });
+
+  describe("Operator Fee Top-Up", () => {
+    it("Should fund the fee pool, tally the operator's ledger, and keep queues working", async () => {
+      const operator = await fundedKeypair();
+      const [feeLedger] = PublicKey.findProgramAddressSync(
+        [Buffer.from("fee_topup"), operator.publicKey.toBuffer()],
+        program.programId
+      );
+      const topup = async (amount: number) => {
+        const event = awaitEvent("feesToppedUp");
+        await program.methods
+          .operatorTopupFees(new BN(amount))
+          .accountsPartial({ operator: operator.publicKey, feeLedger, systemProgram: SystemProgram.programId })
+          .signers([operator])
+          .rpc();
+        return event;
+      };
+
+      const first = await topup(10_000_000);
+      const poolBefore = first.poolBalance.toNumber();
+      const second = await topup(5_000_000);
+
+      expect(second.poolAccount.toString()).to.equal(first.poolAccount.toString());
+      expect(second.poolBalance.toNumber() - poolBefore).to.equal(5_000_000);
+      expect(await provider.connection.getBalance(second.poolAccount)).to.be.at.least(second.poolBalance.toNumber());
+
+      const ledger = await program.account.feeTopupLedger.fetch(feeLedger);
+      expect(ledger.totalToppedUp.toNumber()).to.equal(15_000_000);
+      expect(ledger.topupCount).to.equal(2);
+
+      // With the pool funded, a computation queues and finalizes
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 241);
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const offset = await submitAnswer(player, quizSet, 1, "answer");
+      await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+    });
+  });
+
+  describe("Question Expiry", () => {
+    it("Should reject answers to an expired question", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 251);
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      // Expiry can be changed on a live quiz; this week's question has lapsed
+      await program.methods
+        .setQuestionExpiry(new BN(Math.floor(Date.now() / 1000) - 60))
+        .accountsPartial({ questionBlock: questionBlockAddress(quizSet, 1), quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+
+      try {
+        await submitAnswer(player, quizSet, 1, "answer");
+        expect.fail("expired question should not accept answers");
+      } catch (error: any) {
+        expect(error.toString()).to.include("QuestionExpired");
+      }
+
+      // Other questions are unaffected
+      const offset = await submitAnswer(player, quizSet, 2, "answer");
+      await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+    });
+  });
+
+  describe("Topic Quiz Summaries", () => {
+    // Mirrors quiz_summary_record; no announcement delay or claim window is set,
+    // so a quiz is claimable exactly when it has a winner
+    const summaryHash = async (quizSets: PublicKey[]) => {
+      const records = [];
+      for (const quizSet of quizSets) {
+        const account = await program.account.quizSet.fetch(quizSet);
+        const hasWinner = account.winner !== null;
+        records.push(
+          quizSet.toBuffer(),
+          account.rewardAmount.toArrayLike(Buffer, "le", 8),
+          Buffer.from([account.questionCount, Number(account.isInitialized), Number(hasWinner), Number(hasWinner)])
+        );
+      }
+      return createHash("sha256").update(Buffer.concat(records)).digest();
+    };
+
+    const summarize = async (topicPda: PublicKey, quizSets: PublicKey[]) => {
+      const completed = awaitEvent("batchCompleted");
+      const signature = await program.methods
+        .getTopicQuizSummaries()
+        .accountsPartial({ topic: topicPda })
+        .remainingAccounts(quizSets.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })))
+        .rpc({ commitment: "confirmed" });
+      return { event: await completed, signature };
+    };
+
+    it("Should emit one summary per quiz in the topic", async () => {
+      const owner = await fundedKeypair(3);
+      const topicPda = await createTopic(owner);
+      const quizSets = [
+        await createQuiz(owner, topicPda, 5),
+        await createQuiz(owner, topicPda, 6, 4),
+        await createQuiz(owner, topicPda, 7, 3, new BN(20_000_000)),
+      ];
+      await addQuestionBlocks(owner, quizSets[0], 3);
+
+      await program.methods
+        .setWinnerForDevnet(["a", "b", "c"], ["a", "b", "c"])
+        .accountsPartial({ quizSet: quizSets[0], authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+
+      const summaries: any[] = [];
+      const listenerId = program.addEventListener("quizSummary", (event: any) => summaries.push(event));
+
+      await program.methods
+        .getTopicQuizSummaries()
+        .accountsPartial({ topic: topicPda })
+        .remainingAccounts(quizSets.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })))
+        .rpc({ commitment: "confirmed" });
+
+      for (let i = 0; i < 20 && summaries.length < 3; i++) {
+        await sleep(500);
+      }
+      await program.removeEventListener(listenerId);
+
+      expect(summaries).to.have.length(3);
+      expect(summaries.map((s) => s.quizSet.toString())).to.deep.equal(quizSets.map((q) => q.toString()));
+      expect(summaries[0].name).to.equal("Quiz 5");
+      expect(summaries[0].hasWinner).to.equal(true);
+      expect(summaries[0].isClaimable).to.equal(true);
+      expect(summaries[1].questionCount).to.equal(4);
+      expect(summaries[1].hasWinner).to.equal(false);
+      expect(summaries[2].rewardAmount.toNumber()).to.equal(20_000_000);
+    });
+
+    it("Should emit one aggregate event whose hash covers every quiz in the topic", async () => {
+      const owner = await fundedKeypair(3);
+      const topicPda = await createTopic(owner);
+      const quizSets = [
+        await createQuiz(owner, topicPda, 5),
+        await createQuiz(owner, topicPda, 6, 4),
+        await createQuiz(owner, topicPda, 7, 3, new BN(20_000_000)),
+      ];
+      await addQuestionBlocks(owner, quizSets[0], 3);
+
+      await program.methods
+        .setWinnerForDevnet(["a", "b", "c"], ["a", "b", "c"])
+        .accountsPartial({ quizSet: quizSets[0], authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+
+      const { event } = await summarize(topicPda, quizSets);
+      expect(event.count).to.equal(3);
+      expect(Buffer.from(event.summaryHash).equals(await summaryHash(quizSets))).to.equal(true);
+
+      // Order matters: the records are hashed in the order the accounts were passed
+      const reversed = [...quizSets].reverse();
+      expect(Buffer.from(event.summaryHash).equals(await summaryHash(reversed))).to.equal(false);
+    });
+
+    it("Should log a batch of 10 quizzes within the log limits", async () => {
+      const owner = await fundedKeypair(3);
+      const topicPda = await createTopic(owner);
+      const quizSets: PublicKey[] = [];
+      for (let uniqueId = 120; uniqueId < 130; uniqueId++) {
+        quizSets.push(await createQuiz(owner, topicPda, uniqueId));
+      }
+
+      const { event, signature } = await summarize(topicPda, quizSets);
+      expect(event.count).to.equal(10);
+      expect(Buffer.from(event.summaryHash).equals(await summaryHash(quizSets))).to.equal(true);
+
+      const tx = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const logs = tx!.meta!.logMessages!;
+      // Ten summaries plus the aggregate
+      expect(logs.filter((line) => line.startsWith("Program data:"))).to.have.length(11);
+      expect(logs.some((line) => line.includes("Log truncated"))).to.equal(false);
+      // The runtime caps a transaction's logs at 10 KB
+      expect(Buffer.byteLength(logs.join("\n"))).to.be.below(10_000);
+    });
+  });
+
+  describe("Claim Account Aliasing", () => {
+    it("Should reject a claim that passes the vault as the claimer", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 15);
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      await program.methods
+        .setWinnerForDevnet(["a", "b", "c"], ["a", "b", "c"])
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+
+      const vault = vaultAddress(quizSet);
+      const vaultBefore = await provider.connection.getBalance(vault);
+
+      let rejected = false;
+      try {
+        await program.methods
+          .claimReward(null)
+          .accountsPartial({ quizSet, vault, claimer: vault, systemProgram: SystemProgram.programId })
+          .remainingAccounts(questionBlockMetas(quizSet, [1, 2, 3]))
+          .signers([owner])
+          .rpc();
+      } catch (error: any) {
+        rejected = true;
+      }
+      expect(rejected).to.equal(true);
+
+      expect(await provider.connection.getBalance(vault)).to.equal(vaultBefore);
+      const quizSetAccount = await program.account.quizSet.fetch(quizSet);
+      expect(quizSetAccount.isRewardClaimed).to.equal(false);
+    });
+  });
+
+  describe("Token-Gated Quizzes", () => {
+    it("Should block non-holders from answering and claiming", async () => {
+      const owner = await fundedKeypair();
+      const holder = await fundedKeypair();
+      const outsider = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 25);
+
+      const gateMint = await createMint(provider.connection, owner, owner.publicKey, null, 0);
+      const holderTokens = await getOrCreateAssociatedTokenAccount(provider.connection, owner, gateMint, holder.publicKey);
+      const outsiderTokens = await getOrCreateAssociatedTokenAccount(provider.connection, owner, gateMint, outsider.publicKey);
+      await mintTo(provider.connection, owner, gateMint, holderTokens.address, owner, 1);
+
+      await program.methods
+        .setGateMint(gateMint)
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const answer = async (player: Keypair, gateTokenAccount: PublicKey) => {
+        const salt = await commitAnswer(player, quizSet, 1, "answer");
+        const computationOffset = await computationOffsetFor(player.publicKey, quizSet, 1);
+        return program.methods
+          .revealAnswer(computationOffset, "answer", 1, salt, false)
+          .accountsPartial({
+            payer: player.publicKey,
+            answerSubmission: answerSubmissionAddress(computationOffset),
+            playerProgress: playerProgressAddress(player.publicKey, quizSet),
+            answerCommitment: answerCommitmentAddress(player.publicKey, quizSet, 1),
+            questionBlock: questionBlockAddress(quizSet, 1),
+            quizSet,
+            gateTokenAccount,
+            ...queueAccounts(computationOffset, "validate_answer"),
+          })
+          .signers([player])
+          .rpc({ commitment: "confirmed" });
+      };
+
+      try {
+        await answer(outsider, outsiderTokens.address);
+        expect.fail("an empty gate token account should not let a player answer");
+      } catch (error: any) {
+        expect(error.toString()).to.include("GateRequirementNotMet");
+      }
+
+      await answer(holder, holderTokens.address);
+
+      // Even if made winner, a non-holder cannot claim
+      await program.methods
+        .setWinnerForUser(outsider.publicKey, 3)
+        .accountsPartial({ quizSet, setter: owner.publicKey, systemProgram: SystemProgram.programId })
+        .signers([owner])
+        .rpc();
+
+      try {
+        await program.methods
+          .claimReward(null)
+          .accountsPartial({
+            quizSet,
+            vault: vaultAddress(quizSet),
+            claimer: outsider.publicKey,
+            gateTokenAccount: outsiderTokens.address,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(questionBlockMetas(quizSet, [1, 2, 3]))
+          .signers([outsider])
+          .rpc();
+        expect.fail("a non-holder should not be able to claim");
+      } catch (error: any) {
+        expect(error.toString()).to.include("GateRequirementNotMet");
+      }
+    });
+  });
+
+  describe("Deterministic Computation Offsets", () => {
+    it("Should give concurrent players distinct offsets and reject borrowed ones", async () => {
+      const owner = await fundedKeypair();
+      const alice = await fundedKeypair();
+      const bob = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 35);
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const aliceOffset = await computationOffsetFor(alice.publicKey, quizSet, 1);
+      const bobOffset = await computationOffsetFor(bob.publicKey, quizSet, 1);
+      expect(aliceOffset.eq(bobOffset)).to.equal(false);
+
+      const queue = async (player: Keypair, computationOffset: BN) => {
+        const salt = await commitAnswer(player, quizSet, 1, "answer");
+        return program.methods
+          .revealAnswer(computationOffset, "answer", 1, salt, false)
+          .accountsPartial({
+            payer: player.publicKey,
+            answerSubmission: answerSubmissionAddress(computationOffset),
+            playerProgress: playerProgressAddress(player.publicKey, quizSet),
+            answerCommitment: answerCommitmentAddress(player.publicKey, quizSet, 1),
+            questionBlock: questionBlockAddress(quizSet, 1),
+            quizSet,
+            ...queueAccounts(computationOffset, "validate_answer"),
+          })
+          .signers([player])
+          .rpc({ commitment: "confirmed" });
+      };
+
+      // Bob can't take Alice's slot
+      try {
+        await queue(bob, aliceOffset);
+        expect.fail("an offset derived for another player should be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidComputationOffset");
+      }
+
+      await Promise.all([queue(alice, aliceOffset), queue(bob, bobOffset)]);
+
+      // Each submission advances the counter, so the next answer needs a fresh offset
+      const nextAliceOffset = await computationOffsetFor(alice.publicKey, quizSet, 1);
+      expect(nextAliceOffset.eq(aliceOffset)).to.equal(false);
+      const progress = await program.account.playerProgress.fetch(playerProgressAddress(alice.publicKey, quizSet));
+      expect(progress.submissionCount).to.equal(1);
+    });
+  });
+
+  describe("Practice Quiz Reset", () => {
+    const reset = (player: Keypair, quizSet: PublicKey) =>
+      program.methods
+        .resetPlayerProgress()
+        .accountsPartial({
+          playerProgress: playerProgressAddress(player.publicKey, quizSet),
+          quizSet,
+          player: player.publicKey,
+        })
+        .signers([player])
+        .rpc();
+
+    it("Should let a player reset a practice quiz and answer again", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 45);
+
+      await program.methods
+        .setPractice(true)
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const progressAddress = playerProgressAddress(player.publicKey, quizSet);
+      let offset = await submitAnswer(player, quizSet, 1, "answer");
+      await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+      expect((await program.account.playerProgress.fetch(progressAddress)).correctCount).to.equal(1);
+
+      await reset(player, quizSet);
+      const fresh = await program.account.playerProgress.fetch(progressAddress);
+      expect(fresh.correctCount).to.equal(0);
+      expect(fresh.score).to.equal(0);
+      expect(fresh.submissionCount).to.equal(1);
+
+      offset = await submitAnswer(player, quizSet, 1, "answer");
+      await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+      expect((await program.account.playerProgress.fetch(progressAddress)).correctCount).to.equal(1);
+    });
+
+    it("Should refuse to reset a quiz that isn't a practice quiz", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 46);
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const offset = await submitAnswer(player, quizSet, 1, "answer");
+      await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+
+      try {
+        await reset(player, quizSet);
+        expect.fail("resetting a competitive quiz should be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("ResetNotAllowed");
+      }
+    });
+
+    it("Should keep an eliminated player out across a reset", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 169);
+      for (const method of [program.methods.setPractice(true), program.methods.setSuddenDeath(true)]) {
+        await method.accountsPartial({ quizSet, authority: owner.publicKey }).signers([owner]).rpc();
+      }
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const offset = await submitAnswer(player, quizSet, 1, "nope");
+      await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+
+      try {
+        await reset(player, quizSet);
+        expect.fail("an eliminated player should stay eliminated");
+      } catch (error: any) {
+        expect(error.toString()).to.include("PlayerEliminated");
+      }
+    });
+  });
+
+  describe("Topic Reward Currency Statistics", () => {
+    it("Should track escrowed SOL across creation and claim", async () => {
+      const owner = await fundedKeypair(3);
+      const topicPda = await createTopic(owner);
+      const [rewardStats] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reward_stats"), topicPda.toBuffer()],
+        program.programId
+      );
+
+      // Created with the topic
+      expect((await program.account.topicRewardStats.fetch(rewardStats)).topic.toString()).to.equal(topicPda.toString());
+
+      const createTracked = async (uniqueId: number, rewardAmount: BN) => {
+        const quizSet = quizSetAddress(owner.publicKey, uniqueId);
+        await program.methods
+          .createQuizSet(`Quiz ${uniqueId}`, 3, uniqueId, rewardAmount, rewardAmount, new BN(0))
+          .accountsPartial({
+            quizSet,
+            topic: topicPda,
+            vault: vaultAddress(quizSet),
+            rewardStats,
+            authority: owner.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([owner])
+          .rpc();
+        return quizSet;
+      };
+
+      const first = await createTracked(32, new BN(10_000_000));
+      await createTracked(33, new BN(20_000_000));
+      await addQuestionBlocks(owner, first, 3);
+
+      await program.methods
+        .setWinnerForDevnet(["a", "b", "c"], ["a", "b", "c"])
+        .accountsPartial({ quizSet: first, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+
+      await program.methods
+        .claimReward(null)
+        .accountsPartial({
+          quizSet: first,
+          vault: vaultAddress(first),
+          claimer: owner.publicKey,
+          rewardStats,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(questionBlockMetas(first, [1, 2, 3]))
+        .signers([owner])
+        .rpc();
+
+      const totals = awaitEvent("topicRewardTotal");
+      await program.methods.getTopicRewardStats().accountsPartial({ rewardStats }).rpc();
+      const sol = await totals;
+
+      expect(sol.isNativeSol).to.equal(true);
+      expect(sol.totalDeposited.toNumber()).to.equal(30_000_000);
+      expect(sol.escrowed.toNumber()).to.equal(20_000_000);
+    });
+  });
+
+  describe("Question Index Seed Bounds", () => {
+    it("Should derive the last allowed question block from its single-byte seed", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 75, 50);
+
+      const addBlock = (index: number) =>
+        program.methods
+          .addEncryptedQuestionBlock(
+            index,
+            Array.from(new Uint8Array(64).fill(1)),
+            Array.from(new Uint8Array(64).fill(2)),
+            Array.from(new Uint8Array(32).fill(3)),
+            new BN(index),
+            0,
+            false,
+            0
+          )
+          .accountsPartial({
+            questionBlock: questionBlockAddress(quizSet, index),
+            quizSet,
+            authority: owner.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([owner])
+          .rpc();
+
+      try {
+        await addBlock(51);
+        expect.fail("an index past question_count should be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidQuestionIndex");
+      }
+
+      await addBlock(50);
+      const [expected] = PublicKey.findProgramAddressSync(
+        [Buffer.from("question_block"), quizSet.toBuffer(), Buffer.from([50])],
+        program.programId
+      );
+      const block = await program.account.questionBlock.fetch(expected);
+      expect(block.questionIndex).to.equal(50);
+      expect(block.quizSet.toString()).to.equal(quizSet.toString());
+    });
+  });
+
+  describe("Answer Submission Fee", () => {
+    it("Should collect the configured fee on every submission", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 85);
+      const answerFee = 1_000_000;
+
+      await program.methods
+        .setAnswerFee(new BN(answerFee))
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const collected: any[] = [];
+      const listenerId = program.addEventListener("answerFeeCollected", (event: any) => collected.push(event));
+
+      const before = await provider.connection.getBalance(player.publicKey);
+      for (const questionIndex of [1, 2]) {
+        await submitAnswer(player, quizSet, questionIndex, "answer");
+      }
+      const after = await provider.connection.getBalance(player.publicKey);
+
+      for (let i = 0; i < 20 && collected.length < 2; i++) {
+        await sleep(500);
+      }
+      await program.removeEventListener(listenerId);
+
+      expect(collected).to.have.length(2);
+      for (const event of collected) {
+        expect(event.payer.toString()).to.equal(player.publicKey.toString());
+        expect(event.amount.toNumber()).to.equal(answerFee);
+      }
+      expect(before - after).to.be.at.least(2 * answerFee);
+    });
+  });
+
+
+  describe("Claim To Destination", () => {
+    it("Should pay the winner's reward to a separate destination wallet", async () => {
+      const owner = await fundedKeypair();
+      const coldWallet = Keypair.generate().publicKey;
+      const topicPda = await createTopic(owner);
+      const rewardAmount = 10_000_000;
+      const quizSet = await createQuiz(owner, topicPda, 95, 3, new BN(rewardAmount));
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      await program.methods
+        .setWinnerForDevnet(["a", "b", "c"], ["a", "b", "c"])
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+
+      const outsider = await fundedKeypair();
+      try {
+        await claimReward(outsider, quizSet, undefined, coldWallet);
+        expect.fail("only the recorded winner may direct the payout");
+      } catch (error: any) {
+        expect(error.toString()).to.include("NotWinner");
+      }
+
+      const claimerBefore = await provider.connection.getBalance(owner.publicKey);
+      await claimReward(owner, quizSet, undefined, coldWallet);
+
+      expect(await provider.connection.getBalance(coldWallet)).to.equal(rewardAmount);
+      expect(await provider.connection.getBalance(owner.publicKey)).to.be.at.most(claimerBefore);
+      const claimed = await program.account.quizSet.fetch(quizSet);
+      expect(claimed.isRewardClaimed).to.equal(true);
+    });
+  });
+
+
+  describe("Quiz Set Rename", () => {
+    it("Should rename a quiz set to a longer name within its reserved space", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 105);
+
+      const sizeBefore = (await provider.connection.getAccountInfo(quizSet))!.data.length;
+      const longName = "R".repeat(100);
+
+      await program.methods
+        .renameQuizSet(longName)
+        .accountsPartial({ quizSet, authority: owner.publicKey, systemProgram: SystemProgram.programId })
+        .signers([owner])
+        .rpc();
+
+      const renamed = await program.account.quizSet.fetch(quizSet);
+      expect(renamed.name).to.equal(longName);
+      expect((await provider.connection.getAccountInfo(quizSet))!.data.length).to.equal(sizeBefore);
+
+      try {
+        await program.methods
+          .renameQuizSet("R".repeat(101))
+          .accountsPartial({ quizSet, authority: owner.publicKey, systemProgram: SystemProgram.programId })
+          .signers([owner])
+          .rpc();
+        expect.fail("names past the reserved length should be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("NameTooLong");
+      }
+    });
+  });
+
+
+  describe("Live Standings", () => {
+    it("Should emit standings sorted by points for spectators", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 115);
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const players = [await fundedKeypair(), await fundedKeypair(), await fundedKeypair()];
+      // Each player gets a different amount of progress
+      for (const [index, player] of players.entries()) {
+        for (let questionIndex = 1; questionIndex <= index + 1; questionIndex++) {
+          const offset = await submitAnswer(player, quizSet, questionIndex, "answer");
+          await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+        }
+      }
+
+      const progressAccounts = players.map((player) => playerProgressAddress(player.publicKey, quizSet));
+      const expected = await Promise.all(
+        progressAccounts.map((address) => program.account.playerProgress.fetch(address))
+      );
+      expected.sort((a, b) => b.score - a.score || b.correctCount - a.correctCount);
+
+      const standingsPromise = awaitEvent("liveStandings");
+      await program.methods
+        .getLiveStandings()
+        .accountsPartial({ quizSet })
+        .remainingAccounts(
+          // Deliberately unsorted
+          [progressAccounts[1], progressAccounts[0], progressAccounts[2]].map((pubkey) => ({
+            pubkey,
+            isWritable: false,
+            isSigner: false,
+          }))
+        )
+        .rpc();
+      const event = await standingsPromise;
+
+      expect(event.standings).to.have.length(3);
+      event.standings.forEach((entry: any, i: number) => {
+        expect(entry.player.toString()).to.equal(expected[i].player.toString());
+        expect(entry.points).to.equal(expected[i].score);
+        expect(entry.correctCount).to.equal(expected[i].correctCount);
+      });
+      for (let i = 1; i < event.standings.length; i++) {
+        expect(event.standings[i - 1].points).to.be.at.least(event.standings[i].points);
+      }
+    });
+  });
+
+
+  describe("End Run On Wrong Answer", () => {
+    it("Should end the run on a wrong answer but keep the partial score", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 125);
+
+      await program.methods
+        .setEndOnWrong(true)
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const first = await submitAnswer(player, quizSet, 1, "answer");
+      await awaitComputationFinalization(provider, first, program.programId, "confirmed");
+      const before = await program.account.playerProgress.fetch(playerProgressAddress(player.publicKey, quizSet));
+
+      const wrong = await submitAnswer(player, quizSet, 2, "definitely wrong");
+      await awaitComputationFinalization(provider, wrong, program.programId, "confirmed");
+
+      const progress = await program.account.playerProgress.fetch(playerProgressAddress(player.publicKey, quizSet));
+      expect(progress.finished).to.equal(true);
+      expect(progress.eliminated).to.equal(false);
+      expect(progress.wrongCount).to.equal(before.wrongCount + 1);
+      expect(progress.score).to.equal(before.score);
+      expect(progress.correctCount).to.equal(before.correctCount);
+
+      try {
+        await submitAnswer(player, quizSet, 3, "another answer");
+        expect.fail("a finished run should not accept more answers");
+      } catch (error: any) {
+        expect(error.toString()).to.include("RunEnded");
+      }
+    });
+  });
+
+
+  describe("Circuit Crosscheck", () => {
+    // sha256("k3hoot:crosscheck" || result) for bytes 1..=32 and nonce 0x0123456789abcdef
+    const EXPECTED_RESULT_HASH = "e033cbcba3a03fd35dee94088bbce28fb93439c0bd2573db76e30181b85a404e";
+
+    it("Should produce the committed result hash for a fixed sample", async () => {
+      const compDefAccount = queueAccounts(new BN(0), "crosscheck_circuit").compDefAccount;
+      if (!(await provider.connection.getAccountInfo(compDefAccount))) {
+        await program.methods
+          .initCrosscheckCircuitCompDef()
+          .accountsPartial({ compDefAccount, mxeAccount: getMXEAccAddress(program.programId) })
+          .rpc();
+      }
+
+      const sample = Array.from({ length: 32 }, (_, i) => i + 1);
+      const computationOffset = newComputationOffset();
+      const crosscheck = awaitEvent("circuitCrosscheck");
+      await program.methods
+        .crosscheckCircuit(computationOffset, sample, new BN("0123456789abcdef", "hex"))
+        .accountsPartial({ ...queueAccounts(computationOffset, "crosscheck_circuit") })
+        .rpc({ commitment: "confirmed" });
+      await awaitComputationFinalization(provider, computationOffset, program.programId, "confirmed");
+
+      const event = await crosscheck;
+      expect(Buffer.from(event.resultHash).toString("hex")).to.equal(EXPECTED_RESULT_HASH);
+    });
+  });
+
+
+  describe("Bonus Questions", () => {
+    it("Should win on required questions alone while bonus answers add points", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 135, 4);
+      await addQuestionBlocks(owner, quizSet, 4, undefined, [4]);
+
+      const bonusBlock = await program.account.questionBlock.fetch(questionBlockAddress(quizSet, 4));
+      expect(bonusBlock.isBonus).to.equal(true);
+      expect((await program.account.quizSet.fetch(quizSet)).bonusCount).to.equal(1);
+
+      // The bonus plus two required questions is not enough to finish
+      for (const questionIndex of [4, 1, 2]) {
+        const offset = await submitAnswer(player, quizSet, questionIndex, "answer");
+        await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+      }
+      expect((await program.account.quizSet.fetch(quizSet)).winner).to.equal(null);
+
+      const offset = await submitAnswer(player, quizSet, 3, "answer");
+      await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+
+      const quizSetAccount = await program.account.quizSet.fetch(quizSet);
+      expect(quizSetAccount.winner!.toString()).to.equal(player.publicKey.toString());
+
+      const progress = await program.account.playerProgress.fetch(playerProgressAddress(player.publicKey, quizSet));
+      expect(progress.answeredMask.toNumber()).to.equal(0b0111);
+      expect(progress.score).to.equal(4);
+    });
+
+    it("Should reject a quiz made only of bonus questions", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 136);
+
+      try {
+        await addQuestionBlocks(owner, quizSet, 3, undefined, [1, 2, 3]);
+        expect.fail("at least one question must be required");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidBonusCount");
+      }
+    });
+  });
+
+
+  describe("Idempotent Computation Definition Init", () => {
+    it("Should treat re-running a comp def init as a no-op", async () => {
+      const compDefAccount = queueAccounts(new BN(0), "verify_correct_answer").compDefAccount;
+      const init = () =>
+        program.methods
+          .initVerifyCorrectAnswerCompDef()
+          .accountsPartial({ compDefAccount, mxeAccount: getMXEAccAddress(program.programId) })
+          .rpc({ commitment: "confirmed" });
+
+      if (!(await provider.connection.getAccountInfo(compDefAccount))) {
+        await init();
+      }
+      const before = await provider.connection.getAccountInfo(compDefAccount);
+
+      const signature = await init();
+      const tx = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      expect(tx!.meta!.logMessages!.join("\n")).to.include("already initialized, skipping");
+
+      const after = await provider.connection.getAccountInfo(compDefAccount);
+      expect(after!.data.equals(before!.data)).to.equal(true);
+    });
+  });
+
+
+  describe("Penalty-Free Retry Window", () => {
+    it("Should waive the penalty only for retries inside the window", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 145);
+      const retryWindow = 15;
+
+      await program.methods
+        .setRetryWindow(new BN(retryWindow))
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const answerWrong = async () => {
+        const offset = await submitAnswer(player, quizSet, 1, "definitely wrong");
+        await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+        return program.account.playerProgress.fetch(playerProgressAddress(player.publicKey, quizSet));
+      };
+
+      const first = await answerWrong();
+      expect(first.wrongCount).to.equal(1);
+      expect(first.lastWrongQuestion).to.equal(1);
+
+      const quickRetry = await answerWrong();
+      expect(quickRetry.wrongCount).to.equal(1);
+      expect(quickRetry.lastWrongAt.toNumber()).to.equal(first.lastWrongAt.toNumber());
+
+      await sleep((retryWindow + 2) * 1000);
+      const lateRetry = await answerWrong();
+      expect(lateRetry.wrongCount).to.equal(2);
+    });
+
+    it("Should hold off sudden death until the retry has also failed", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 180);
+
+      for (const configure of [program.methods.setRetryWindow(new BN(30)), program.methods.setSuddenDeath(true)]) {
+        await configure.accountsPartial({ quizSet, authority: owner.publicKey }).signers([owner]).rpc();
+      }
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const answer = async (questionIndex: number, text: string) => {
+        const offset = await submitAnswer(player, quizSet, questionIndex, text);
+        await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+        return program.account.playerProgress.fetch(playerProgressAddress(player.publicKey, quizSet));
+      };
+
+      let progress = await answer(1, "definitely wrong");
+      expect(progress.eliminated).to.equal(false);
+      expect(progress.pendingKnockout).to.equal(true);
+
+      progress = await answer(1, "answer");
+      expect(progress.eliminated).to.equal(false);
+      expect(progress.pendingKnockout).to.equal(false);
+      expect(progress.correctCount).to.equal(1);
+
+      await answer(2, "definitely wrong");
+      progress = await answer(2, "still wrong");
+      expect(progress.eliminated).to.equal(true);
+    });
+  });
+
+
+  describe("Completion Topic Linkage", () => {
+    it("Should reject recording a completion against another topic", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const otherTopic = await createTopic(owner, `Other ${Date.now() % 1_000_000}`);
+      const quizSet = await createQuiz(owner, topicPda, 155);
+
+      try {
+        await recordCompletion(player, quizSet, otherTopic);
+        expect.fail("a completion must credit the quiz's own topic");
+      } catch (error: any) {
+        expect(error.toString()).to.include("QuizNotInTopic");
+      }
+
+      const otherTopicAccount = await program.account.topic.fetch(otherTopic);
+      expect(otherTopicAccount.totalParticipants).to.equal(0);
+
+      await recordCompletion(player, quizSet, topicPda);
+    });
+  });
+
+
+  describe("MXE Key Rotation", () => {
+    const encryptWithNonce = (data: Buffer, nonce: BN) => {
+      const nonceBytes = nonce.toArrayLike(Buffer, "le", 16);
+      return Array.from(data, (byte, i) => (byte + nonceBytes[i % 16]) & 0xff);
+    };
+    const decryptWithNonce = (data: number[], nonce: BN) => {
+      const nonceBytes = nonce.toArrayLike(Buffer, "le", 16);
+      return Buffer.from(data.map((byte, i) => (byte - nonceBytes[i % 16]) & 0xff));
+    };
+    const padded = (text: string) => {
+      const buffer = Buffer.alloc(64);
+      buffer.write(text);
+      return buffer;
+    };
+
+    const initRotateBlock = async () => {
+      const compDefAccount = queueAccounts(new BN(0), "rotate_block").compDefAccount;
+      if (!(await provider.connection.getAccountInfo(compDefAccount))) {
+        await program.methods
+          .initRotateBlockCompDef()
+          .accountsPartial({ compDefAccount, mxeAccount: getMXEAccAddress(program.programId) })
+          .rpc();
+      }
+    };
+
+    const addBlocks = async (owner: Keypair, quizSet: PublicKey, key: number[]) => {
+      for (let i = 1; i <= 3; i++) {
+        const nonce = new BN(1000 + i);
+        await program.methods
+          .addEncryptedQuestionBlock(
+            i,
+            encryptWithNonce(padded(`question ${i}`), nonce),
+            encryptWithNonce(padded(`answer${i}`), nonce),
+            key,
+            nonce,
+            0,
+            false,
+            0
+          )
+          .accountsPartial({
+            questionBlock: questionBlockAddress(quizSet, i),
+            quizSet,
+            authority: owner.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([owner])
+          .rpc();
+      }
+    };
+
+    const beginRotation = (owner: Keypair, quizSet: PublicKey, key: number[]) =>
+      program.methods
+        .beginKeyRotation(key)
+        .accountsPartial({ quizSet, questionBlock: questionBlockAddress(quizSet, 1), authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+
+    const rotate = async (owner: Keypair, quizSet: PublicKey, questionIndex: number) => {
+      const computationOffset = newComputationOffset();
+      await program.methods
+        .rotateBlock(computationOffset, questionIndex, new BN(5000 + questionIndex))
+        .accountsPartial({
+          payer: owner.publicKey,
+          questionBlock: questionBlockAddress(quizSet, questionIndex),
+          quizSet,
+          ...queueAccounts(computationOffset, "rotate_block"),
+        })
+        .signers([owner])
+        .rpc({ commitment: "confirmed" });
+      await awaitComputationFinalization(provider, computationOffset, program.programId, "confirmed");
+    };
+
+    it("Should re-encrypt every block under the new key and keep answers valid", async () => {
+      await initRotateBlock();
+
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      // Topics require at least three questions per quiz
+      const quizSet = await createQuiz(owner, topicPda, 165);
+      const oldKey = Array.from(randomBytes(32));
+      await addBlocks(owner, quizSet, oldKey);
+
+      try {
+        await beginRotation(owner, quizSet, oldKey);
+        expect.fail("rotating to the current key must be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("SameRotationKey");
+      }
+
+      const newKey = Array.from(randomBytes(32));
+      await beginRotation(owner, quizSet, newKey);
+
+      try {
+        await submitAnswer(player, quizSet, 1, "answer1");
+        expect.fail("answers should be paused during a rotation");
+      } catch (error: any) {
+        expect(error.toString()).to.include("KeyRotationInProgress");
+      }
+
+      for (let i = 1; i <= 3; i++) {
+        await rotate(owner, quizSet, i);
+      }
+
+      const rotated = await program.account.quizSet.fetch(quizSet);
+      expect(rotated.rotationInProgress).to.equal(false);
+      expect(rotated.rotatedCount).to.equal(3);
+
+      for (let i = 1; i <= 3; i++) {
+        const block = await program.account.questionBlock.fetch(questionBlockAddress(quizSet, i));
+        expect(Array.from(block.arciumPubkey)).to.deep.equal(newKey);
+        expect(block.nonce.toNumber()).to.equal(5000 + i);
+        expect(decryptWithNonce(block.encryptedYCoordinate, block.nonce).equals(padded(`answer${i}`))).to.equal(true);
+        expect(decryptWithNonce(block.encryptedXCoordinate, block.nonce).equals(padded(`question ${i}`))).to.equal(true);
+      }
+
+      const verified = awaitEvent("answerVerifiedEvent");
+      const offset = await submitAnswer(player, quizSet, 1, "answer1");
+      await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+      expect((await verified).isCorrect).to.equal(true);
+
+      try {
+        await beginRotation(owner, quizSet, newKey);
+        expect.fail("the quiz is already on the new key");
+      } catch (error: any) {
+        expect(error.toString()).to.include("SameRotationKey");
+      }
+    });
+
+    it("Should resume answers after an abort and pick the rotation up again later", async () => {
+      await initRotateBlock();
+
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 177);
+      await addBlocks(owner, quizSet, Array.from(randomBytes(32)));
+
+      const newKey = Array.from(randomBytes(32));
+      await beginRotation(owner, quizSet, newKey);
+      await rotate(owner, quizSet, 1);
+
+      const aborted = awaitEvent("keyRotationAborted");
+      await program.methods
+        .abortKeyRotation()
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      expect((await aborted).rotatedCount).to.equal(1);
+      expect((await program.account.quizSet.fetch(quizSet)).rotationInProgress).to.equal(false);
+
+      // Same key again: block 1 stays done, only 2 and 3 are left
+      await beginRotation(owner, quizSet, newKey);
+      await rotate(owner, quizSet, 2);
+      await rotate(owner, quizSet, 3);
+
+      const rotated = await program.account.quizSet.fetch(quizSet);
+      expect(rotated.rotationInProgress).to.equal(false);
+      expect(rotated.rotatedCount).to.equal(3);
+    });
+  });
+
+
+  describe("Reveal Correct Answer On Wrong", () => {
+    it("Should reveal the correct answer only on unrewarded practice quizzes", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+
+      const rewarded = await createQuiz(owner, topicPda, 175);
+      await program.methods
+        .setPractice(true)
+        .accountsPartial({ quizSet: rewarded, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      try {
+        await program.methods
+          .setRevealOnWrong(true)
+          .accountsPartial({ quizSet: rewarded, authority: owner.publicKey })
+          .signers([owner])
+          .rpc();
+        expect.fail("rewarded quizzes must not reveal answers, even in practice mode");
+      } catch (error: any) {
+        expect(error.toString()).to.include("RevealRequiresPractice");
+      }
+
+      // A zero reward creates a practice quiz that can't be switched to a real one
+      const practice = await createQuiz(owner, topicPda, 176, 3, new BN(0));
+      expect((await program.account.quizSet.fetch(practice)).isPractice).to.equal(true);
+      await program.methods
+        .setRevealOnWrong(true)
+        .accountsPartial({ quizSet: practice, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      try {
+        await program.methods
+          .setPractice(false)
+          .accountsPartial({ quizSet: practice, authority: owner.publicKey })
+          .signers([owner])
+          .rpc();
+        expect.fail("an unrewarded quiz must stay in practice mode");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidRewardAmount");
+      }
+      await addQuestionBlocks(owner, practice, 3);
+
+      const revealed = awaitEvent("correctAnswerRevealed");
+      let offset = await submitAnswer(player, practice, 1, "definitely wrong");
+      await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+      const event = await revealed;
+      expect(event.player.toString()).to.equal(player.publicKey.toString());
+      expect(event.questionIndex).to.equal(1);
+      expect(event).to.not.have.property("correctAnswer");
+
+      // The answer is only delivered encrypted to the player, without ending their run
+      const compDefAccount = queueAccounts(new BN(0), "reveal_answer").compDefAccount;
+      if (!(await provider.connection.getAccountInfo(compDefAccount))) {
+        await program.methods
+          .initRevealAnswerCompDef()
+          .accountsPartial({ compDefAccount, mxeAccount: getMXEAccAddress(program.programId) })
+          .rpc();
+      }
+      const privateKey = x25519.utils.randomPrivateKey();
+      const computationOffset = newComputationOffset();
+      const mistake = awaitEvent("mistakeRevealed");
+      await program.methods
+        .revealMyMistakes(
+          computationOffset,
+          1,
+          Array.from(x25519.getPublicKey(privateKey)),
+          new BN(randomBytes(16), "le")
+        )
+        .accountsPartial({
+          payer: player.publicKey,
+          questionBlock: questionBlockAddress(practice, 1),
+          quizSet: practice,
+          ...queueAccounts(computationOffset, "reveal_answer"),
+        })
+        .signers([player])
+        .rpc({ commitment: "confirmed" });
+      await awaitComputationFinalization(provider, computationOffset, program.programId, "confirmed");
+      const delivered = await mistake;
+
+      const mxePublicKey = await getMXEPublicKey(provider, program.programId);
+      const cipher = new RescueCipher(x25519.getSharedSecret(privateKey, mxePublicKey!));
+      const bytes = cipher.decrypt(delivered.ciphertexts, Uint8Array.from(delivered.nonce.toArrayLike(Buffer, "le", 16)));
+      expect(Buffer.from(bytes.map((byte) => Number(byte))).toString().replace(/\0+$/, "")).to.equal("answer");
+
+      offset = await submitAnswer(player, practice, 2, "answer");
+      await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+      const progress = await program.account.playerProgress.fetch(playerProgressAddress(player.publicKey, practice));
+      expect(progress.answersRevealed).to.equal(false);
+    });
+  });
+
+
+  describe("Global User Stats", () => {
+    it("Should accumulate completions, wins and claimed rewards across topics", async () => {
+      const owner = await fundedKeypair();
+      const firstTopic = await createTopic(owner, `Global A ${Date.now() % 1_000_000}`);
+      const secondTopic = await createTopic(owner, `Global B ${Date.now() % 1_000_000}`);
+      const rewardAmount = 10_000_000;
+      const firstQuiz = await createQuiz(owner, firstTopic, 185, 3, new BN(rewardAmount));
+      const secondQuiz = await createQuiz(owner, secondTopic, 186);
+      await addQuestionBlocks(owner, firstQuiz, 3);
+
+      await program.methods
+        .setWinnerForDevnet(["a", "b", "c"], ["a", "b", "c"])
+        .accountsPartial({ quizSet: firstQuiz, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await claimReward(owner, firstQuiz);
+
+      await recordCompletion(owner, firstQuiz, firstTopic, new BN(rewardAmount));
+      await recordCompletion(owner, secondQuiz, secondTopic);
+
+      const [globalStats] = PublicKey.findProgramAddressSync(
+        [Buffer.from("global"), owner.publicKey.toBuffer()],
+        program.programId
+      );
+      // Each quiz set counts once, so repeating a record can't inflate the totals
+      try {
+        await recordCompletion(owner, firstQuiz, firstTopic, new BN(rewardAmount));
+        expect.fail("a completion is recorded once per quiz set");
+      } catch (error: any) {
+        expect(error.toString()).to.include("CompletionAlreadyRecorded");
+      }
+
+      const stats = await program.account.globalUserStats.fetch(globalStats);
+      expect(stats.totalCompletions).to.equal(2);
+      expect(stats.totalWins).to.equal(1);
+      expect(stats.totalRewards.toNumber()).to.equal(rewardAmount);
+
+      const event = awaitEvent("userGlobalStats");
+      await program.methods.getUserGlobalStats().accountsPartial({ user: owner.publicKey, globalStats }).rpc();
+      const emitted = await event;
+      expect(emitted.totalCompletions).to.equal(2);
+      expect(emitted.totalRewards.toNumber()).to.equal(rewardAmount);
+    });
+  });
+
+
+  describe("Keeper Settlement", () => {
+    it("Should let any keeper settle a long-abandoned quiz for a fee", async () => {
+      const owner = await fundedKeypair();
+      const keeper = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const rewardAmount = 100_000_000;
+      const quizSet = await createQuiz(owner, topicPda, 195, 3, new BN(rewardAmount));
+
+      for (const configure of [
+        program.methods.setClaimWindow(new BN(1), new BN(0)),
+        program.methods.setKeeperDelay(new BN(2)),
+      ]) {
+        await configure.accountsPartial({ quizSet, authority: owner.publicKey }).signers([owner]).rpc();
+      }
+      await addQuestionBlocks(owner, quizSet, 3);
+      await program.methods
+        .setWinnerForDevnet(["a", "b", "c"], ["a", "b", "c"])
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+
+      const settle = () =>
+        program.methods
+          .keeperSettle()
+          .accountsPartial({
+            quizSet,
+            vault: vaultAddress(quizSet),
+            authority: owner.publicKey,
+            keeper: keeper.publicKey,
+            authorQuota: authorQuotaAddress(owner.publicKey),
+          })
+          .signers([keeper])
+          .rpc();
+
+      try {
+        await settle();
+        expect.fail("a fresh quiz can't be settled by a keeper");
+      } catch (error: any) {
+        expect(error.toString()).to.include("QuizNotAbandoned");
+      }
+
+      await sleep(5000);
+      const keeperBefore = await provider.connection.getBalance(keeper.publicKey);
+      const ownerBefore = await provider.connection.getBalance(owner.publicKey);
+      await settle();
+
+      const keeperFee = rewardAmount / 100;
+      expect(await provider.connection.getBalance(keeper.publicKey)).to.be.greaterThan(keeperBefore + keeperFee - 10_000);
+      expect(await provider.connection.getBalance(owner.publicKey)).to.be.greaterThan(ownerBefore + rewardAmount - keeperFee);
+      expect(await provider.connection.getAccountInfo(vaultAddress(quizSet))).to.equal(null);
+      expect(await provider.connection.getAccountInfo(quizSet)).to.equal(null);
+    });
+
+    it("Should settle a quiz nobody won once its deadline has passed", async () => {
+      const owner = await fundedKeypair();
+      const keeper = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const rewardAmount = 100_000_000;
+      const deadline = Math.floor(Date.now() / 1000) + 5;
+      const quizSet = await createQuiz(owner, topicPda, 179, 3, new BN(rewardAmount), new BN(rewardAmount), new BN(deadline));
+      await program.methods
+        .setKeeperDelay(new BN(2))
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+
+      const settle = () =>
+        program.methods
+          .keeperSettle()
+          .accountsPartial({
+            quizSet,
+            vault: vaultAddress(quizSet),
+            authority: owner.publicKey,
+            keeper: keeper.publicKey,
+            authorQuota: authorQuotaAddress(owner.publicKey),
+          })
+          .signers([keeper])
+          .rpc();
+
+      try {
+        await settle();
+        expect.fail("an open quiz can't be settled by a keeper");
+      } catch (error: any) {
+        expect(error.toString()).to.include("QuizNotAbandoned");
+      }
+
+      await sleep(9000);
+      const ownerBefore = await provider.connection.getBalance(owner.publicKey);
+      await settle();
+
+      const keeperFee = rewardAmount / 100;
+      expect(await provider.connection.getBalance(owner.publicKey)).to.be.greaterThan(ownerBefore + rewardAmount - keeperFee);
+      expect(await provider.connection.getAccountInfo(quizSet)).to.equal(null);
+    });
+  });
+
+
+  describe("Multiple-Choice Option Validation", () => {
+    const encryptQuestion = async (owner: Keypair, questionBlock: PublicKey, options: string[]) => {
+      const computationOffset = newComputationOffset();
+      return program.methods
+        .encryptQuizData(computationOffset, "Capital of France?", options as any, "Paris", new BN(42), 0)
+        .accountsPartial({
+          payer: owner.publicKey,
+          questionBlock,
+          ...queueAccounts(computationOffset, "encrypt_quiz"),
+        })
+        .signers([owner])
+        .rpc();
+    };
+
+    it("Should reject duplicate and empty options", async () => {
+      const compDefAccount = queueAccounts(new BN(0), "encrypt_quiz").compDefAccount;
+      await program.methods
+        .initEncryptQuizCompDef()
+        .accountsPartial({ compDefAccount, mxeAccount: getMXEAccAddress(program.programId) })
+        .rpc();
+
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 205);
+      await addQuestionBlocks(owner, quizSet, 1);
+      const questionBlock = questionBlockAddress(quizSet, 1);
+
+      try {
+        await encryptQuestion(owner, questionBlock, ["Paris", "Lyon", "paris", "Nice"]);
+        expect.fail("duplicate options should be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("DuplicateOption");
+      }
+
+      try {
+        await encryptQuestion(owner, questionBlock, ["Paris", "Lyon", " ", "Nice"]);
+        expect.fail("empty options should be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("EmptyOption");
+      }
+    });
+  });
+
+
+  describe("Deferred Quiz Funding", () => {
+    it("Should block answers until a partially funded quiz is fully funded", async () => {
+      const owner = await fundedKeypair();
+      const sponsor = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const rewardAmount = 20_000_000;
+      const quizSet = await createQuiz(owner, topicPda, 215, 3, new BN(rewardAmount), new BN(0));
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const fund = (amount: number) =>
+        program.methods
+          .fundQuiz(new BN(amount))
+          .accountsPartial({ quizSet, vault: vaultAddress(quizSet), funder: sponsor.publicKey })
+          .signers([sponsor])
+          .rpc();
+
+      expect((await program.account.quizSet.fetch(quizSet)).isFunded).to.equal(false);
+      try {
+        await submitAnswer(player, quizSet, 1, "answer");
+        expect.fail("answers should wait for funding");
+      } catch (error: any) {
+        expect(error.toString()).to.include("QuizNotFunded");
+      }
+
+      await fund(rewardAmount / 2);
+      expect((await program.account.quizSet.fetch(quizSet)).isFunded).to.equal(false);
+
+      try {
+        await fund(rewardAmount);
+        expect.fail("funding past the reward should be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidFundingAmount");
+      }
+
+      const vaultBefore = await provider.connection.getBalance(vaultAddress(quizSet));
+      await fund(rewardAmount / 2);
+      const funded = await program.account.quizSet.fetch(quizSet);
+      expect(funded.isFunded).to.equal(true);
+      expect(funded.fundedAmount.toNumber()).to.equal(rewardAmount);
+      expect(await provider.connection.getBalance(vaultAddress(quizSet))).to.equal(vaultBefore + rewardAmount / 2);
+
+      const offset = await submitAnswer(player, quizSet, 1, "answer");
+      await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+    });
+  });
+
+
+  describe("Claim Receipts", () => {
+    it("Should keep the receipt and refuse to recreate a closed quiz set at the same address", async () => {
+      const owner = await fundedKeypair(5);
+      const keeper = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+
+      const setUpWonQuiz = async () => {
+        const quizSet = await createQuiz(owner, topicPda, 225);
+        for (const configure of [
+          program.methods.setClaimWindow(new BN(1), new BN(0)),
+          program.methods.setKeeperDelay(new BN(1)),
+        ]) {
+          await configure.accountsPartial({ quizSet, authority: owner.publicKey }).signers([owner]).rpc();
+        }
+        await addQuestionBlocks(owner, quizSet, 3);
+        await program.methods
+          .setWinnerForDevnet(["a", "b", "c"], ["a", "b", "c"])
+          .accountsPartial({ quizSet, authority: owner.publicKey })
+          .signers([owner])
+          .rpc();
+        return quizSet;
+      };
+
+      const quizSet = await setUpWonQuiz();
+      await claimReward(owner, quizSet);
+
+      const [receipt] = PublicKey.findProgramAddressSync(
+        [Buffer.from("claim"), quizSet.toBuffer(), owner.publicKey.toBuffer()],
+        program.programId
+      );
+      const receiptAccount = await program.account.claimReceipt.fetch(receipt);
+      expect(receiptAccount.claimer.toString()).to.equal(owner.publicKey.toString());
+      expect(receiptAccount.amount.toNumber()).to.equal(10_000_000);
+
+      // Settle and close the quiz; its id is retired, so the receipt can't be
+      // sidestepped by recreating the quiz at the same address
+      await sleep(4000);
+      await program.methods
+        .keeperSettle()
+        .accountsPartial({
+          quizSet,
+          vault: vaultAddress(quizSet),
+          authority: owner.publicKey,
+          keeper: keeper.publicKey,
+          authorQuota: authorQuotaAddress(owner.publicKey),
+        })
+        .signers([keeper])
+        .rpc();
+      expect((await program.account.claimReceipt.fetch(receipt)).amount.toNumber()).to.equal(10_000_000);
+
+      try {
+        await setUpWonQuiz();
+        expect.fail("a closed quiz set's address must not be reused");
+      } catch (error: any) {
+        expect(error.toString()).to.include("QuizIdRetired");
+      }
+    });
+  });
+
+
+  describe("Progressive Hints", () => {
+    it("Should reveal hints in order with cumulative penalties", async () => {
+      const compDefAccount = queueAccounts(new BN(0), "reveal_hint").compDefAccount;
+      if (!(await provider.connection.getAccountInfo(compDefAccount))) {
+        await program.methods
+          .initRevealHintCompDef()
+          .accountsPartial({ compDefAccount, mxeAccount: getMXEAccAddress(program.programId) })
+          .rpc();
+      }
+
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 245);
+      await program.methods
+        .setTotalWeight(30)
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3, [10, 10, 10]);
+
+      const hintNonce = new BN(4242);
+      const nonceBytes = hintNonce.toArrayLike(Buffer, "le", 16);
+      const hints = ["starts with a", "five letters", "rhymes with cancer"];
+      const encryptedHints = hints.map((text) => {
+        const buffer = Buffer.alloc(32);
+        buffer.write(text);
+        return Array.from(buffer, (byte, i) => (byte + nonceBytes[i % 16]) & 0xff);
+      });
+      await program.methods
+        .setQuestionHints(encryptedHints, hintNonce)
+        .accountsPartial({ questionBlock: questionBlockAddress(quizSet, 2), quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+
+      const offset = await submitAnswer(player, quizSet, 1, "answer");
+      await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+
+      // Hints come back encrypted to the requesting player's key
+      const privateKey = x25519.utils.randomPrivateKey();
+      const mxePublicKey = await getMXEPublicKey(provider, program.programId);
+      const cipher = new RescueCipher(x25519.getSharedSecret(privateKey, mxePublicKey!));
+      const queueHint = (computationOffset: BN, payer: Keypair = player) =>
+        program.methods
+          .requestNextHint(computationOffset, 2, Array.from(x25519.getPublicKey(privateKey)), new BN(randomBytes(16), "le"))
+          .accountsPartial({
+            payer: payer.publicKey,
+            questionBlock: questionBlockAddress(quizSet, 2),
+            quizSet,
+            ...queueAccounts(computationOffset, "reveal_hint"),
+          })
+          .signers([payer])
+          .rpc({ commitment: "confirmed" });
+      const requestHint = async () => {
+        const computationOffset = newComputationOffset();
+        const revealed = awaitEvent("hintRevealed");
+        await queueHint(computationOffset);
+        await awaitComputationFinalization(provider, computationOffset, program.programId, "confirmed");
+        const event = await revealed;
+        const progress = await program.account.playerProgress.fetch(playerProgressAddress(player.publicKey, quizSet));
+        const bytes = cipher.decrypt(event.ciphertexts, Uint8Array.from(event.nonce.toArrayLike(Buffer, "le", 16)));
+        return { text: Buffer.from(bytes.map((byte) => Number(byte))).toString().replace(/\0+$/, ""), progress };
+      };
+
+      // Hint n costs n points: 10 - 1, then - 2, then - 3
+      const expectedScores = [9, 7, 4];
+      for (let i = 0; i < hints.length; i++) {
+        const { text, progress } = await requestHint();
+        expect(text).to.equal(hints[i]);
+        expect(progress.hintsUsed[1]).to.equal(i + 1);
+        expect(progress.score).to.equal(expectedScores[i]);
+      }
+      expect((await program.account.playerProgress.fetch(playerProgressAddress(player.publicKey, quizSet))).hintPenalty).to.equal(6);
+
+      try {
+        await queueHint(newComputationOffset());
+        expect.fail("only three hints were set");
+      } catch (error: any) {
+        expect(error.toString()).to.include("NoHintsLeft");
+      }
+
+      // A hint taken before scoring anything is owed out of the next points
+      const latecomer = await fundedKeypair();
+      const computationOffset = newComputationOffset();
+      await queueHint(computationOffset, latecomer);
+      await awaitComputationFinalization(provider, computationOffset, program.programId, "confirmed");
+      let progress = await program.account.playerProgress.fetch(playerProgressAddress(latecomer.publicKey, quizSet));
+      expect(progress.score).to.equal(0);
+      expect(progress.hintDebt).to.equal(1);
+
+      const answered = await submitAnswer(latecomer, quizSet, 2, "answer");
+      await awaitComputationFinalization(provider, answered, program.programId, "confirmed");
+      progress = await program.account.playerProgress.fetch(playerProgressAddress(latecomer.publicKey, quizSet));
+      expect(progress.score).to.equal(9);
+      expect(progress.hintDebt).to.equal(0);
+    });
+  });
+
+
+  describe("Winner Requires Initialized Quiz", () => {
+    it("Should refuse to set a winner before every question block is added", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 255);
+      await addQuestionBlocks(owner, quizSet, 2);
+      expect((await program.account.quizSet.fetch(quizSet)).isInitialized).to.equal(false);
+
+      try {
+        await program.methods
+          .setWinnerForDevnet(["a", "b"], ["a", "b"])
+          .accountsPartial({ quizSet, authority: owner.publicKey })
+          .signers([owner])
+          .rpc();
+        expect.fail("an uninitialized quiz can't have a winner");
+      } catch (error: any) {
+        expect(error.toString()).to.include("QuizNotInitialized");
+      }
+
+      const quizSetAccount = await program.account.quizSet.fetch(quizSet);
+      expect(quizSetAccount.winner).to.equal(null);
+    });
+  });
+
+
+  describe("Acceptable Answer Whitelist", () => {
+    it("Should accept any of several stored answers for one question", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 9);
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const block = await program.account.questionBlock.fetch(questionBlockAddress(quizSet, 1));
+      const nonceBytes = block.nonce.toArrayLike(Buffer, "le", 16);
+      const encrypt = (text: string) => {
+        const buffer = Buffer.alloc(64);
+        buffer.write(text);
+        return Array.from(buffer, (byte, i) => (byte + nonceBytes[i % 16]) & 0xff);
+      };
+
+      const setAnswers = (answers: string[]) =>
+        program.methods
+          .setAcceptableAnswers(answers.map(encrypt))
+          .accountsPartial({ questionBlock: questionBlockAddress(quizSet, 1), quizSet, authority: owner.publicKey })
+          .signers([owner])
+          .rpc();
+
+      const { contentHash } = await program.account.quizSet.fetch(quizSet);
+      await setAnswers(["red", "blue", "yellow"]);
+      expect((await program.account.questionBlock.fetch(questionBlockAddress(quizSet, 1))).altAnswerCount).to.equal(3);
+      const updated = await program.account.quizSet.fetch(quizSet);
+      expect(Buffer.from(updated.contentHash).equals(Buffer.from(contentHash))).to.equal(false);
+
+      for (const answer of ["red", "blue", "yellow"]) {
+        const player = await fundedKeypair();
+        const verified = awaitEvent("answerVerifiedEvent");
+        const offset = await submitAnswer(player, quizSet, 1, answer);
+        await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+        expect((await verified).isCorrect).to.equal(true);
+
+        const progress = await program.account.playerProgress.fetch(playerProgressAddress(player.publicKey, quizSet));
+        expect(progress.correctCount).to.equal(1);
+      }
+
+      try {
+        await setAnswers(["green"]);
+        expect.fail("accepted answers are fixed once players have answered");
+      } catch (error: any) {
+        expect(error.toString()).to.include("AnswersLocked");
+      }
+    });
+  });
+
+
+  describe("Winner Reassignment", () => {
+    it("Should move an unclaimed reward from a disqualified winner to a qualifying player", async () => {
+      const owner = await fundedKeypair();
+      const cheater = await fundedKeypair();
+      const honest = await fundedKeypair();
+      const partial = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 19);
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      for (const player of [cheater, honest]) {
+        for (const questionIndex of [1, 2, 3]) {
+          const offset = await submitAnswer(player, quizSet, questionIndex, "answer");
+          await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+        }
+      }
+      const offset = await submitAnswer(partial, quizSet, 1, "answer");
+      await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+      expect((await program.account.quizSet.fetch(quizSet)).winner!.toString()).to.equal(cheater.publicKey.toString());
+
+      const reassign = (newWinner: PublicKey) =>
+        program.methods
+          .reassignWinner(newWinner)
+          .accountsPartial({ quizSet, authority: owner.publicKey })
+          .signers([owner])
+          .rpc();
+
+      try {
+        await reassign(partial.publicKey);
+        expect.fail("a player who didn't finish can't take the reward");
+      } catch (error: any) {
+        expect(error.toString()).to.include("WinnerNotQualified");
+      }
+
+      const reassigned = awaitEvent("winnerReassigned");
+      await reassign(honest.publicKey);
+      const event = await reassigned;
+      expect(event.previousWinner.toString()).to.equal(cheater.publicKey.toString());
+      expect(event.newWinner.toString()).to.equal(honest.publicKey.toString());
+
+      try {
+        await claimReward(cheater, quizSet);
+        expect.fail("the disqualified winner can no longer claim");
+      } catch (error: any) {
+        expect(error).to.exist;
+      }
+
+      const before = await provider.connection.getBalance(honest.publicKey);
+      await claimReward(honest, quizSet);
+      expect(await provider.connection.getBalance(honest.publicKey)).to.be.greaterThan(before);
+      expect((await program.account.quizSet.fetch(quizSet)).isRewardClaimed).to.equal(true);
+    });
+
+    it("Should refuse to reassign once a co-winner has been paid", async () => {
+      const owner = await fundedKeypair();
+      const first = await fundedKeypair();
+      const second = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 20);
+      await program.methods
+        .setMaxWinners(2)
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      for (const player of [first, second]) {
+        for (const questionIndex of [1, 2, 3]) {
+          const offset = await submitAnswer(player, quizSet, questionIndex, "answer");
+          await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+        }
+      }
+      const quiz = await program.account.quizSet.fetch(quizSet);
+      expect(quiz.coWinners.map((key) => key.toString())).to.deep.equal([second.publicKey.toString()]);
+
+      await claimReward(second, quizSet);
+
+      // Promoting the paid co-winner would let them claim the winner's share too
+      try {
+        await program.methods
+          .reassignWinner(second.publicKey)
+          .accountsPartial({ quizSet, authority: owner.publicKey })
+          .signers([owner])
+          .rpc();
+        expect.fail("the winner list is frozen once a share is paid");
+      } catch (error: any) {
+        expect(error.toString()).to.include("RewardAlreadyClaimed");
+      }
+
+      const after = await program.account.quizSet.fetch(quizSet);
+      expect(after.winner!.toString()).to.equal(first.publicKey.toString());
+      expect(after.coWinners.map((key) => key.toString())).to.deep.equal([second.publicKey.toString()]);
+    });
+  });
+
+
+  describe("Batch Limits", () => {
+    it("Should reject a batch larger than MAX_BATCH before doing any work", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 29);
+
+      // 17 accounts, one over the limit; none need to exist since the guard runs first
+      const accounts = Array.from({ length: 17 }, () => ({
+        pubkey: Keypair.generate().publicKey,
+        isWritable: false,
+        isSigner: false,
+      }));
+
+      try {
+        await program.methods.getLiveStandings().accountsPartial({ quizSet }).remainingAccounts(accounts).rpc();
+        expect.fail("17 accounts exceed the batch limit");
+      } catch (error: any) {
+        expect(error.toString()).to.include("BatchTooLarge");
+      }
+    });
+  });
+
+
+  describe("Time Remaining", () => {
+    it("Should report the countdown mid-window and expiry past the deadline", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 39);
+      const duration = 30;
+
+      await program.methods
+        .setDuration(new BN(duration))
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      // The timer starts with the first submission
+      const offset = await submitAnswer(player, quizSet, 1, "answer");
+      await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+
+      const timeRemaining = async () => {
+        const event = awaitEvent("timeRemaining");
+        await program.methods
+          .getTimeRemaining()
+          .accountsPartial({ quizSet, playerProgress: playerProgressAddress(player.publicKey, quizSet) })
+          .rpc();
+        return event;
+      };
+
+      const midWindow = await timeRemaining();
+      expect(midWindow.expired).to.equal(false);
+      expect(midWindow.secondsLeft.toNumber()).to.be.greaterThan(0);
+      expect(midWindow.secondsLeft.toNumber()).to.be.at.most(duration);
+
+      await sleep((duration + 2) * 1000);
+      const pastDeadline = await timeRemaining();
+      expect(pastDeadline.expired).to.equal(true);
+      expect(pastDeadline.secondsLeft.toNumber()).to.equal(0);
+
+      try {
+        await submitAnswer(player, quizSet, 2, "answer");
+        expect.fail("answers after the deadline should be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("TimeExpired");
+      }
+    });
+  });
+
+
+  describe("Topic Status After Ownership Transfer", () => {
+    it("Should reject a toggle the old owner signed before the transfer landed", async () => {
+      const oldOwner = await fundedKeypair();
+      const newOwner = await fundedKeypair();
+      const topicPda = await createTopic(oldOwner);
+
+      // Signed while oldOwner still owns the topic, but not sent yet
+      const toggle = await program.methods
+        .toggleTopicStatus(false)
+        .accountsPartial({ topic: topicPda, owner: oldOwner.publicKey, systemProgram: SystemProgram.programId })
+        .transaction();
+      toggle.feePayer = oldOwner.publicKey;
+      toggle.recentBlockhash = (await provider.connection.getLatestBlockhash()).blockhash;
+      toggle.sign(oldOwner);
+
+      await program.methods
+        .transferTopicOwnership(newOwner.publicKey)
+        .accountsPartial({ topic: topicPda, owner: oldOwner.publicKey, systemProgram: SystemProgram.programId })
+        .signers([oldOwner])
+        .rpc({ commitment: "confirmed" });
+
+      try {
+        await provider.connection.sendRawTransaction(toggle.serialize());
+        expect.fail("the stale toggle should be rejected");
+      } catch (error: any) {
+        expect(String(error.logs ?? error)).to.include("NotTopicOwner");
+      }
+
+      expect((await program.account.topic.fetch(topicPda)).isActive).to.equal(true);
+    });
+  });
+
+
+  describe("Distinct Answers Quality Gate", () => {
+    const addBlocksWithAnswers = async (owner: Keypair, quizSet: PublicKey, answers: string[]) => {
+      for (let i = 1; i <= answers.length; i++) {
+        // Each answer is encrypted under a different nonce per block
+        const nonce = new BN(7000 + i);
+        await program.methods
+          .addEncryptedQuestionBlock(
+            i,
+            Array.from(new Uint8Array(64).fill(i)),
+            encryptAnswer(answers[i - 1], nonce),
+            Array.from(new Uint8Array(32).fill(i + 2)),
+            nonce,
+            0,
+            false,
+            0
+          )
+          .accountsPartial({
+            questionBlock: questionBlockAddress(quizSet, i),
+            quizSet,
+            authority: owner.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([owner])
+          .rpc();
+      }
+    };
+
+    it("Should reject an all-same-answer quiz only when the topic gate is on", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const sameAnswers = ["same", "same", "same"];
+
+      const ungated = await createQuiz(owner, topicPda, 49);
+      await addBlocksWithAnswers(owner, ungated, sameAnswers);
+      expect((await program.account.quizSet.fetch(ungated)).isInitialized).to.equal(true);
+
+      await program.methods
+        .setTopicDistinctAnswers(true)
+        .accountsPartial({ topic: topicPda, owner: owner.publicKey })
+        .signers([owner])
+        .rpc();
+
+      const gated = await createQuiz(owner, topicPda, 50);
+      try {
+        await addBlocksWithAnswers(owner, gated, sameAnswers);
+        expect.fail("identical answers should be rejected at finalize");
+      } catch (error: any) {
+        expect(error.toString()).to.include("TrivialQuizRejected");
+      }
+      expect((await program.account.quizSet.fetch(gated)).isInitialized).to.equal(false);
+
+      const distinct = await createQuiz(owner, topicPda, 51);
+      await addBlocksWithAnswers(owner, distinct, ["red", "green", "blue"]);
+      expect((await program.account.quizSet.fetch(distinct)).isInitialized).to.equal(true);
+    });
+  });
+
+  describe("Claim Error Context", () => {
+    it("Should log an ErrorContext event alongside NotWinner", async () => {
+      const owner = await fundedKeypair();
+      const stranger = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 59);
+      await addQuestionBlocks(owner, quizSet, 3);
+      await program.methods
+        .setWinnerForDevnet(["a", "b", "c"], ["a", "b", "c"])
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+
+      let logs: string[] = [];
+      try {
+        await claimReward(stranger, quizSet);
+        expect.fail("only a winner may claim");
+      } catch (error: any) {
+        expect(error.toString()).to.include("NotWinner");
+        logs = error.logs ?? [];
+      }
+
+      // Events from a failed transaction never reach listeners, so read them from the logs
+      const parser = new anchor.EventParser(program.programId, program.coder);
+      const contexts = Array.from(parser.parseLogs(logs)).filter((event) => event.name === "errorContext");
+      expect(contexts).to.have.length(1);
+
+      const context = contexts[0].data as any;
+      expect(context.claimer.toString()).to.equal(stranger.publicKey.toString());
+      expect(context.winner.toString()).to.equal(owner.publicKey.toString());
+      expect(context.isRewardClaimed).to.equal(false);
+      expect(context.vaultBalance.toNumber()).to.be.at.least(10_000_000);
+    });
+  });
+
+
+  describe("Password-Protected Quizzes", () => {
+    const passwordKey = (quizSet: PublicKey, password: string) =>
+      Keypair.fromSeed(
+        createHash("sha256").update("k3hoot:password").update(quizSet.toBuffer()).update(password).digest()
+      );
+
+    it("Should let players answer only after joining with the right password", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 69);
+
+      await program.methods
+        .setPasswordKey(passwordKey(quizSet, "open sesame").publicKey)
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      // Only a signature by the password-derived key goes on chain, never the password
+      const join = (password: string) =>
+        program.methods
+          .joinQuiz()
+          .accountsPartial({
+            quizSet,
+            player: player.publicKey,
+            instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          })
+          .preInstructions([
+            anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+              privateKey: passwordKey(quizSet, password).secretKey,
+              message: Buffer.concat([Buffer.from("k3hoot:join"), player.publicKey.toBuffer(), quizSet.toBuffer()]),
+            }),
+          ])
+          .signers([player])
+          .rpc();
+
+      try {
+        await submitAnswer(player, quizSet, 1, "answer");
+        expect.fail("answering before joining should fail");
+      } catch (error: any) {
+        expect(error.toString()).to.include("NotJoined");
+      }
+
+      try {
+        await join("open says me");
+        expect.fail("a wrong password should not join");
+      } catch (error: any) {
+        expect(error.toString()).to.include("WrongPassword");
+      }
+
+      await join("open sesame");
+      const progress = await program.account.playerProgress.fetch(playerProgressAddress(player.publicKey, quizSet));
+      expect(progress.joined).to.equal(true);
+
+      // The proof is bound to the player, so someone who saw it can't reuse it
+      const onlooker = await fundedKeypair();
+      try {
+        await program.methods
+          .joinQuiz()
+          .accountsPartial({
+            quizSet,
+            player: onlooker.publicKey,
+            instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          })
+          .preInstructions([
+            anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+              privateKey: passwordKey(quizSet, "open sesame").secretKey,
+              message: Buffer.concat([Buffer.from("k3hoot:join"), player.publicKey.toBuffer(), quizSet.toBuffer()]),
+            }),
+          ])
+          .signers([onlooker])
+          .rpc();
+        expect.fail("another player's join proof should not be accepted");
+      } catch (error: any) {
+        expect(error.toString()).to.include("WrongPassword");
+      }
+
+      const offset = await submitAnswer(player, quizSet, 1, "answer");
+      await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+      expect(
+        (await program.account.playerProgress.fetch(playerProgressAddress(player.publicKey, quizSet))).correctCount
+      ).to.equal(1);
+    });
+  });
+
+
+  describe("Real Validation Result", () => {
+    it("Should treat a wrong answer as wrong and never score a question twice", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 79);
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const answer = async (questionIndex: number, text: string) => {
+        const verified = awaitEvent("answerVerifiedEvent");
+        const offset = await submitAnswer(player, quizSet, questionIndex, text);
+        await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+        return (await verified).isCorrect;
+      };
+
+      for (const questionIndex of [1, 2, 3]) {
+        expect(await answer(questionIndex, "not the answer")).to.equal(false);
+      }
+      let quizSetAccount = await program.account.quizSet.fetch(quizSet);
+      expect(quizSetAccount.winner).to.equal(null);
+      expect(quizSetAccount.correctAnswersCount).to.equal(0);
+
+      expect(await answer(1, "answer")).to.equal(true);
+      expect(await answer(1, "answer")).to.equal(true);
+
+      const progress = await program.account.playerProgress.fetch(playerProgressAddress(player.publicKey, quizSet));
+      expect(progress.correctCount).to.equal(1);
+      expect(progress.score).to.equal(1);
+      quizSetAccount = await program.account.quizSet.fetch(quizSet);
+      expect(quizSetAccount.correctAnswersCount).to.equal(1);
+      expect(quizSetAccount.winner).to.equal(null);
+    });
+  });
+
+
+  describe("Pre-Created Vault", () => {
+    it("Should create a quiz set against a vault initialized beforehand", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const uniqueId = 89;
+      const quizSet = quizSetAddress(owner.publicKey, uniqueId);
+      const vault = vaultAddress(quizSet);
+
+      await program.methods
+        .initQuizVault(uniqueId)
+        .accountsPartial({ authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      const preCreated = await provider.connection.getAccountInfo(vault);
+      expect(preCreated!.owner.toString()).to.equal(program.programId.toString());
+      expect(await provider.connection.getAccountInfo(quizSet)).to.equal(null);
+
+      await createQuiz(owner, topicPda, uniqueId);
+
+      const funded = await provider.connection.getAccountInfo(vault);
+      expect(funded!.owner.toString()).to.equal(program.programId.toString());
+      expect(funded!.lamports).to.equal(preCreated!.lamports + 10_000_000);
+      expect((await program.account.quizSet.fetch(quizSet)).isFunded).to.equal(true);
+    });
+
+    it("Should create a quiz set whose vault address already holds lamports", async () => {
+      const owner = await fundedKeypair();
+      const griefer = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const uniqueId = 90;
+      const vault = vaultAddress(quizSetAddress(owner.publicKey, uniqueId));
+
+      // A bare transfer to the PDA would make create_account fail
+      const tx = new anchor.web3.Transaction().add(
+        SystemProgram.transfer({ fromPubkey: griefer.publicKey, toPubkey: vault, lamports: 1_000 })
+      );
+      await provider.sendAndConfirm(tx, [griefer]);
+
+      const quizSet = await createQuiz(owner, topicPda, uniqueId);
+      const created = await provider.connection.getAccountInfo(vault);
+      expect(created!.owner.toString()).to.equal(program.programId.toString());
+      expect((await program.account.quizSet.fetch(quizSet)).isFunded).to.equal(true);
+    });
+  });
+
+
+  describe("Per-Player Completion", () => {
+    it("Should not crown anyone when two players split the questions", async () => {
+      const owner = await fundedKeypair();
+      const first = await fundedKeypair();
+      const second = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 111);
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      for (const [player, questionIndices] of [
+        [first, [1, 2]],
+        [second, [3]],
+      ] as [Keypair, number[]][]) {
+        for (const questionIndex of questionIndices) {
+          const offset = await submitAnswer(player, quizSet, questionIndex, "answer");
+          await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+        }
+      }
+
+      // Between them every question is answered, but neither finished alone
+      const quizSetAccount = await program.account.quizSet.fetch(quizSet);
+      expect(quizSetAccount.correctAnswersCount).to.equal(3);
+      expect(quizSetAccount.winner).to.equal(null);
+
+      const firstProgress = await program.account.playerProgress.fetch(playerProgressAddress(first.publicKey, quizSet));
+      const secondProgress = await program.account.playerProgress.fetch(playerProgressAddress(second.publicKey, quizSet));
+      expect(firstProgress.answeredMask.toNumber()).to.equal(0b011);
+      expect(secondProgress.answeredMask.toNumber()).to.equal(0b100);
+    });
+  });
+
+
+  describe("Per-Question Rewards", () => {
+    it("Should pay the claimer for each question answered correctly", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 112);
+      const perQuestionReward = 3_000_000;
+
+      await program.methods
+        .setPerQuestionReward(new BN(perQuestionReward))
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      for (const [questionIndex, answer] of [
+        [1, "answer"],
+        [2, "answer"],
+        [3, "not the answer"],
+      ] as [number, string][]) {
+        const offset = await submitAnswer(player, quizSet, questionIndex, answer);
+        await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+      }
+
+      const progressPda = playerProgressAddress(player.publicKey, quizSet);
+      let progress = await program.account.playerProgress.fetch(progressPda);
+      expect(progress.claimable.toNumber()).to.equal(2 * perQuestionReward);
+
+      // No completion prize: 2 of 3 correct is still paid
+      const vaultBefore = await provider.connection.getBalance(vaultAddress(quizSet));
+      await claimReward(player, quizSet, undefined, undefined, progressPda);
+      const vaultAfter = await provider.connection.getBalance(vaultAddress(quizSet));
+      expect(vaultBefore - vaultAfter).to.equal(2 * perQuestionReward);
+
+      progress = await program.account.playerProgress.fetch(progressPda);
+      expect(progress.claimable.toNumber()).to.equal(0);
+      const [receiptPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("claim"), quizSet.toBuffer(), player.publicKey.toBuffer()],
+        program.programId
+      );
+      const receipt = await program.account.claimReceipt.fetch(receiptPda);
+      expect(receipt.amount.toNumber()).to.equal(2 * perQuestionReward);
+
+      // Nothing left until another question is answered
+      try {
+        await claimReward(player, quizSet, undefined, undefined, progressPda);
+        expect.fail("a second claim with nothing earned should fail");
+      } catch (error: any) {
+        expect(error.toString()).to.include("NothingToClaim");
+      }
+    });
+
+    it("Should stop crediting once the whole reward has been handed out", async () => {
+      const owner = await fundedKeypair();
+      const first = await fundedKeypair();
+      const second = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 171);
+
+      await program.methods
+        .setPerQuestionReward(new BN(3_000_000))
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      for (const player of [first, second]) {
+        for (const questionIndex of [1, 2, 3]) {
+          const offset = await submitAnswer(player, quizSet, questionIndex, "answer");
+          await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+        }
+      }
+
+      // 9_000_000 to the first player leaves only 1_000_000 of the 10_000_000 reward
+      const firstProgress = await program.account.playerProgress.fetch(playerProgressAddress(first.publicKey, quizSet));
+      const secondProgress = await program.account.playerProgress.fetch(playerProgressAddress(second.publicKey, quizSet));
+      expect(firstProgress.claimable.toNumber()).to.equal(9_000_000);
+      expect(secondProgress.claimable.toNumber()).to.equal(1_000_000);
+      expect((await program.account.quizSet.fetch(quizSet)).perQuestionCredited.toNumber()).to.equal(10_000_000);
+    });
+  });
+
+
+  describe("SPL Token Rewards", () => {
+    const winAllQuestions = async (player: Keypair, quizSet: PublicKey) => {
+      for (const questionIndex of [1, 2, 3]) {
+        const offset = await submitAnswer(player, quizSet, questionIndex, "answer");
+        await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+      }
+    };
+
+    // 6 decimals, like USDC: 25.5 tokens = 25_500_000 base units
+    const decimals = 6;
+    const createTokenQuiz = async (owner: Keypair, uniqueId: number) => {
+      const topicPda = await createTopic(owner);
+      const quizSet = quizSetAddress(owner.publicKey, uniqueId);
+      const vault = vaultAddress(quizSet);
+      const rewardAmount = new BN(25_500_000);
+      const rewardMint = await createMint(provider.connection, owner, owner.publicKey, null, decimals);
+      const ownerTokens = await getOrCreateAssociatedTokenAccount(provider.connection, owner, rewardMint, owner.publicKey);
+      const vaultTokens = await getOrCreateAssociatedTokenAccount(provider.connection, owner, rewardMint, vault, true);
+      await mintTo(provider.connection, owner, rewardMint, ownerTokens.address, owner, 100 * 10 ** decimals);
+
+      await program.methods
+        .createQuizSet(`Quiz ${uniqueId}`, 3, uniqueId, rewardAmount, rewardAmount, new BN(0))
+        .accountsPartial({
+          quizSet,
+          topic: topicPda,
+          vault,
+          authority: owner.publicKey,
+          rewardMint,
+          vaultTokenAccount: vaultTokens.address,
+          authorityTokenAccount: ownerTokens.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([owner])
+        .rpc();
+      return { quizSet, vault, rewardMint, ownerTokens, vaultTokens };
+    };
+
+    it("Should keep paying lamports when no reward mint is given", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 113);
+      await addQuestionBlocks(owner, quizSet, 3);
+      await winAllQuestions(player, quizSet);
+
+      expect((await program.account.quizSet.fetch(quizSet)).rewardMint).to.equal(null);
+
+      const vaultBefore = await provider.connection.getBalance(vaultAddress(quizSet));
+      await claimReward(player, quizSet);
+      const vaultAfter = await provider.connection.getBalance(vaultAddress(quizSet));
+      expect(vaultBefore - vaultAfter).to.equal(10_000_000);
+    });
+
+    it("Should escrow and pay out a token reward in the mint's base units", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const { quizSet, vault, rewardMint, vaultTokens } = await createTokenQuiz(owner, 114);
+      const playerTokens = await getOrCreateAssociatedTokenAccount(provider.connection, owner, rewardMint, player.publicKey);
+
+      const quizSetAccount = await program.account.quizSet.fetch(quizSet);
+      expect(quizSetAccount.rewardMint!.toString()).to.equal(rewardMint.toString());
+      expect((await getAccount(provider.connection, vaultTokens.address)).amount).to.equal(BigInt(25_500_000));
+
+      await addQuestionBlocks(owner, quizSet, 3);
+      await winAllQuestions(player, quizSet);
+
+      await program.methods
+        .claimReward(null)
+        .accountsPartial({
+          quizSet,
+          vault,
+          claimer: player.publicKey,
+          destinationAccount: null,
+          playerProgress: null,
+          rewardMint,
+          vaultTokenAccount: vaultTokens.address,
+          claimerTokenAccount: playerTokens.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(questionBlockMetas(quizSet, [1, 2, 3]))
+        .signers([player])
+        .rpc();
+
+      const paid = await getAccount(provider.connection, playerTokens.address);
+      expect(paid.amount).to.equal(BigInt(25_500_000));
+      expect(Number(paid.amount) / 10 ** decimals).to.equal(25.5);
+      expect((await getAccount(provider.connection, vaultTokens.address)).amount).to.equal(BigInt(0));
+    });
+
+    it("Should return an unclaimed token reward to the authority after the claim window", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const { quizSet, vault, rewardMint, ownerTokens, vaultTokens } = await createTokenQuiz(owner, 174);
+      await program.methods
+        .setClaimWindow(new BN(2), new BN(0))
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+      await winAllQuestions(player, quizSet);
+
+      await sleep(4000);
+
+      const ownerBefore = (await getAccount(provider.connection, ownerTokens.address)).amount;
+      await program.methods
+        .reclaimUnclaimedWinnerReward()
+        .accountsPartial({
+          quizSet,
+          vault,
+          authority: owner.publicKey,
+          rewardMint,
+          vaultTokenAccount: vaultTokens.address,
+          authorityTokenAccount: ownerTokens.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([owner])
+        .rpc();
+
+      const ownerAfter = (await getAccount(provider.connection, ownerTokens.address)).amount;
+      expect(ownerAfter - ownerBefore).to.equal(BigInt(25_500_000));
+      expect((await getAccount(provider.connection, vaultTokens.address)).amount).to.equal(BigInt(0));
+    });
+  });
+
+
+  describe("Quiz Deadline Refund", () => {
+    const refund = (owner: Keypair, quizSet: PublicKey) =>
+      program.methods
+        .refundUnclaimedReward()
+        .accountsPartial({ quizSet, vault: vaultAddress(quizSet), authority: owner.publicKey })
+        .signers([owner])
+        .rpc({ commitment: "confirmed" });
+
+    it("Should refund an unwon reward to the authority only after the deadline", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const deadline = Math.floor(Date.now() / 1000) + 5;
+      const quizSet = await createQuiz(owner, topicPda, 115, 3, new BN(10_000_000), new BN(10_000_000), new BN(deadline));
+
+      try {
+        await refund(owner, quizSet);
+        expect.fail("refund should be blocked before the deadline");
+      } catch (error: any) {
+        expect(error.toString()).to.include("DeadlineNotReached");
+      }
+
+      await sleep(7000);
+
+      const refunded = awaitEvent("rewardRefunded");
+      const vaultBefore = await provider.connection.getBalance(vaultAddress(quizSet));
+      await refund(owner, quizSet);
+      const vaultAfter = await provider.connection.getBalance(vaultAddress(quizSet));
+
+      expect(vaultBefore - vaultAfter).to.equal(10_000_000);
+      const event = await refunded;
+      expect(event.amount.toNumber()).to.equal(10_000_000);
+      expect(event.authority.toString()).to.equal(owner.publicKey.toString());
+      expect((await program.account.quizSet.fetch(quizSet)).isFunded).to.equal(false);
+    });
+
+    it("Should reject answers submitted after the deadline", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const deadline = Math.floor(Date.now() / 1000) + 30;
+      const quizSet = await createQuiz(owner, topicPda, 173, 3, new BN(10_000_000), new BN(10_000_000), new BN(deadline));
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      await sleep(Math.max(0, (deadline + 2) * 1000 - Date.now()));
+
+      try {
+        await commitAnswer(player, quizSet, 1, "answer");
+        expect.fail("answers close at the deadline");
+      } catch (error: any) {
+        expect(error.toString()).to.include("QuizDeadlinePassed");
+      }
+    });
+
+    it("Should reject a winner's claim made after the deadline", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const deadline = Math.floor(Date.now() / 1000) + 45;
+      const quizSet = await createQuiz(owner, topicPda, 116, 3, new BN(10_000_000), new BN(10_000_000), new BN(deadline));
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      for (const questionIndex of [1, 2, 3]) {
+        const offset = await submitAnswer(player, quizSet, questionIndex, "answer");
+        await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+      }
+      expect((await program.account.quizSet.fetch(quizSet)).winner!.toString()).to.equal(player.publicKey.toString());
+
+      await sleep(Math.max(0, (deadline + 2) * 1000 - Date.now()));
+
+      try {
+        await claimReward(player, quizSet);
+        expect.fail("claim after the deadline should fail");
+      } catch (error: any) {
+        expect(error.toString()).to.include("QuizDeadlinePassed");
+      }
+    });
+  });
+
+
+  describe("Vault Balance Guard", () => {
+    it("Should fail gracefully when the vault can no longer cover a claim", async () => {
+      const owner = await fundedKeypair();
+      const first = await fundedKeypair();
+      const second = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 118);
+
+      // 3 × 3M per player fits the 10M pool, but two players together overdraw it
+      await program.methods
+        .setPerQuestionReward(new BN(3_000_000))
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      for (const [player, questionIndices] of [
+        [first, [1, 2, 3]],
+        [second, [1]],
+      ] as [Keypair, number[]][]) {
+        for (const questionIndex of questionIndices) {
+          const offset = await submitAnswer(player, quizSet, questionIndex, "answer");
+          await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+        }
+      }
+
+      await claimReward(first, quizSet, undefined, undefined, playerProgressAddress(first.publicKey, quizSet));
+
+      // 1M left above rent: the second claim must not dip into the rent reserve
+      const vault = vaultAddress(quizSet);
+      const vaultBefore = await provider.connection.getBalance(vault);
+      const rentExempt = await provider.connection.getMinimumBalanceForRentExemption(0);
+      expect(vaultBefore - rentExempt).to.equal(1_000_000);
+
+      try {
+        await claimReward(second, quizSet, undefined, undefined, playerProgressAddress(second.publicKey, quizSet));
+        expect.fail("claim larger than the vault balance should fail");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InsufficientVaultBalance");
+      }
+      expect(await provider.connection.getBalance(vault)).to.equal(vaultBefore);
+    });
+  });
+
+
+  describe("Active Quiz Cap", () => {
+    it("Should block new quizzes at the cap until one settles", async () => {
+      const owner = await fundedKeypair(3);
+      const topicPda = await createTopic(owner);
+      const quota = authorQuotaAddress(owner.publicKey);
+      const setCap = (maxActiveQuizzes: number) =>
+        program.methods
+          .setMaxActiveQuizzes(maxActiveQuizzes)
+          .accountsPartial({ programConfig: programConfigAddress, admin: provider.wallet.publicKey })
+          .rpc();
+
+      // The cap is program-wide: a topic owner can't lift it for themselves
+      try {
+        await program.methods
+          .setMaxActiveQuizzes(0)
+          .accountsPartial({ programConfig: programConfigAddress, admin: owner.publicKey })
+          .signers([owner])
+          .rpc();
+        expect.fail("only the program admin may change the cap");
+      } catch (error: any) {
+        expect(error.toString()).to.include("Unauthorized");
+      }
+
+      await setCap(2);
+      try {
+        const deadline = Math.floor(Date.now() / 1000) + 5;
+        const expiring = await createQuiz(owner, topicPda, 130, 3, new BN(10_000_000), new BN(10_000_000), new BN(deadline));
+        await createQuiz(owner, topicPda, 131);
+        expect((await program.account.authorQuota.fetch(quota)).activeQuizCount).to.equal(2);
+
+        try {
+          await createQuiz(owner, topicPda, 132);
+          expect.fail("a third active quiz should exceed the cap");
+        } catch (error: any) {
+          expect(error.toString()).to.include("TooManyActiveQuizzes");
+        }
+
+        // Refunding the expired quiz settles it and frees its slot
+        await sleep(7000);
+        await program.methods
+          .refundUnclaimedReward()
+          .accountsPartial({
+            quizSet: expiring,
+            vault: vaultAddress(expiring),
+            authority: owner.publicKey,
+            authorQuota: quota,
+          })
+          .signers([owner])
+          .rpc({ commitment: "confirmed" });
+        expect((await program.account.authorQuota.fetch(quota)).activeQuizCount).to.equal(1);
+        expect((await program.account.quizSet.fetch(expiring)).isActive).to.equal(false);
+
+        await createQuiz(owner, topicPda, 132);
+        expect((await program.account.authorQuota.fetch(quota)).activeQuizCount).to.equal(2);
+      } finally {
+        await setCap(0);
+      }
+    });
+  });
+
+
+  describe("Result Authority", () => {
+    const setWinner = (quizSet: PublicKey, setter: Keypair, winner: PublicKey) =>
+      program.methods
+        .setWinnerForUser(winner, 3)
+        .accountsPartial({ quizSet, setter: setter.publicKey, systemProgram: SystemProgram.programId })
+        .signers([setter])
+        .rpc();
+
+    it("Should only let the quiz's result authority set the winner", async () => {
+      const owner = await fundedKeypair();
+      const oracle = await fundedKeypair();
+      const attacker = await fundedKeypair();
+      const player = Keypair.generate();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 133);
+
+      expect((await program.account.quizSet.fetch(quizSet)).resultAuthority.toString()).to.equal(
+        owner.publicKey.toString()
+      );
+
+      await program.methods
+        .setResultAuthority(oracle.publicKey)
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      // Neither an outsider nor the (no longer delegated) authority may set it
+      for (const setter of [attacker, owner]) {
+        try {
+          await setWinner(quizSet, setter, setter.publicKey);
+          expect.fail("only the result authority may set the winner");
+        } catch (error: any) {
+          expect(error.toString()).to.include("UnauthorizedSetter");
+        }
+      }
+      expect((await program.account.quizSet.fetch(quizSet)).winner).to.equal(null);
+
+      await setWinner(quizSet, oracle, player.publicKey);
+      expect((await program.account.quizSet.fetch(quizSet)).winner!.toString()).to.equal(player.publicKey.toString());
+    });
+  });
+
+
+  describe("Close Quiz Set", () => {
+    it("Should return the quiz set, question block and vault rent to the authority", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 134);
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const blocks = [1, 2, 3].map((index) => ({
+        pubkey: questionBlockAddress(quizSet, index),
+        isSigner: false,
+        isWritable: true,
+      }));
+      const close = () => closeQuizSet(owner, quizSet);
+
+      await program.methods
+        .setWinnerForUser(player.publicKey, 3)
+        .accountsPartial({ quizSet, setter: owner.publicKey, systemProgram: SystemProgram.programId })
+        .signers([owner])
+        .rpc();
+
+      try {
+        await close();
+        expect.fail("closing before the reward is claimed should fail");
+      } catch (error: any) {
+        expect(error.toString()).to.include("QuizNotSettled");
+      }
+
+      await claimReward(player, quizSet);
+
+      try {
+        await close();
+        expect.fail("closing before the settlement is finalized should fail");
+      } catch (error: any) {
+        expect(error.toString()).to.include("SettlementNotFinalized");
+      }
+      await finalizeSettlement(owner, quizSet);
+
+      const closedAccounts = [quizSet, vaultAddress(quizSet), ...blocks.map((block) => block.pubkey)];
+      let rent = 0;
+      for (const pubkey of closedAccounts) {
+        rent += await provider.connection.getBalance(pubkey);
+      }
+      const before = await provider.connection.getBalance(owner.publicKey);
+
+      const signature = await close();
+      const tx = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const after = await provider.connection.getBalance(owner.publicKey);
+
+      expect(after - before).to.equal(rent - tx!.meta!.fee);
+      for (const pubkey of closedAccounts) {
+        expect(await provider.connection.getAccountInfo(pubkey)).to.equal(null);
+      }
+
+      try {
+        await createQuiz(owner, topicPda, 134);
+        expect.fail("a closed quiz set's address must not be reused");
+      } catch (error: any) {
+        expect(error.toString()).to.include("QuizIdRetired");
+      }
+    });
+
+    it("Should close a per-question quiz once every credit is paid out", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 178, 3, new BN(9_000_000));
+      await program.methods
+        .setPerQuestionReward(new BN(3_000_000))
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      for (const questionIndex of [1, 2, 3]) {
+        const offset = await submitAnswer(player, quizSet, questionIndex, "answer");
+        await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+      }
+      await claimReward(player, quizSet, undefined, undefined, playerProgressAddress(player.publicKey, quizSet));
+      const paidOut = await program.account.quizSet.fetch(quizSet);
+      expect(paidOut.perQuestionPaid.toNumber()).to.equal(9_000_000);
+      expect(paidOut.isActive).to.equal(false);
+
+      await finalizeSettlement(owner, quizSet);
+      await closeQuizSet(owner, quizSet);
+      expect(await provider.connection.getAccountInfo(quizSet)).to.equal(null);
+    });
+  });
+
+
+  describe("Offline Answer Queue", () => {
+    it("Should queue an answer without Arcium and validate it once processed", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 135);
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const answerQueue = PublicKey.findProgramAddressSync(
+        [Buffer.from("answer_queue"), player.publicKey.toBuffer(), quizSet.toBuffer()],
+        program.programId
+      )[0];
+      const progressPda = playerProgressAddress(player.publicKey, quizSet);
+
+      // No Arcium accounts involved while queuing, and only a commitment is stored
+      const salt = Array.from(randomBytes(32));
+      const commitment = answerCommitmentHash("answer", salt, player.publicKey);
+      await program.methods
+        .enqueueAnswer(2, Array.from(commitment))
+        .accountsPartial({
+          answerQueue,
+          playerProgress: progressPda,
+          questionBlock: questionBlockAddress(quizSet, 2),
+          quizSet,
+          player: player.publicKey,
+        })
+        .signers([player])
+        .rpc({ commitment: "confirmed" });
+
+      let queue = await program.account.answerQueue.fetch(answerQueue);
+      expect(queue.entries).to.have.length(1);
+      expect(queue.entries[0].questionIndex).to.equal(2);
+      expect(Buffer.from(queue.entries[0].commitment)).to.deep.equal(commitment);
+      const queuedAt = queue.entries[0].queuedAt.toNumber();
+
+      const processNext = async () => {
+        const computationOffset = await computationOffsetFor(player.publicKey, quizSet, 2);
+        await program.methods
+          .processQueuedAnswers(computationOffset, "answer", salt)
+          .accountsPartial({
+            payer: player.publicKey,
+            answerQueue,
+            answerSubmission: answerSubmissionAddress(computationOffset),
+            playerProgress: progressPda,
+            questionBlock: questionBlockAddress(quizSet, 2),
+            quizSet,
+            ...queueAccounts(computationOffset, "validate_answer"),
+          })
+          .signers([player])
+          .rpc({ commitment: "confirmed" });
+        return computationOffset;
+      };
+
+      const verified = awaitEvent("answerVerifiedEvent");
+      const computationOffset = await processNext();
+
+      // The submission is dated when the answer was queued, not when it was sent
+      const submission = await program.account.answerSubmission.fetch(answerSubmissionAddress(computationOffset));
+      expect(submission.submittedAt.toNumber()).to.equal(queuedAt);
+
+      await awaitComputationFinalization(provider, computationOffset, program.programId, "confirmed");
+      expect((await verified).isCorrect).to.equal(true);
+
+      queue = await program.account.answerQueue.fetch(answerQueue);
+      expect(queue.entries).to.have.length(0);
+      const progress = await program.account.playerProgress.fetch(progressPda);
+      expect(progress.correctCount).to.equal(1);
+
+      try {
+        await processNext();
+        expect.fail("an empty queue has nothing to process");
+      } catch (error: any) {
+        expect(error.toString()).to.include("AnswerQueueEmpty");
+      }
+    });
+  });
+
+  describe("Underfunded Winner Warning", () => {
+    it("Should warn when a winner is set on an underfunded vault", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 137, 3, new BN(10_000_000), new BN(4_000_000));
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const underfunded = awaitEvent("vaultUnderfunded");
+      await program.methods
+        .setWinnerForUser(player.publicKey, 3)
+        .accountsPartial({ quizSet, setter: owner.publicKey, systemProgram: SystemProgram.programId })
+        .signers([owner])
+        .rpc({ commitment: "confirmed" });
+
+      const event = await underfunded;
+      expect(event.quizSet.toString()).to.equal(quizSet.toString());
+      expect(event.winner.toString()).to.equal(player.publicKey.toString());
+      expect(event.rewardAmount.toNumber()).to.equal(10_000_000);
+      const rentExempt = await provider.connection.getMinimumBalanceForRentExemption(0);
+      const vaultBalance = await provider.connection.getBalance(vaultAddress(quizSet));
+      expect(event.vaultBalance.toNumber()).to.equal(vaultBalance - rentExempt);
+      expect(event.vaultBalance.toNumber()).to.be.lessThan(10_000_000);
+
+      // The warning doesn't block the result
+      const quiz = await program.account.quizSet.fetch(quizSet);
+      expect(quiz.winner!.toString()).to.equal(player.publicKey.toString());
+    });
+  });
+
+  describe("Long Question Encryption", () => {
+    const question = "Which scientist formulated the three laws of motion and the law of universal gravitation in the Principia, printed 1687?";
+    const options = ["Isaac Newton of 1643", "Galileo Galilei 1564", "Johannes Kepler 1571", "Rene Descartes, 1596"];
+
+    const encryptChunk = async (owner: Keypair, questionBlock: PublicKey, questionText: string, chunkIndex: number) => {
+      const computationOffset = newComputationOffset();
+      return program.methods
+        .encryptQuizData(computationOffset, questionText, options as any, options[0], new BN(42), chunkIndex)
+        .accountsPartial({
+          payer: owner.publicKey,
+          questionBlock,
+          ...queueAccounts(computationOffset, "encrypt_quiz"),
+        })
+        .signers([owner])
+        .rpc({ commitment: "confirmed" });
+    };
+
+    it("Should encrypt a 120-character question across chunks without truncation", async () => {
+      expect(question.length).to.equal(120);
+      expect(options.every((option) => option.length === 20)).to.equal(true);
+
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 138);
+      await addQuestionBlocks(owner, quizSet, 1);
+      const questionBlock = questionBlockAddress(quizSet, 1);
+
+      // 1 + 120 question bytes and 4 × (1 + 20) option bytes span four 64-byte chunks
+      for (const chunkIndex of [0, 1, 2, 3]) {
+        const tx = await encryptChunk(owner, questionBlock, question, chunkIndex);
+        const logs = (await provider.connection.getTransaction(tx, {
+          commitment: "confirmed",
+          maxSupportedTransactionVersion: 0,
+        }))!.meta!.logMessages!.join("\n");
+        expect(logs).to.include(`chunk ${chunkIndex + 1} of 4`);
+      }
+
+      try {
+        await encryptChunk(owner, questionBlock, question, 4);
+        expect.fail("a chunk past the end should be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidQuestionChunk");
+      }
+    });
+
+    it("Should reject content past the maximum instead of truncating it", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 139);
+      await addQuestionBlocks(owner, quizSet, 1);
+
+      try {
+        await encryptChunk(owner, questionBlockAddress(quizSet, 1), question.repeat(2), 0);
+        expect.fail("oversized content should be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("QuestionContentTooLong");
+      }
+    });
+  });
+
+  describe("Reveal My Mistakes", () => {
+    const revealMistake = async (payer: Keypair, quizSet: PublicKey, questionIndex: number, accounts: object = {}) => {
+      const privateKey = x25519.utils.randomPrivateKey();
+      const publicKey = x25519.getPublicKey(privateKey);
+      const nonce = randomBytes(16);
+      const computationOffset = newComputationOffset();
+      await program.methods
+        .revealMyMistakes(computationOffset, questionIndex, Array.from(publicKey), new BN(nonce, "le"))
+        .accountsPartial({
+          payer: payer.publicKey,
+          questionBlock: questionBlockAddress(quizSet, questionIndex),
+          quizSet,
+          ...queueAccounts(computationOffset, "reveal_answer"),
+          ...accounts,
+        })
+        .signers([payer])
+        .rpc({ commitment: "confirmed" });
+      return { computationOffset, privateKey };
+    };
+
+    it("Should reveal a wrong answer's solution to the player alone", async () => {
+      const compDefAccount = queueAccounts(new BN(0), "reveal_answer").compDefAccount;
+      if (!(await provider.connection.getAccountInfo(compDefAccount))) {
+        await program.methods
+          .initRevealAnswerCompDef()
+          .accountsPartial({ compDefAccount, mxeAccount: getMXEAccAddress(program.programId) })
+          .rpc();
+      }
+
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const outsider = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 140);
+      await program.methods
+        .setEndOnWrong(true)
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      let offset = await submitAnswer(player, quizSet, 1, "answer");
+      await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+
+      try {
+        await revealMistake(player, quizSet, 2);
+        expect.fail("the player's run is still open");
+      } catch (error: any) {
+        expect(error.toString()).to.include("PlayerNotCompleted");
+      }
+
+      // The wrong answer ends the run
+      offset = await submitAnswer(player, quizSet, 2, "nope");
+      await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+
+      // Another player can't point the reveal at someone else's progress
+      try {
+        await revealMistake(outsider, quizSet, 2, { playerProgress: playerProgressAddress(player.publicKey, quizSet) });
+        expect.fail("only the player can reveal their own mistakes");
+      } catch (error: any) {
+        expect(error.toString()).to.include("ConstraintSeeds");
+      }
+
+      try {
+        await revealMistake(player, quizSet, 1);
+        expect.fail("question 1 was answered correctly");
+      } catch (error: any) {
+        expect(error.toString()).to.include("NotAMistake");
+      }
+
+      const revealed = awaitEvent("mistakeRevealed");
+      const { computationOffset, privateKey } = await revealMistake(player, quizSet, 2);
+      await awaitComputationFinalization(provider, computationOffset, program.programId, "confirmed");
+      const event = await revealed;
+      expect(event.player.toString()).to.equal(player.publicKey.toString());
+      expect(event.questionIndex).to.equal(2);
+
+      const mxePublicKey = await getMXEPublicKey(provider, program.programId);
+      const cipher = new RescueCipher(x25519.getSharedSecret(privateKey, mxePublicKey!));
+      const bytes = cipher.decrypt(event.ciphertexts, Uint8Array.from(event.nonce.toArrayLike(Buffer, "le", 16)));
+      const answer = Buffer.from(bytes.map((byte) => Number(byte))).toString().replace(/\0+$/, "");
+      expect(answer).to.equal("answer");
+
+      // Having seen the answer, the player can't reset to replay
+      try {
+        await program.methods
+          .resetPlayerProgress()
+          .accountsPartial({ playerProgress: playerProgressAddress(player.publicKey, quizSet), quizSet, player: player.publicKey })
+          .signers([player])
+          .rpc();
+        expect.fail("a player who revealed answers should not be able to reset");
+      } catch (error: any) {
+        expect(error.toString()).to.include("AnswersRevealed");
+      }
+    });
+
+    it("Should keep answers hidden while the player's own run is open, even after someone wins", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const winner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 168);
+      await program.methods
+        .setMaxWinners(3)
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const offset = await submitAnswer(player, quizSet, 1, "nope");
+      await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+      await program.methods
+        .setWinnerForUser(winner.publicKey, 3)
+        .accountsPartial({ quizSet, setter: owner.publicKey, systemProgram: SystemProgram.programId })
+        .signers([owner])
+        .rpc({ commitment: "confirmed" });
+
+      try {
+        await revealMistake(player, quizSet, 1);
+        expect.fail("the player's run is still open");
+      } catch (error: any) {
+        expect(error.toString()).to.include("PlayerNotCompleted");
+      }
+    });
+  });
+
+  describe("Quiz Data Callback Payloads", () => {
+    it("Should emit the real encrypted and decrypted bytes from the circuits", async () => {
+      for (const [circuit, init] of [
+        ["encrypt_quiz", () => program.methods.initEncryptQuizCompDef()],
+        ["decrypt_quiz", () => program.methods.initDecryptQuizCompDef()],
+      ] as [string, () => any][]) {
+        const compDefAccount = queueAccounts(new BN(0), circuit).compDefAccount;
+        if (!(await provider.connection.getAccountInfo(compDefAccount))) {
+          await init().accountsPartial({ compDefAccount, mxeAccount: getMXEAccAddress(program.programId) }).rpc();
+        }
+      }
+
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 141);
+      await addQuestionBlocks(owner, quizSet, 1);
+      const questionBlock = questionBlockAddress(quizSet, 1);
+
+      const question = "Capital of France?";
+      const options = ["Paris", "Lyon", "Nice", "Lille"];
+      const nonce = new BN(42);
+
+      // Chunk 0 is the length-prefixed question followed by each option
+      const plaintext = Buffer.alloc(64);
+      let offset = 0;
+      for (const field of [question, ...options]) {
+        plaintext[offset] = field.length;
+        offset += 1 + plaintext.write(field, offset + 1);
+      }
+      const nonceBytes = nonce.toArrayLike(Buffer, "le", 16);
+      const expectedCiphertext = Array.from(plaintext, (byte, i) => (byte + nonceBytes[i % 16]) & 0xff);
+
+      const encryptOffset = newComputationOffset();
+      const encrypted = awaitEvent("quizDataEncryptedEvent");
+      await program.methods
+        .encryptQuizData(encryptOffset, question, options as any, "Paris", nonce, 0)
+        .accountsPartial({ payer: owner.publicKey, questionBlock, ...queueAccounts(encryptOffset, "encrypt_quiz") })
+        .signers([owner])
+        .rpc({ commitment: "confirmed" });
+      await awaitComputationFinalization(provider, encryptOffset, program.programId, "confirmed");
+      const encryptedEvent = await encrypted;
+      expect(Array.from(encryptedEvent.encryptedData)).to.deep.equal(expectedCiphertext);
+      expect(encryptedEvent.questionBlock.toString()).to.equal(questionBlock.toString());
+      expect(encryptedEvent.chunkIndex).to.equal(0);
+      expect(encryptedEvent.chunkCount).to.equal(1);
+
+      const decryptOffset = newComputationOffset();
+      const decrypted = awaitEvent("quizDataDecryptedEvent");
+      await program.methods
+        .decryptQuizData(decryptOffset, expectedCiphertext, nonce)
+        .accountsPartial({ payer: owner.publicKey, questionBlock, ...queueAccounts(decryptOffset, "decrypt_quiz") })
+        .signers([owner])
+        .rpc({ commitment: "confirmed" });
+      await awaitComputationFinalization(provider, decryptOffset, program.programId, "confirmed");
+      expect(Buffer.from((await decrypted).decryptedData)).to.deep.equal(plaintext);
+    });
+  });
+
+  describe("Arcium Preflight", () => {
+    const circuits = [
+      "validate_answer",
+      "validate_answer_short",
+      "verify_correct_answer",
+      "crosscheck_circuit",
+      "rotate_block",
+      "decrypt_quiz",
+      "reveal_hint",
+      "encrypt_quiz",
+      "reveal_answer",
+    ];
+    const compDefAddress = (circuit: string) => queueAccounts(new BN(0), circuit).compDefAccount;
+
+    const preflight = async (compDefs: PublicKey[]) => {
+      const result = awaitEvent("arciumPreflight");
+      await program.methods
+        .preflightArcium()
+        .accountsPartial({
+          mxeAccount: getMXEAccAddress(program.programId),
+          clusterAccount: getArciumEnv().arciumClusterPubkey,
+          poolAccount: getFeePoolAccAddress(),
+          clockAccount: getClockAccAddress(),
+        })
+        .remainingAccounts(compDefs.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })))
+        .rpc({ commitment: "confirmed" });
+      return result;
+    };
+
+    it("Should flag exactly the comp-defs that are missing", async () => {
+      const compDefs = circuits.map(compDefAddress);
+      let expectedMask = 0;
+      for (let i = 0; i < compDefs.length; i++) {
+        if (!(await provider.connection.getAccountInfo(compDefs[i]))) {
+          expectedMask |= 1 << (4 + i);
+        }
+      }
+
+      const baseline = await preflight(compDefs);
+      expect(baseline.missingMask).to.equal(expectedMask);
+      expect(baseline.allOk).to.equal(expectedMask === 0);
+
+      // An address nothing was initialized at stands in for the crosscheck comp-def
+      const crosscheckBit = 1 << (4 + circuits.indexOf("crosscheck_circuit"));
+      const missing = [...compDefs];
+      missing[circuits.indexOf("crosscheck_circuit")] = Keypair.generate().publicKey;
+      const flagged = await preflight(missing);
+      expect(flagged.allOk).to.equal(false);
+      expect(flagged.missingMask).to.equal(expectedMask | crosscheckBit);
+      expect(flagged.missingMask & 0b1111).to.equal(0);
+
+      // Leaving a trailing comp-def out flags it too
+      const truncated = await preflight(compDefs.slice(0, -1));
+      expect(truncated.missingMask).to.equal(expectedMask | (1 << (4 + circuits.length - 1)));
+    });
+  });
+
+  describe("Multi-Winner Rewards", () => {
+    it("Should split the reward between the first three finishers only", async () => {
+      const owner = await fundedKeypair();
+      const finishers = [await fundedKeypair(), await fundedKeypair(), await fundedKeypair(), await fundedKeypair()];
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 142);
+      await program.methods
+        .setMaxWinners(3)
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const finish = (player: Keypair) =>
+        program.methods
+          .setWinnerForUser(player.publicKey, 3)
+          .accountsPartial({ quizSet, setter: owner.publicKey, systemProgram: SystemProgram.programId })
+          .signers([owner])
+          .rpc({ commitment: "confirmed" });
+
+      for (const player of finishers.slice(0, 3)) {
+        await finish(player);
+      }
+      try {
+        await finish(finishers[3]);
+        expect.fail("the fourth finisher should find every slot taken");
+      } catch (error: any) {
+        expect(error.toString()).to.include("WinnersFull");
+      }
+
+      const quiz = await program.account.quizSet.fetch(quizSet);
+      expect(quiz.winner!.toString()).to.equal(finishers[0].publicKey.toString());
+      expect(quiz.coWinners.map((key) => key.toString())).to.deep.equal(
+        finishers.slice(1, 3).map((player) => player.publicKey.toString())
+      );
+
+      // 10_000_000 / 3 each; the first finisher also takes the 1-lamport remainder
+      const shares: number[] = [];
+      for (const player of finishers.slice(0, 3)) {
+        const claimed = awaitEvent("rewardClaimed");
+        await claimReward(player, quizSet);
+        shares.push((await claimed).rewardAmount.toNumber());
+      }
+      expect(shares).to.deep.equal([3_333_334, 3_333_333, 3_333_333]);
+
+      for (const player of [finishers[0], finishers[3]]) {
+        try {
+          await claimReward(player, quizSet);
+          expect.fail("each winner claims once and only winners claim");
+        } catch (error: any) {
+          expect(error.toString()).to.match(/ClaimReceiptExists|NotWinner|already in use/);
+        }
+      }
+    });
+
+    it("Should keep later winners' shares when the first finisher claims early", async () => {
+      const owner = await fundedKeypair();
+      const early = await fundedKeypair();
+      const late = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 143);
+      await program.methods
+        .setMaxWinners(3)
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const finish = (player: Keypair) =>
+        program.methods
+          .setWinnerForUser(player.publicKey, 3)
+          .accountsPartial({ quizSet, setter: owner.publicKey, systemProgram: SystemProgram.programId })
+          .signers([owner])
+          .rpc({ commitment: "confirmed" });
+      const claim = async (player: Keypair) => {
+        const claimed = awaitEvent("rewardClaimed");
+        await claimReward(player, quizSet);
+        return (await claimed).rewardAmount.toNumber();
+      };
+
+      // The first finisher claims before anyone else finishes and still gets a third
+      await finish(early);
+      expect(await claim(early)).to.equal(3_333_334);
+
+      // A later finisher can still join and is paid the same share
+      await finish(late);
+      expect(await claim(late)).to.equal(3_333_333);
+
+      // The third share, never won, stays in the vault
+      const quiz = await program.account.quizSet.fetch(quizSet);
+      expect(quiz.coWinners.map((key) => key.toString())).to.deep.equal([late.publicKey.toString()]);
+      expect(await provider.connection.getBalance(vaultAddress(quizSet))).to.be.at.least(3_333_333);
+    });
+  });
+
+  describe("Answer Attempt Limits", () => {
+    const limitedQuiz = async (owner: Keypair, uniqueId: number, maxAttempts: number, cooldownSeconds: number) => {
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, uniqueId);
+      await program.methods
+        .setAttemptLimits(maxAttempts, new BN(cooldownSeconds))
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+      return quizSet;
+    };
+
+    it("Should reject the attempt after max_attempts on a question", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const quizSet = await limitedQuiz(owner, 143, 2, 0);
+
+      for (const guess of ["aaaa", "bbbb"]) {
+        const offset = await submitAnswer(player, quizSet, 1, guess);
+        await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+      }
+
+      try {
+        await submitAnswer(player, quizSet, 1, "cccc");
+        expect.fail("a third attempt should be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("TooManyAttempts");
+      }
+
+      // The limit is per question
+      const offset = await submitAnswer(player, quizSet, 2, "answer");
+      await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+      const progress = await program.account.playerProgress.fetch(playerProgressAddress(player.publicKey, quizSet));
+      expect(progress.attempts.slice(0, 3)).to.deep.equal([2, 1, 0]);
+    });
+
+    it("Should reject a retry made before the cooldown elapses", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const quizSet = await limitedQuiz(owner, 144, 0, 60);
+
+      const offset = await submitAnswer(player, quizSet, 1, "aaaa");
+      await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+
+      try {
+        await submitAnswer(player, quizSet, 1, "bbbb");
+        expect.fail("a retry inside the cooldown should be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("AttemptCooldown");
+      }
+    });
+  });
+
+  describe("Stale Computation Results", () => {
+    const queueThenWin = async (uniqueId: number, graceSeconds?: number) => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const winner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, uniqueId);
+      if (graceSeconds !== undefined) {
+        await program.methods
+          .setResultGrace(new BN(graceSeconds))
+          .accountsPartial({ quizSet, authority: owner.publicKey })
+          .signers([owner])
+          .rpc();
+      }
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      // The quiz is won while the player's computation is still in flight
+      const offset = await submitAnswer(player, quizSet, 1, "answer");
+      await program.methods
+        .setWinnerForUser(winner.publicKey, 3)
+        .accountsPartial({ quizSet, setter: owner.publicKey, systemProgram: SystemProgram.programId })
+        .signers([owner])
+        .rpc({ commitment: "confirmed" });
+      return { quizSet, player, offset };
+    };
+
+    it("Should ignore a result that lands after the quiz was won", async () => {
+      const stale = awaitEvent("staleComputation");
+      const verified = awaitEvent("answerVerifiedEvent");
+      const { quizSet, player, offset } = await queueThenWin(145);
+      await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+
+      const event = await stale;
+      expect(event.quizSet.toString()).to.equal(quizSet.toString());
+      expect(event.player.toString()).to.equal(player.publicKey.toString());
+      expect(event.questionIndex).to.equal(1);
+
+      // Clients waiting on the submission's offset still get a result
+      const result = await verified;
+      expect(result.isStale).to.equal(true);
+      expect(result.computationOffset.toString()).to.equal(offset.toString());
+
+      const progress = await program.account.playerProgress.fetch(playerProgressAddress(player.publicKey, quizSet));
+      expect(progress.correctCount).to.equal(0);
+      expect(progress.scoredMask.toNumber()).to.equal(0);
+    });
+
+    it("Should still count the result within the configured grace", async () => {
+      const { quizSet, player, offset } = await queueThenWin(146, 600);
+      await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+
+      const progress = await program.account.playerProgress.fetch(playerProgressAddress(player.publicKey, quizSet));
+      expect(progress.correctCount).to.equal(1);
+    });
+  });
+
+  describe("Question Categories", () => {
+    it("Should tally scores per category at completion", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 147, 5);
+      // Questions 1-3 are history (1), 4-5 geography (2)
+      await addQuestionBlocks(owner, quizSet, 5, undefined, [], [1, 1, 1, 2, 2]);
+
+      const quiz = await program.account.quizSet.fetch(quizSet);
+      expect(quiz.categoryTotals.slice(0, 3)).to.deep.equal([0, 3, 2]);
+
+      const scores = awaitEvent("categoryScores");
+      for (const [questionIndex, text] of [[1, "answer"], [2, "answer"], [3, "answer"], [4, "nope"], [5, "answer"]] as [number, string][]) {
+        const offset = await submitAnswer(player, quizSet, questionIndex, text);
+        await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+      }
+
+      // 3/3 in history, 1/2 in geography
+      const event = await scores;
+      expect(event.player.toString()).to.equal(player.publicKey.toString());
+      expect(event.correct.slice(0, 3)).to.deep.equal([0, 3, 1]);
+      expect(event.totals.slice(0, 3)).to.deep.equal([0, 3, 2]);
+
+      const progress = await program.account.playerProgress.fetch(playerProgressAddress(player.publicKey, quizSet));
+      expect(progress.categoryCorrect.slice(0, 3)).to.deep.equal([0, 3, 1]);
+    });
+  });
+
+  describe("Aggregated User Stats", () => {
+    it("Should sum the user's per-topic scores passed as remaining accounts", async () => {
+      const owner = await fundedKeypair();
+      const firstTopic = await createTopic(owner, `Aggregate A ${Date.now() % 1_000_000}`);
+      const secondTopic = await createTopic(owner, `Aggregate B ${Date.now() % 1_000_000}`);
+      const rewardAmount = 10_000_000;
+      const firstQuiz = await createQuiz(owner, firstTopic, 148, 3, new BN(rewardAmount));
+      const secondQuiz = await createQuiz(owner, secondTopic, 149);
+      await addQuestionBlocks(owner, firstQuiz, 3);
+
+      await program.methods
+        .setWinnerForDevnet(["a", "b", "c"], ["a", "b", "c"])
+        .accountsPartial({ quizSet: firstQuiz, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await claimReward(owner, firstQuiz);
+
+      await recordCompletion(owner, firstQuiz, firstTopic, new BN(rewardAmount));
+      await recordCompletion(owner, secondQuiz, secondTopic);
+
+      const [globalStats] = PublicKey.findProgramAddressSync(
+        [Buffer.from("global"), owner.publicKey.toBuffer()],
+        program.programId
+      );
+      const userScores = [firstTopic, secondTopic].map((topic) => userScoreAddress(owner.publicKey, topic));
+      const getStats = (scores: PublicKey[]) =>
+        program.methods
+          .getUserGlobalStats()
+          .accountsPartial({ user: owner.publicKey, globalStats })
+          .remainingAccounts(scores.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })))
+          .rpc();
+
+      const event = awaitEvent("userGlobalStats");
+      await getStats(userScores);
+      const emitted = await event;
+      expect(emitted.topicCount).to.equal(2);
+      expect(emitted.topicScore).to.equal(1);
+      expect(emitted.topicCompletions).to.equal(2);
+      expect(emitted.topicRewards.toNumber()).to.equal(rewardAmount);
+
+      try {
+        await getStats([userScores[0], userScores[0]]);
+        expect.fail("a topic passed twice should be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidUserScore");
+      }
+    });
+  });
+
+  describe("Prefunded Answers", () => {
+    it("Should cover the next answer with the fee prepaid on the previous one", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 150);
+      const answerFee = 1_000_000;
+
+      await program.methods
+        .setAnswerFee(new BN(answerFee))
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const collected: any[] = [];
+      const listenerId = program.addEventListener("answerFeeCollected", (event: any) => collected.push(event));
+
+      await submitAnswer(player, quizSet, 1, "answer", true);
+      const progress = await program.account.playerProgress.fetch(playerProgressAddress(player.publicKey, quizSet));
+      expect(progress.prepaidAnswers).to.equal(1);
+
+      await submitAnswer(player, quizSet, 2, "answer");
+
+      for (let i = 0; i < 20 && collected.length < 2; i++) {
+        await sleep(500);
+      }
+      await program.removeEventListener(listenerId);
+
+      expect(collected).to.have.length(2);
+      expect(collected[0].amount.toNumber()).to.equal(2 * answerFee);
+      expect(collected[0].usedPrepaid).to.equal(false);
+      expect(collected[1].amount.toNumber()).to.equal(0);
+      expect(collected[1].usedPrepaid).to.equal(true);
+      expect(collected[1].prepaidAnswers).to.equal(0);
+
+      const after = await program.account.playerProgress.fetch(playerProgressAddress(player.publicKey, quizSet));
+      expect(after.prepaidAnswers).to.equal(0);
+    });
+  });
+
+  describe("Authority Participation", () => {
+    it("Should keep the authority from answering or winning their own quiz by default", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner, undefined, false);
+      const quizSet = await createQuiz(owner, topicPda, 151);
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      expect((await program.account.quizSet.fetch(quizSet)).allowAuthorityPlay).to.equal(false);
+
+      // The owner can't grant themselves authority play without the program admin
+      try {
+        await program.methods
+          .setTopicAllowAuthorityPlay(true)
+          .accountsPartial({
+            topic: topicPda,
+            owner: owner.publicKey,
+            programConfig: programConfigAddress,
+            admin: owner.publicKey,
+          })
+          .signers([owner])
+          .rpc();
+        expect.fail("the program admin must co-sign");
+      } catch (error: any) {
+        expect(error.toString()).to.include("Unauthorized");
+      }
+
+      try {
+        await submitAnswer(owner, quizSet, 1, "answer");
+        expect.fail("the authority knows the answers");
+      } catch (error: any) {
+        expect(error.toString()).to.include("AuthorityCannotParticipate");
+      }
+
+      try {
+        await program.methods
+          .setWinnerForDevnet(["a", "b", "c"], ["a", "b", "c"])
+          .accountsPartial({ quizSet, authority: owner.publicKey })
+          .signers([owner])
+          .rpc();
+        expect.fail("the authority can't make themselves the winner");
+      } catch (error: any) {
+        expect(error.toString()).to.include("AuthorityCannotParticipate");
+      }
+
+      try {
+        await program.methods
+          .setWinnerForUser(owner.publicKey, 3)
+          .accountsPartial({ quizSet, setter: owner.publicKey })
+          .signers([owner])
+          .rpc();
+        expect.fail("the authority can't record themselves as the winner");
+      } catch (error: any) {
+        expect(error.toString()).to.include("AuthorityCannotParticipate");
+      }
+
+      await submitAnswer(player, quizSet, 1, "answer");
+      const progress = await program.account.playerProgress.fetch(playerProgressAddress(player.publicKey, quizSet));
+      expect(progress.submissionCount).to.equal(1);
+    });
+
+    it("Should let the authority play when the topic allows it", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 152);
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      expect((await program.account.quizSet.fetch(quizSet)).allowAuthorityPlay).to.equal(true);
+
+      await submitAnswer(owner, quizSet, 1, "answer");
+      await program.methods
+        .setWinnerForDevnet(["a", "b", "c"], ["a", "b", "c"])
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+
+      const quiz = await program.account.quizSet.fetch(quizSet);
+      expect(quiz.winner.toString()).to.equal(owner.publicKey.toString());
+    });
+  });
+
+  describe("Normalized Answer Comparison", () => {
+    it("Should accept answers that differ only in case or surrounding spaces", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 153);
+
+      for (let i = 1; i <= 3; i++) {
+        const nonce = new BN(987654321 + i);
+        await program.methods
+          .addEncryptedQuestionBlock(
+            i,
+            Array.from(new Uint8Array(64).fill(i)),
+            encryptAnswer("paris", nonce),
+            Array.from(new Uint8Array(32).fill(i + 2)),
+            nonce,
+            0,
+            false,
+            0
+          )
+          .accountsPartial({
+            questionBlock: questionBlockAddress(quizSet, i),
+            quizSet,
+            authority: owner.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([owner])
+          .rpc();
+      }
+
+      for (const [answer, expected] of [
+        ["Paris", true],
+        ["paris", true],
+        [" PARIS ", true],
+        ["pa ris", false],
+      ] as [string, boolean][]) {
+        const player = await fundedKeypair();
+        const verified = awaitEvent("answerVerifiedEvent");
+        const offset = await submitAnswer(player, quizSet, 1, answer);
+        await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+        expect((await verified).isCorrect, answer).to.equal(expected);
+      }
+    });
+  });
+
+  describe("Batch Question Blocks", () => {
+    it("Should create five question blocks in a single transaction", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 154, 5);
+      const arciumPubkey = Array.from(new Uint8Array(32).fill(7));
+
+      const payloads = [1, 2, 3, 4, 5].map((i) => {
+        const nonce = new BN(555000 + i);
+        return {
+          questionIndex: i,
+          encryptedXCoordinate: Array.from(new Uint8Array(64).fill(i)),
+          encryptedYCoordinate: encryptAnswer("answer", nonce),
+          nonce,
+          weight: 0,
+          isBonus: false,
+          category: i % 2,
+        };
+      });
+
+      await program.methods
+        .addEncryptedQuestionBlocksBatch(arciumPubkey, payloads)
+        .accountsPartial({ quizSet, topic: topicPda, authority: owner.publicKey })
+        .remainingAccounts(
+          payloads.map((payload) => ({
+            pubkey: questionBlockAddress(quizSet, payload.questionIndex),
+            isSigner: false,
+            isWritable: true,
+          }))
+        )
+        .signers([owner])
+        .rpc();
+
+      for (const payload of payloads) {
+        const block = await program.account.questionBlock.fetch(questionBlockAddress(quizSet, payload.questionIndex));
+        expect(block.quizSet.toString()).to.equal(quizSet.toString());
+        expect(block.questionIndex).to.equal(payload.questionIndex);
+        expect(block.encryptedXCoordinate).to.deep.equal(payload.encryptedXCoordinate);
+        expect(block.encryptedYCoordinate).to.deep.equal(payload.encryptedYCoordinate);
+        expect(block.arciumPubkey).to.deep.equal(arciumPubkey);
+        expect(block.nonce.toString()).to.equal(payload.nonce.toString());
+        expect(block.category).to.equal(payload.category);
+      }
+
+      const quiz = await program.account.quizSet.fetch(quizSet);
+      expect(quiz.isInitialized).to.equal(true);
+      expect(quiz.categoryTotals.slice(0, 2)).to.deep.equal([2, 3]);
+    });
+
+    it("Should reject payloads that are out of order", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 155);
+      const payloads = [2, 1].map((i) => ({
+        questionIndex: i,
+        encryptedXCoordinate: Array.from(new Uint8Array(64).fill(i)),
+        encryptedYCoordinate: encryptAnswer("answer", new BN(i)),
+        nonce: new BN(i),
+        weight: 0,
+        isBonus: false,
+        category: 0,
+      }));
+
+      try {
+        await program.methods
+          .addEncryptedQuestionBlocksBatch(Array.from(new Uint8Array(32)), payloads)
+          .accountsPartial({ quizSet, topic: topicPda, authority: owner.publicKey })
+          .remainingAccounts(
+            payloads.map((payload) => ({
+              pubkey: questionBlockAddress(quizSet, payload.questionIndex),
+              isSigner: false,
+              isWritable: true,
+            }))
+          )
+          .signers([owner])
+          .rpc();
+        expect.fail("indices must be strictly increasing");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidQuestionIndex");
+      }
+    });
+  });
+
+  describe("Speed Bracket Rewards", () => {
+    it("Should pay each winner the share of their completion-time bracket", async () => {
+      const owner = await fundedKeypair();
+      const fast = await fundedKeypair();
+      const slow = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 156);
+      await program.methods
+        .setMaxWinners(2)
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      // Full share within 20 seconds, half after that
+      await program.methods
+        .setRewardBrackets([
+          { maxSeconds: 20, bps: 10_000 },
+          { maxSeconds: 4_294_967_295, bps: 5_000 },
+        ])
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const answerAll = async (player: Keypair, questionIndices: number[]) => {
+        const offsets: BN[] = [];
+        for (const questionIndex of questionIndices) {
+          offsets.push(await submitAnswer(player, quizSet, questionIndex, "answer"));
+        }
+        for (const offset of offsets) {
+          await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+        }
+      };
+
+      // The slow player starts first and finishes well past the first bracket
+      await answerAll(slow, [1]);
+      await sleep(21_000);
+      await answerAll(fast, [1, 2, 3]);
+      await answerAll(slow, [2, 3]);
+
+      const quiz = await program.account.quizSet.fetch(quizSet);
+      expect(quiz.winner!.toString()).to.equal(fast.publicKey.toString());
+      expect(quiz.coWinners.map((key) => key.toString())).to.deep.equal([slow.publicKey.toString()]);
+
+      const claimed: Record<string, number> = {};
+      for (const player of [fast, slow]) {
+        const event = awaitEvent("rewardClaimed");
+        await claimReward(player, quizSet, undefined, undefined, playerProgressAddress(player.publicKey, quizSet));
+        claimed[player.publicKey.toString()] = (await event).rewardAmount.toNumber();
+      }
+
+      // 10_000_000 split two ways; the slow winner's half share leaves 2_500_000 in the vault
+      expect(claimed[fast.publicKey.toString()]).to.equal(5_000_000);
+      expect(claimed[slow.publicKey.toString()]).to.equal(2_500_000);
+    });
+  });
+
+  describe("Minimum Participants", () => {
+    it("Should hold the winner's claim until enough players have taken part", async () => {
+      const owner = await fundedKeypair();
+      const winner = await fundedKeypair();
+      const latecomer = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 157);
+      await program.methods
+        .setMinParticipants(2)
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      // Repeat submissions from the same player count once
+      await submitAnswer(winner, quizSet, 1, "answer");
+      await submitAnswer(winner, quizSet, 2, "answer");
+      expect((await program.account.quizSet.fetch(quizSet)).participantCount).to.equal(1);
+
+      await program.methods
+        .setWinnerForUser(winner.publicKey, 3)
+        .accountsPartial({ quizSet, setter: owner.publicKey, systemProgram: SystemProgram.programId })
+        .signers([owner])
+        .rpc();
+
+      try {
+        await claimReward(winner, quizSet);
+        expect.fail("one participant is below the minimum");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InsufficientParticipants");
+      }
+
+      await submitAnswer(latecomer, quizSet, 1, "answer");
+      expect((await program.account.quizSet.fetch(quizSet)).participantCount).to.equal(2);
+
+      const claimed = awaitEvent("rewardClaimed");
+      await claimReward(winner, quizSet);
+      expect((await claimed).winner.toString()).to.equal(winner.publicKey.toString());
+    });
+
+    it("Should pay a held-back winner after the deadline instead of refunding", async () => {
+      const owner = await fundedKeypair();
+      const winner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const deadline = Math.floor(Date.now() / 1000) + 30;
+      const quizSet = await createQuiz(owner, topicPda, 170, 3, new BN(10_000_000), new BN(10_000_000), new BN(deadline));
+      await program.methods
+        .setMinParticipants(2)
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      for (const questionIndex of [1, 2, 3]) {
+        const offset = await submitAnswer(winner, quizSet, questionIndex, "answer");
+        await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+      }
+
+      await sleep(Math.max(0, (deadline + 2) * 1000 - Date.now()));
+
+      // The winner is still owed the reward, so the authority can't take it back
+      try {
+        await program.methods
+          .refundUnclaimedReward()
+          .accountsPartial({ quizSet, vault: vaultAddress(quizSet), authority: owner.publicKey })
+          .signers([owner])
+          .rpc();
+        expect.fail("a refund must not take an unpaid winner's reward");
+      } catch (error: any) {
+        expect(error.toString()).to.include("WinnerAlreadySet");
+      }
+
+      const claimed = awaitEvent("rewardClaimed");
+      await claimReward(winner, quizSet);
+      expect((await claimed).rewardAmount.toNumber()).to.equal(10_000_000);
+    });
+  });
+
+  describe("Empty Question Rejection", () => {
+    it("Should refuse to encrypt a question with no text", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 158);
+      await addQuestionBlocks(owner, quizSet, 1);
+      const options = ["Paris", "London", "Berlin", "Madrid"];
+
+      const computationOffset = newComputationOffset();
+      try {
+        await program.methods
+          .encryptQuizData(computationOffset, "", options as any, options[0], new BN(42), 0)
+          .accountsPartial({
+            payer: owner.publicKey,
+            questionBlock: questionBlockAddress(quizSet, 1),
+            ...queueAccounts(computationOffset, "encrypt_quiz"),
+          })
+          .signers([owner])
+          .rpc();
+        expect.fail("an empty question should be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("EmptyQuestion");
+      }
+    });
+  });
+
+  describe("Claim Proof", () => {
+    it("Should reject a claim that isn't co-signed by the registered proof key", async () => {
+      const owner = await fundedKeypair();
+      const winner = await fundedKeypair();
+      const proofKey = Keypair.generate();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 159);
+      await program.methods
+        .setRequireClaimProof(true)
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      await program.methods
+        .registerProofKey(proofKey.publicKey)
+        .accountsPartial({ quizSet, player: winner.publicKey })
+        .signers([winner])
+        .rpc();
+      await program.methods
+        .setWinnerForUser(winner.publicKey, 3)
+        .accountsPartial({ quizSet, setter: owner.publicKey, systemProgram: SystemProgram.programId })
+        .signers([owner])
+        .rpc();
+
+      const progress = playerProgressAddress(winner.publicKey, quizSet);
+      try {
+        await claimReward(winner, quizSet, undefined, undefined, progress);
+        expect.fail("the proof key has to co-sign");
+      } catch (error: any) {
+        expect(error.toString()).to.include("ClaimProofMissing");
+      }
+
+      const claimed = awaitEvent("rewardClaimed");
+      await program.methods
+        .claimReward(null)
+        .accountsPartial({
+          quizSet,
+          vault: vaultAddress(quizSet),
+          claimer: winner.publicKey,
+          playerProgress: progress,
+          proofSigner: proofKey.publicKey,
+          authorQuota: authorQuotaAddress(owner.publicKey),
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(questionBlockMetas(quizSet, [1, 2, 3]))
+        .signers([winner, proofKey])
+        .rpc();
+      expect((await claimed).winner.toString()).to.equal(winner.publicKey.toString());
+    });
+  });
+
+  describe("Answer Submitted Event", () => {
+    it("Should emit a submission event whose offset matches the verification event", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 160);
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const submitted = awaitEvent("answerSubmittedEvent");
+      const verified = awaitEvent("answerVerifiedEvent");
+      const offset = await submitAnswer(player, quizSet, 1, "answer");
+
+      const submission = await submitted;
+      expect(submission.user.toString()).to.equal(player.publicKey.toString());
+      expect(submission.quizSet.toString()).to.equal(quizSet.toString());
+      expect(submission.questionIndex).to.equal(1);
+      expect(submission.computationOffset.toString()).to.equal(offset.toString());
+
+      await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+      const result = await verified;
+      expect(result.computationOffset.toString()).to.equal(submission.computationOffset.toString());
+      expect(result.isCorrect).to.equal(true);
+    });
+  });
+
+  describe("Proportional Rewards", () => {
+    const proportionalQuiz = async (owner: Keypair, uniqueId: number, deadline = new BN(0)) => {
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, uniqueId, 3, new BN(10_000_000), new BN(10_000_000), deadline);
+      await program.methods
+        .setRewardMode({ proportional: {} })
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await addQuestionBlocks(owner, quizSet, 3);
+      return { topicPda, quizSet };
+    };
+
+    // Answer the given questions, right or wrong, and record the resulting score
+    const play = async (player: Keypair, quizSet: PublicKey, topicPda: PublicKey, answers: string[]) => {
+      await answerQuestions(player, quizSet, answers);
+      return recordCompletion(player, quizSet, topicPda);
+    };
+
+    const claim = async (player: Keypair, quizSet: PublicKey, quizHistory: PublicKey) => {
+      const claimed = awaitEvent("rewardClaimed");
+      await claimReward(player, quizSet, undefined, undefined, playerProgressAddress(player.publicKey, quizSet), quizHistory);
+      return (await claimed).rewardAmount.toNumber();
+    };
+
+    it("Should pay a 2/3-correct claimant two thirds of the reward", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const { topicPda, quizSet } = await proportionalQuiz(owner, 161);
+
+      const history = await play(player, quizSet, topicPda, ["answer", "answer", "wrong"]);
+      expect(await claim(player, quizSet, history)).to.equal(6_666_666);
+    });
+
+    it("Should stop paying once the pool is exhausted", async () => {
+      const owner = await fundedKeypair();
+      const players = [await fundedKeypair(), await fundedKeypair(), await fundedKeypair()];
+      const { topicPda, quizSet } = await proportionalQuiz(owner, 162);
+
+      const histories: PublicKey[] = [];
+      for (const player of players) {
+        histories.push(await play(player, quizSet, topicPda, ["answer", "answer", "wrong"]));
+      }
+
+      // Two thirds, then only what is left of the pool, then nothing
+      expect(await claim(players[0], quizSet, histories[0])).to.equal(6_666_666);
+      expect(await claim(players[1], quizSet, histories[1])).to.equal(3_333_334);
+      try {
+        await claim(players[2], quizSet, histories[2]);
+        expect.fail("the pool is already paid out");
+      } catch (error: any) {
+        expect(error.toString()).to.include("RewardPoolExhausted");
+      }
+
+      const quizSetAccount = await program.account.quizSet.fetch(quizSet);
+      expect(quizSetAccount.proportionalPaid.toNumber()).to.equal(10_000_000);
+      expect(quizSetAccount.isActive).to.equal(false);
+    });
+
+    it("Should refund what is left of the pool after the deadline", async () => {
+      const owner = await fundedKeypair();
+      const finisher = await fundedKeypair();
+      const player = await fundedKeypair();
+      const deadline = Math.floor(Date.now() / 1000) + 30;
+      const { topicPda, quizSet } = await proportionalQuiz(owner, 172, new BN(deadline));
+
+      // The full run sets a winner, but proportional quizzes have no winner share to hold back
+      await play(finisher, quizSet, topicPda, ["answer", "answer", "answer"]);
+      const history = await play(player, quizSet, topicPda, ["answer", "wrong", "wrong"]);
+      expect(await claim(player, quizSet, history)).to.equal(3_333_333);
+
+      await sleep(Math.max(0, (deadline + 2) * 1000 - Date.now()));
+
+      const refunded = awaitEvent("rewardRefunded");
+      await program.methods
+        .refundUnclaimedReward()
+        .accountsPartial({
+          quizSet,
+          vault: vaultAddress(quizSet),
+          authority: owner.publicKey,
+          authorQuota: authorQuotaAddress(owner.publicKey),
+        })
+        .signers([owner])
+        .rpc();
+      expect((await refunded).amount.toNumber()).to.equal(6_666_667);
+      expect((await program.account.quizSet.fetch(quizSet)).isActive).to.equal(false);
+    });
+  });
+
+  describe("Quiz Settlement", () => {
+    it("Should emit one settlement record once the reward is claimed", async () => {
+      const owner = await fundedKeypair();
+      const winner = await fundedKeypair();
+      const other = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 163);
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const finalize = () =>
+        program.methods
+          .finalizeSettlement()
+          .accountsPartial({ quizSet, caller: other.publicKey })
+          .remainingAccounts(questionBlockMetas(quizSet, [1, 2, 3]))
+          .signers([other])
+          .rpc();
+
+      // A wrong attempt on Q1, then a full correct run
+      let offset = await submitAnswer(other, quizSet, 1, "wrong");
+      await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+      for (const index of [1, 2, 3]) {
+        offset = await submitAnswer(winner, quizSet, index, "answer");
+        await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+      }
+
+      try {
+        await finalize();
+        expect.fail("the reward is still unclaimed");
+      } catch (error: any) {
+        expect(error.toString()).to.include("QuizNotSettled");
+      }
+
+      await claimReward(winner, quizSet);
+
+      const settled = awaitEvent("quizSettlement");
+      await finalize();
+      const settlement = await settled;
+
+      expect(settlement.quizSet.toString()).to.equal(quizSet.toString());
+      expect(settlement.participantCount).to.equal(2);
+      expect(settlement.winner.toString()).to.equal(winner.publicKey.toString());
+      expect(settlement.winnerClaimed).to.equal(true);
+      expect(settlement.rewardAmount.toNumber()).to.equal(10_000_000);
+      expect(
+        settlement.questionStats.map((stats: any) => [stats.questionIndex, stats.attemptCount, stats.correctCount])
+      ).to.deep.equal([
+        [1, 2, 1],
+        [2, 1, 1],
+        [3, 1, 1],
+      ]);
+
+      try {
+        await finalize();
+        expect.fail("the settlement was already emitted");
+      } catch (error: any) {
+        expect(error.toString()).to.include("SettlementAlreadyFinalized");
+      }
+    });
+  });
+
+  describe("Leaderboard Minimum Score", () => {
+    it("Should keep users below the minimum score off the leaderboard", async () => {
+      const owner = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 164);
+      await addQuestionBlocks(owner, quizSet, 3);
+      const [leaderboard] = PublicKey.findProgramAddressSync(
+        [Buffer.from("leaderboard"), topicPda.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .initTopicLeaderboard()
+        .accountsPartial({ leaderboard, topic: topicPda, owner: owner.publicKey, systemProgram: SystemProgram.programId })
+        .signers([owner])
+        .rpc();
+      await program.methods
+        .setLeaderboardMinScore(1)
+        .accountsPartial({ topic: topicPda, leaderboard, owner: owner.publicKey })
+        .signers([owner])
+        .rpc();
+
+      // One completion without a win (score 0), one with a win (score 1)
+      const lowScorer = await fundedKeypair();
+      const qualifier = await fundedKeypair();
+      await program.methods
+        .setWinnerForUser(qualifier.publicKey, 3)
+        .accountsPartial({ quizSet, setter: owner.publicKey, systemProgram: SystemProgram.programId })
+        .signers([owner])
+        .rpc();
+      await recordCompletion(lowScorer, quizSet, topicPda, new BN(0), leaderboard);
+      await recordCompletion(qualifier, quizSet, topicPda, new BN(0), leaderboard);
+
+      const entries = (await program.account.topicLeaderboard.fetch(leaderboard)).entries;
+      expect(entries.map((entry: any) => entry.user.toString())).to.deep.equal([qualifier.publicKey.toString()]);
+
+      // Raising the minimum drops existing entries below it right away
+      await program.methods
+        .setLeaderboardMinScore(2)
+        .accountsPartial({ topic: topicPda, leaderboard, owner: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      expect((await program.account.topicLeaderboard.fetch(leaderboard)).entries).to.have.length(0);
+    });
+  });
+
+  describe("Commit-Reveal Answers", () => {
+    it("Should only accept reveals after the commit deadline, and no commits after it", async () => {
+      const owner = await fundedKeypair();
+      const early = await fundedKeypair();
+      const late = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 167);
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const commitDeadline = Math.floor(Date.now() / 1000) + 5;
+      await program.methods
+        .setCommitDeadline(new BN(commitDeadline))
+        .accountsPartial({ quizSet, authority: owner.publicKey })
+        .signers([owner])
+        .rpc();
+
+      const salt = await commitAnswer(early, quizSet, 1, "answer");
+      try {
+        await revealAnswer(early, quizSet, 1, "answer", salt);
+        expect.fail("reveals should wait for the commit phase to close");
+      } catch (error: any) {
+        expect(error.toString()).to.include("CommitPhaseOpen");
+      }
+
+      await sleep(7000);
+
+      // A revealed answer can no longer be copied into a fresh commitment
+      try {
+        await commitAnswer(late, quizSet, 1, "answer");
+        expect.fail("commits should be closed");
+      } catch (error: any) {
+        expect(error.toString()).to.include("CommitPhaseClosed");
+      }
+
+      const verified = awaitEvent("answerVerifiedEvent");
+      const offset = await revealAnswer(early, quizSet, 1, "answer", salt);
+      await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+      expect((await verified).isCorrect).to.equal(true);
+    });
+
+    it("Should validate a revealed answer that matches its commitment", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 165);
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const salt = await commitAnswer(player, quizSet, 1, "answer");
+      const commitment = await program.account.answerCommitment.fetch(answerCommitmentAddress(player.publicKey, quizSet, 1));
+      expect(Buffer.from(commitment.commitment)).to.deep.equal(answerCommitmentHash("answer", salt, player.publicKey));
+
+      const verified = awaitEvent("answerVerifiedEvent");
+      const offset = await revealAnswer(player, quizSet, 1, "answer", salt);
+      await awaitComputationFinalization(provider, offset, program.programId, "confirmed");
+      expect((await verified).isCorrect).to.equal(true);
+
+      // The commitment is spent by the reveal
+      const closed = await program.account.answerCommitment.fetchNullable(answerCommitmentAddress(player.publicKey, quizSet, 1));
+      expect(closed).to.equal(null);
+    });
+
+    it("Should reject a tampered reveal and a reveal without a commitment", async () => {
+      const owner = await fundedKeypair();
+      const player = await fundedKeypair();
+      const topicPda = await createTopic(owner);
+      const quizSet = await createQuiz(owner, topicPda, 166);
+      await addQuestionBlocks(owner, quizSet, 3);
+
+      const salt = await commitAnswer(player, quizSet, 1, "guess");
+      try {
+        await revealAnswer(player, quizSet, 1, "answer", salt);
+        expect.fail("a reveal that differs from the commitment should be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("CommitmentMismatch");
+      }
+
+      try {
+        await revealAnswer(player, quizSet, 2, "answer", salt);
+        expect.fail("a reveal with no prior commitment should be rejected");
+      } catch (error: any) {
+        expect(error.toString()).to.include("AnswerNotCommitted");
+      }
     });
   });
-});
\ No newline at end of file
+});